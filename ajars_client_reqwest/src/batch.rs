@@ -0,0 +1,93 @@
+use std::convert::TryFrom;
+
+use ::reqwest::header::{HeaderName, HeaderValue};
+use ajars_core::{
+    AjarsError, BatchCall, BatchCalls, BatchRest, BatchResult, BatchResults, Batched, NoError, RestBatch, RestType,
+};
+use http::HeaderMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{AjarsClientReqwest, RequestBuilder, reqwest};
+
+impl AjarsClientReqwest {
+    /// Prepares a batch of calls, sent together in a single request to the batch endpoint.
+    pub fn batch<'a>(&self, rest: &'a RestBatch) -> BatchRequestBuilder<'a> {
+        BatchRequestBuilder { request: self.request(rest.rest()), calls: BatchCalls::default() }
+    }
+}
+
+/// The calls of a batch, sent in a single request.
+pub struct BatchRequestBuilder<'a> {
+    request: RequestBuilder<'a, Vec<BatchCall>, Vec<BatchResult>, BatchRest>,
+    calls: BatchCalls,
+}
+
+impl BatchRequestBuilder<'_> {
+    /// Adds a call to an endpoint without path parameters.
+    /// The returned handle reads the output of the call from the results of the batch.
+    pub fn call<I, O, REST>(
+        &mut self,
+        rest: &REST,
+        input: &I,
+    ) -> Result<Batched<O, REST::Error>, AjarsError<REST::Error, reqwest::Error>>
+    where
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        REST: RestType<I, O, PathParams = ()>,
+    {
+        self.calls.push(rest, &(), input)
+    }
+
+    /// Adds a call to an endpoint whose path template contains placeholders,
+    /// filled with the given path parameters.
+    pub fn call_with_path<I, O, REST>(
+        &mut self,
+        rest: &REST,
+        path_params: &REST::PathParams,
+        input: &I,
+    ) -> Result<Batched<O, REST::Error>, AjarsError<REST::Error, reqwest::Error>>
+    where
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        REST: RestType<I, O>,
+    {
+        self.calls.push(rest, path_params, input)
+    }
+
+    /// Sends all the calls in a single request and returns their results.
+    pub async fn send(self) -> Result<BatchResults<reqwest::Error>, AjarsError<NoError, reqwest::Error>> {
+        let calls = self.calls.len();
+        let results = self.request.send(&self.calls.into_calls()).await?;
+        BatchResults::new(calls, results).map_err(AjarsError::Decode)
+    }
+
+    /// Add a `Header` to the request of the batch.
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.request = self.request.header(key, value);
+        self
+    }
+
+    /// Add a set of Headers to the existing ones of the request of the batch.
+    ///
+    /// The headers will be merged in to any already set.
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.request = self.request.headers(headers);
+        self
+    }
+
+    /// Enable HTTP bearer authentication.
+    pub fn bearer_auth<T>(mut self, token: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        self.request = self.request.bearer_auth(token);
+        self
+    }
+}
@@ -0,0 +1,151 @@
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::ops::RangeBounds;
+
+use ::reqwest::StatusCode;
+use ::reqwest::header::{self, HeaderName, HeaderValue};
+use ajars_core::{AjarsError, ContentRange, Rest, RestDownload, content_disposition_file_name, range_header};
+use futures_util::stream::BoxStream;
+use futures_util::{StreamExt, TryStreamExt};
+use http::HeaderMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::reqwest;
+use crate::{AjarsClientReqwest, RequestBuilder, check_response};
+
+impl AjarsClientReqwest {
+    pub fn request_download<'a, I: Serialize + DeserializeOwned, E: Serialize + DeserializeOwned>(
+        &self,
+        rest: &'a RestDownload<I, (), E>,
+    ) -> DownloadRequestBuilder<'a, I, (), E> {
+        self.request_download_with_path(rest, &())
+    }
+
+    /// Prepares a request to a download endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_download_with_path<
+        'a,
+        I: Serialize + DeserializeOwned,
+        P: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &self,
+        rest: &'a RestDownload<I, P, E>,
+        path_params: &P,
+    ) -> DownloadRequestBuilder<'a, I, P, E> {
+        DownloadRequestBuilder { request: self.request_with_path(rest.rest(), path_params) }
+    }
+}
+
+pub struct DownloadRequestBuilder<
+    'a,
+    I: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+> {
+    request: RequestBuilder<'a, I, (), Rest<I, (), P, E>>,
+}
+
+impl<I, P, E> DownloadRequestBuilder<'_, I, P, E>
+where
+    I: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+{
+    /// Asks only for the given bytes of the content, e.g. `100..` to resume a download after its first 100 bytes.
+    pub fn range<R: RangeBounds<u64>>(self, range: R) -> Self {
+        self.header(header::RANGE, range_header(range))
+    }
+
+    /// Sends the Request to the target URL, returning a future that resolves
+    /// once the response headers are received.
+    ///
+    /// The content is not read yet: it can be read as a whole or as a stream from the returned response.
+    pub async fn send(self, data: &I) -> Result<DownloadResponse<E>, AjarsError<E, reqwest::Error>> {
+        let rest = self.request.rest;
+        let request = self.request.prepare(data)?;
+        let response = request.send().await.map_err(AjarsError::Transport)?;
+
+        // A '206 Partial Content' response answers a request with a `Range` header
+        let response = match response.status() {
+            StatusCode::PARTIAL_CONTENT => response,
+            _ => check_response(rest, response).await?,
+        };
+        Ok(DownloadResponse { response, phantom_e: PhantomData })
+    }
+
+    /// Add a `Header` to this Request.
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.request = self.request.header(key, value);
+        self
+    }
+
+    /// Add a set of Headers to the existing ones on this Request.
+    ///
+    /// The headers will be merged in to any already set.
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.request = self.request.headers(headers);
+        self
+    }
+
+    /// Enable HTTP bearer authentication.
+    pub fn bearer_auth<T>(mut self, token: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        self.request = self.request.bearer_auth(token);
+        self
+    }
+}
+
+/// The successful response of a download endpoint, whose content has not been read yet.
+pub struct DownloadResponse<E> {
+    response: reqwest::Response,
+    phantom_e: PhantomData<E>,
+}
+
+impl<E: Send + 'static> DownloadResponse<E> {
+    /// The `Content-Type` of the content.
+    pub fn content_type(&self) -> Option<&str> {
+        self.header(header::CONTENT_TYPE)
+    }
+
+    /// The name of the file suggested by the `Content-Disposition` header of the response.
+    pub fn file_name(&self) -> Option<String> {
+        self.header(header::CONTENT_DISPOSITION).and_then(content_disposition_file_name)
+    }
+
+    /// The bytes of the content sent by the response, if it answered a request with a range.
+    pub fn content_range(&self) -> Option<ContentRange> {
+        match self.response.status() {
+            StatusCode::PARTIAL_CONTENT => self.header(header::CONTENT_RANGE).and_then(ContentRange::parse),
+            _ => None,
+        }
+    }
+
+    /// The number of bytes of the body, if known in advance.
+    pub fn content_length(&self) -> Option<u64> {
+        self.response.content_length()
+    }
+
+    /// Reads the whole body.
+    pub async fn bytes(self) -> Result<Vec<u8>, AjarsError<E, reqwest::Error>> {
+        Ok(self.response.bytes().await.map_err(AjarsError::Transport)?.into())
+    }
+
+    /// Returns the chunks of the body as soon as they arrive.
+    pub fn stream(self) -> BoxStream<'static, Result<Vec<u8>, AjarsError<E, reqwest::Error>>> {
+        self.response.bytes_stream().map_ok(Vec::from).map_err(AjarsError::Transport).boxed()
+    }
+
+    fn header(&self, name: HeaderName) -> Option<&str> {
+        self.response.headers().get(name).and_then(|value| value.to_str().ok())
+    }
+}
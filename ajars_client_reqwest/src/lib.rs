@@ -1,14 +1,38 @@
 use std::convert::TryFrom;
+use std::future::Future;
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
 
 use ::reqwest::header::{HeaderName, HeaderValue};
-use ajars_core::{HttpMethod, RestType};
+use ajars_core::{
+    AjarsClient, AjarsError, AjarsRequestBuilder, CircuitBreaker, Codec, CodecError, HttpMethod, InputLocation,
+    Response, RestType, RetryPolicy, empty_body_output, head_output, is_empty_body,
+};
 use http::HeaderMap;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 
 use crate::reqwest::{Client, RequestBuilder as ReqwestRequestBuilder};
 
+mod batch;
+mod download;
+mod page;
+#[cfg(not(target_arch = "wasm32"))]
+mod sse;
+mod stream;
+#[cfg(feature = "upload")]
+mod upload;
+
+pub use batch::BatchRequestBuilder;
+pub use download::{DownloadRequestBuilder, DownloadResponse};
+pub use page::PaginateRequestBuilder;
+#[cfg(not(target_arch = "wasm32"))]
+pub use sse::{DEFAULT_RECONNECT_DELAY, SseRequestBuilder};
+pub use stream::StreamRequestBuilder;
+#[cfg(feature = "upload")]
+pub use upload::UploadRequestBuilder;
+
 pub mod reqwest {
     pub use ::reqwest::*;
 }
@@ -17,33 +41,116 @@ pub mod reqwest {
 pub struct AjarsClientReqwest {
     client: Client,
     base_url: String,
+    retry_policy: Arc<RetryPolicy>,
+    circuit_breaker: Option<CircuitBreaker>,
 }
 
 impl AjarsClientReqwest {
+    /// Creates a client that sends the requests to `base_url`.
+    /// Trailing slashes of the base URL are ignored, as the paths of the endpoints start with `/`.
     pub fn new<S: Into<String>>(client: Client, base_url: S) -> Self {
-        Self { client, base_url: base_url.into() }
+        Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            retry_policy: Arc::new(RetryPolicy::none()),
+            circuit_breaker: None,
+        }
+    }
+
+    /// Sets the policy used to send again the requests to the idempotent endpoints that fail
+    /// for a transient reason. By default, the requests are never retried.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Arc::new(retry_policy);
+        self
+    }
+
+    /// Sets the circuit breaker that stops sending the requests to a server that keeps failing.
+    /// Its circuits are keyed by the base URL of the client or by endpoint, see [`CircuitBreaker::circuit`].
+    /// By default, the requests are always sent.
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
     }
 
-    pub fn request<'a, I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+    pub fn request<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        REST: RestType<I, O, PathParams = ()>,
+    >(
         &self,
         rest: &'a REST,
     ) -> RequestBuilder<'a, I, O, REST> {
-        let url = format!("{}{}", &self.base_url, rest.path());
+        self.request_with_path(rest, &())
+    }
+
+    /// Prepares a request to an endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_with_path<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        REST: RestType<I, O>,
+    >(
+        &self,
+        rest: &'a REST,
+        path_params: &REST::PathParams,
+    ) -> RequestBuilder<'a, I, O, REST> {
+        let url = format!("{}{}", &self.base_url, rest.path_with(path_params));
 
         let request = match rest.method() {
             HttpMethod::DELETE => self.client.delete(&url),
             HttpMethod::GET => self.client.get(&url),
+            HttpMethod::HEAD => self.client.head(&url),
+            HttpMethod::OPTIONS => self.client.request(reqwest::Method::OPTIONS, &url),
+            HttpMethod::PATCH => self.client.patch(&url),
             HttpMethod::POST => self.client.post(&url),
             HttpMethod::PUT => self.client.put(&url),
         };
 
-        RequestBuilder { rest, request, phantom_i: PhantomData, phantom_o: PhantomData }
+        RequestBuilder {
+            rest,
+            request,
+            retry_policy: self.retry_policy.clone(),
+            circuit: self
+                .circuit_breaker
+                .as_ref()
+                .map(|breaker| (breaker.clone(), breaker.circuit(&self.base_url, rest))),
+            phantom_i: PhantomData,
+            phantom_o: PhantomData,
+        }
+    }
+}
+
+impl AjarsClient for AjarsClientReqwest {
+    type Error = reqwest::Error;
+    type RequestBuilder<'a, I, O, REST>
+        = RequestBuilder<'a, I, O, REST>
+    where
+        I: Serialize + DeserializeOwned + 'a,
+        O: Serialize + DeserializeOwned + 'a,
+        REST: RestType<I, O> + 'a;
+
+    fn request_with_path<'a, I, O, REST>(
+        &'a self,
+        rest: &'a REST,
+        path_params: &REST::PathParams,
+    ) -> RequestBuilder<'a, I, O, REST>
+    where
+        I: Serialize + DeserializeOwned + 'a,
+        O: Serialize + DeserializeOwned + 'a,
+        REST: RestType<I, O> + 'a,
+    {
+        AjarsClientReqwest::request_with_path(self, rest, path_params)
     }
 }
 
 pub struct RequestBuilder<'a, I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>> {
     rest: &'a REST,
     request: ReqwestRequestBuilder,
+    retry_policy: Arc<RetryPolicy>,
+    /// The circuit breaker of the client along with the circuit of the request
+    circuit: Option<(CircuitBreaker, String)>,
     phantom_i: PhantomData<I>,
     phantom_o: PhantomData<O>,
 }
@@ -53,13 +160,69 @@ impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: Res
 {
     /// Sends the Request to the target URL, returning a
     /// future Response.
-    pub async fn send(self, data: &I) -> Result<O, reqwest::Error> {
-        let request = match self.rest.method() {
-            HttpMethod::DELETE | HttpMethod::GET => self.request.query(data),
-            HttpMethod::POST | HttpMethod::PUT => self.request.header("Content-Type", "application/json").json(data),
-        };
+    pub async fn send(self, data: &I) -> Result<O, AjarsError<REST::Error, reqwest::Error>> {
+        self.send_full(data).await.map(Response::into_body)
+    }
+
+    /// Sends the Request to the target URL, returning a future Response
+    /// with the status and the headers of the HTTP response along with the output.
+    pub async fn send_full(self, data: &I) -> Result<Response<O>, AjarsError<REST::Error, reqwest::Error>> {
+        let rest = self.rest;
+        let response = self.execute(data).await?;
+        let status = response.status().as_u16().into();
+        let headers = header_pairs(response.headers());
+        Ok(Response::new(status, headers, read_output(rest, response).await?))
+    }
+
+    /// Sends the request and returns the response if its status is the success status of the endpoint.
+    /// The request is sent again, as long as the retry policy allows it, if it fails for a transient reason.
+    async fn execute(self, data: &I) -> Result<reqwest::Response, AjarsError<REST::Error, reqwest::Error>> {
+        let rest = self.rest;
+        let retry_policy = self.retry_policy.clone();
+        let circuit = self.circuit.clone();
+        // A header that is not valid, or an input that cannot be encoded in the URL, fails before any attempt
+        let (client, request) = self.prepare(data)?.build_split();
+        let request = request.map_err(AjarsError::Transport)?;
+        retry_policy
+            .run(
+                rest.is_idempotent(),
+                || {
+                    let request = try_clone_request(&request);
+                    let client = &client;
+                    let send = async move {
+                        let response = client.execute(request?).await.map_err(AjarsError::Transport)?;
+                        check_response(rest, response).await
+                    };
+                    // Every attempt goes through the circuit breaker, which fails fast while the circuit is open
+                    let circuit = &circuit;
+                    async move {
+                        match circuit {
+                            Some((breaker, circuit)) => breaker.run(circuit, send).await,
+                            None => send.await,
+                        }
+                    }
+                },
+                sleep,
+            )
+            .await
+    }
+
+    /// Validates the input and sets it in the request.
+    fn prepare(self, data: &I) -> Result<ReqwestRequestBuilder, AjarsError<REST::Error, reqwest::Error>> {
+        // An input that fails the validation of the endpoint is not sent
+        #[cfg(feature = "validator")]
+        self.rest.validate(data).map_err(AjarsError::Validation)?;
 
-        request.send().await?.json().await
+        // An empty input, like `()`, is sent without a query string and without a body
+        Ok(match self.rest.input_location() {
+            _ if is_empty_body(data) => self.request,
+            InputLocation::Query => self.request.query(data),
+            InputLocation::Body => self
+                .request
+                .header(reqwest::header::CONTENT_TYPE, REST::Codec::CONTENT_TYPE)
+                .body(REST::Codec::encode(data).map_err(AjarsError::Encode)?),
+            InputLocation::Form => self.request.form(data),
+        })
     }
 
     /// Add a `Header` to this Request.
@@ -82,7 +245,6 @@ impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: Res
         self
     }
 
-    #[cfg(not(target_arch = "wasm32"))]
     /// Enable HTTP basic authentication.
     pub fn basic_auth<U, P>(mut self, username: U, password: Option<P>) -> Self
     where
@@ -113,3 +275,103 @@ impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: Res
         self
     }
 }
+
+impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>
+    AjarsRequestBuilder<I, O, REST> for RequestBuilder<'_, I, O, REST>
+{
+    type Error = reqwest::Error;
+
+    fn header(self, name: &str, value: &str) -> Self {
+        RequestBuilder::header(self, name, value)
+    }
+
+    fn basic_auth(self, username: &str, password: Option<&str>) -> Self {
+        RequestBuilder::basic_auth(self, username, password)
+    }
+
+    fn bearer_auth(self, token: &str) -> Self {
+        RequestBuilder::bearer_auth(self, token)
+    }
+
+    fn send(self, input: &I) -> impl Future<Output = Result<O, AjarsError<REST::Error, reqwest::Error>>> {
+        RequestBuilder::send(self, input)
+    }
+
+    fn send_full(
+        self,
+        input: &I,
+    ) -> impl Future<Output = Result<Response<O>, AjarsError<REST::Error, reqwest::Error>>> {
+        RequestBuilder::send_full(self, input)
+    }
+}
+
+/// Reads the output of the endpoint from a successful response.
+async fn read_output<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+    rest: &REST,
+    response: reqwest::Response,
+) -> Result<O, AjarsError<REST::Error, reqwest::Error>> {
+    // A response to a HEAD request never has a body
+    if *rest.method() == HttpMethod::HEAD {
+        return head_output().map_err(AjarsError::Decode);
+    }
+
+    let body = response.bytes().await.map_err(AjarsError::Transport)?;
+
+    // An empty body, like the one of a '204 No Content' response, carries an empty output such as `()`
+    if body.is_empty()
+        && let Some(output) = empty_body_output()
+    {
+        return Ok(output);
+    }
+    REST::Codec::decode(&body).map_err(AjarsError::Decode)
+}
+
+/// Returns the response if its status is the success status of the endpoint.
+async fn check_response<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+    rest: &REST,
+    response: reqwest::Response,
+) -> Result<reqwest::Response, AjarsError<REST::Error, reqwest::Error>> {
+    // The body of an error status is read before decoding anything, as it may not be encoded with the codec
+    let status = response.status().as_u16();
+    if response.status().is_client_error() || response.status().is_server_error() {
+        let headers = header_pairs(response.headers());
+        let body = response.bytes().await.map_err(AjarsError::Transport)?;
+        return Err(AjarsError::from_error_response::<REST::Codec>(status, rest.error_status(), headers, body.into()));
+    }
+
+    // A successful status other than the one declared by the endpoint breaks the contract of the endpoint
+    if !rest.accepts_success_status(status) {
+        return Err(AjarsError::UnexpectedStatus { status: status.into(), expected: rest.success_status().into() });
+    }
+    Ok(response)
+}
+
+/// Clones a built request to send it, once for each attempt.
+pub(crate) fn try_clone_request<E>(
+    request: &reqwest::Request,
+) -> Result<reqwest::Request, AjarsError<E, reqwest::Error>> {
+    // Only a streaming body cannot be cloned, and the inputs are always encoded in memory
+    request.try_clone().ok_or_else(|| {
+        AjarsError::Encode(CodecError::new("The body of the request is a stream that cannot be sent again"))
+    })
+}
+
+/// Waits before retrying a request.
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(delay: Duration) {
+    tokio::time::sleep(delay).await
+}
+
+/// Waits before retrying a request.
+#[cfg(target_arch = "wasm32")]
+async fn sleep(delay: Duration) {
+    gloo_timers::future::sleep(delay).await
+}
+
+/// Returns the headers of a response as name and value pairs; the values that are not valid strings are skipped.
+fn header_pairs(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| value.to_str().ok().map(|value| (name.to_string(), value.to_owned())))
+        .collect()
+}
@@ -0,0 +1,209 @@
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use ::reqwest::header::{HeaderName, HeaderValue};
+use ajars_core::{AjarsError, LAST_EVENT_ID_HEADER, Rest, RestSse, SSE_CONTENT_TYPE, SseDecoder, SseEvent};
+use futures_util::stream::{self, BoxStream, StreamExt};
+use http::HeaderMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::reqwest::{self, Client};
+use crate::{AjarsClientReqwest, RequestBuilder, check_response, try_clone_request};
+
+/// The time waited before reconnecting when the server does not send one.
+pub const DEFAULT_RECONNECT_DELAY: Duration = Duration::from_secs(3);
+
+impl AjarsClientReqwest {
+    pub fn request_sse<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &self,
+        rest: &'a RestSse<I, O, (), E>,
+    ) -> SseRequestBuilder<'a, I, O, (), E> {
+        self.request_sse_with_path(rest, &())
+    }
+
+    /// Prepares a request to a Server-Sent Events endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_sse_with_path<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        P: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &self,
+        rest: &'a RestSse<I, O, P, E>,
+        path_params: &P,
+    ) -> SseRequestBuilder<'a, I, O, P, E> {
+        SseRequestBuilder {
+            request: self.request_with_path(rest.rest(), path_params).header(reqwest::header::ACCEPT, SSE_CONTENT_TYPE),
+            reconnect_delay: DEFAULT_RECONNECT_DELAY,
+        }
+    }
+}
+
+pub struct SseRequestBuilder<
+    'a,
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+> {
+    request: RequestBuilder<'a, I, O, Rest<I, O, P, E>>,
+    reconnect_delay: Duration,
+}
+
+impl<I, O, P, E> SseRequestBuilder<'_, I, O, P, E>
+where
+    I: Serialize + DeserializeOwned + Send + Sync + 'static,
+    O: Serialize + DeserializeOwned + Send + Sync + 'static,
+    P: Serialize + DeserializeOwned + Send + Sync + 'static,
+    E: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    /// Sends the Request to the target URL, returning a future that resolves
+    /// once the response headers are received.
+    ///
+    /// The events are decoded as soon as they arrive. When the connection is lost, or cannot be established,
+    /// the client reconnects after the reconnection time and sends the ID of the last event received
+    /// in the `Last-Event-ID` header. The stream ends when the server answers with `204 No Content`,
+    /// and after yielding the error of a response with an error status.
+    pub async fn send(
+        self,
+        data: &I,
+    ) -> Result<BoxStream<'static, Result<SseEvent<O>, AjarsError<E, reqwest::Error>>>, AjarsError<E, reqwest::Error>>
+    {
+        let rest = self.request.rest.clone();
+        // A header that is not valid, or an input that cannot be encoded in the URL, fails before connecting
+        let (client, request) = self.request.prepare(data)?.build_split();
+        let request = request.map_err(AjarsError::Transport)?;
+        let mut events = EventSource {
+            rest,
+            client,
+            request,
+            reconnect_delay: self.reconnect_delay,
+            decoder: SseDecoder::default(),
+            response: None,
+            events: VecDeque::new(),
+            closed: false,
+        };
+        events.open().await?;
+        Ok(stream::unfold(events, |mut events| async move { events.next().await.map(|event| (event, events)) }).boxed())
+    }
+
+    /// Sets the time waited before reconnecting, when the server does not send one.
+    pub fn reconnect_delay(mut self, delay: Duration) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+
+    /// Add a `Header` to this Request.
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.request = self.request.header(key, value);
+        self
+    }
+
+    /// Add a set of Headers to the existing ones on this Request.
+    ///
+    /// The headers will be merged in to any already set.
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.request = self.request.headers(headers);
+        self
+    }
+
+    /// Enable HTTP bearer authentication.
+    pub fn bearer_auth<T>(mut self, token: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        self.request = self.request.bearer_auth(token);
+        self
+    }
+}
+
+/// The connections to a Server-Sent Events endpoint, reopened when they are lost.
+struct EventSource<I, O, P, E> {
+    rest: Rest<I, O, P, E>,
+    client: Client,
+    request: reqwest::Request,
+    reconnect_delay: Duration,
+    decoder: SseDecoder,
+    response: Option<reqwest::Response>,
+    events: VecDeque<Result<SseEvent<O>, AjarsError<E, reqwest::Error>>>,
+    closed: bool,
+}
+
+impl<I, O, P, E> EventSource<I, O, P, E>
+where
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+{
+    /// Opens a connection, resuming the stream after the last event received.
+    async fn open(&mut self) -> Result<(), AjarsError<E, reqwest::Error>> {
+        let mut request = try_clone_request(&self.request)?;
+        // An ID that is not a valid header value cannot be sent, so the stream is resumed from its start
+        if let Some(id) = self.decoder.last_event_id().and_then(|id| HeaderValue::from_str(id).ok()) {
+            let name =
+                HeaderName::from_bytes(LAST_EVENT_ID_HEADER.as_bytes()).expect("The header name should be valid");
+            request.headers_mut().insert(name, id);
+        }
+        let response = self.client.execute(request).await.map_err(AjarsError::Transport)?;
+        let response = check_response(&self.rest, response).await?;
+
+        // A '204 No Content' response asks the client to stop reconnecting
+        if response.status() == reqwest::StatusCode::NO_CONTENT {
+            self.closed = true;
+        } else {
+            self.response = Some(response);
+        }
+        Ok(())
+    }
+
+    async fn next(&mut self) -> Option<Result<SseEvent<O>, AjarsError<E, reqwest::Error>>> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Some(event);
+            }
+            if self.closed {
+                return None;
+            }
+            match &mut self.response {
+                Some(response) => match response.chunk().await {
+                    Ok(Some(chunk)) => self
+                        .events
+                        .extend(self.decoder.decode(&chunk).into_iter().map(|event| event.map_err(AjarsError::Decode))),
+                    // The connection was closed or lost
+                    Ok(None) | Err(_) => {
+                        self.response = None;
+                        self.decoder.reconnect();
+                    }
+                },
+                None => {
+                    tokio::time::sleep(self.decoder.retry().unwrap_or(self.reconnect_delay)).await;
+                    match self.open().await {
+                        Ok(()) => {}
+                        // A connection that cannot be established is retried, like a lost one
+                        Err(AjarsError::Transport(_)) => {}
+                        Err(error) => {
+                            self.closed = true;
+                            return Some(Err(error));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::convert::TryFrom;
+
+use ::reqwest::header::{HeaderName, HeaderValue};
+use ajars_core::{AjarsError, NdjsonDecoder, Rest, RestStream};
+use futures_util::stream::{self, BoxStream};
+use futures_util::{Stream, StreamExt, future};
+use http::HeaderMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::reqwest;
+use crate::{AjarsClientReqwest, RequestBuilder};
+
+impl AjarsClientReqwest {
+    pub fn request_stream<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &self,
+        rest: &'a RestStream<I, O, (), E>,
+    ) -> StreamRequestBuilder<'a, I, O, (), E> {
+        self.request_stream_with_path(rest, &())
+    }
+
+    /// Prepares a request to a streaming endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_stream_with_path<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        P: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &self,
+        rest: &'a RestStream<I, O, P, E>,
+        path_params: &P,
+    ) -> StreamRequestBuilder<'a, I, O, P, E> {
+        StreamRequestBuilder { request: self.request_with_path(rest.rest(), path_params) }
+    }
+}
+
+pub struct StreamRequestBuilder<
+    'a,
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+> {
+    request: RequestBuilder<'a, I, O, Rest<I, O, P, E>>,
+}
+
+impl<I, O, P, E> StreamRequestBuilder<'_, I, O, P, E>
+where
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned + Send + 'static,
+    E: Serialize + DeserializeOwned + Send + 'static,
+    P: Serialize + DeserializeOwned,
+{
+    /// Sends the Request to the target URL, returning a future that resolves
+    /// once the response headers are received.
+    ///
+    /// The items of the stream are decoded as soon as their lines arrive; the typed error of the endpoint
+    /// is returned by the future, while a failure of the connection or of the decoding of an item ends the stream.
+    pub async fn send(
+        self,
+        data: &I,
+    ) -> Result<BoxStream<'static, Result<O, AjarsError<E, reqwest::Error>>>, AjarsError<E, reqwest::Error>> {
+        let response = self.request.execute(data).await?;
+        Ok(decode_ndjson(response.bytes_stream()))
+    }
+
+    /// Add a `Header` to this Request.
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.request = self.request.header(key, value);
+        self
+    }
+
+    /// Add a set of Headers to the existing ones on this Request.
+    ///
+    /// The headers will be merged in to any already set.
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.request = self.request.headers(headers);
+        self
+    }
+
+    /// Enable HTTP bearer authentication.
+    pub fn bearer_auth<T>(mut self, token: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        self.request = self.request.bearer_auth(token);
+        self
+    }
+}
+
+/// Decodes the newline-delimited JSON items of a response body; the last line can lack its trailing newline.
+fn decode_ndjson<O, E, B: AsRef<[u8]> + Send + 'static>(
+    body: impl Stream<Item = Result<B, reqwest::Error>> + Send + 'static,
+) -> BoxStream<'static, Result<O, AjarsError<E, reqwest::Error>>>
+where
+    O: DeserializeOwned + Send + 'static,
+    E: Send + 'static,
+{
+    body.map(Some)
+        .chain(stream::once(future::ready(None)))
+        .scan((NdjsonDecoder::default(), false), |(decoder, failed), chunk| {
+            if *failed {
+                return future::ready(None);
+            }
+            let mut items: Vec<Result<O, _>> = match chunk {
+                Some(Ok(chunk)) => {
+                    decoder.decode(chunk.as_ref()).into_iter().map(|item| item.map_err(AjarsError::Decode)).collect()
+                }
+                Some(Err(error)) => vec![Err(AjarsError::Transport(error))],
+                None => decoder.finish().into_iter().map(|item| item.map_err(AjarsError::Decode)).collect(),
+            };
+            // The stream ends with its first error, dropping the items that follow it
+            if let Some(position) = items.iter().position(Result::is_err) {
+                items.truncate(position + 1);
+                *failed = true;
+            }
+            future::ready(Some(stream::iter(items)))
+        })
+        .flatten()
+        .boxed()
+}
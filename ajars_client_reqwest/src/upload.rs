@@ -0,0 +1,126 @@
+use std::convert::TryFrom;
+
+use ::reqwest::header::{HeaderName, HeaderValue};
+use ::reqwest::multipart::{Form, Part};
+use ajars_core::{AjarsError, Codec, FILE_PART, FilePart, Json, METADATA_PART, Rest, RestUpload};
+use http::HeaderMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::reqwest;
+use crate::{AjarsClientReqwest, RequestBuilder, check_response, read_output};
+
+impl AjarsClientReqwest {
+    pub fn request_upload<
+        'a,
+        M: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &self,
+        rest: &'a RestUpload<M, O, (), E>,
+    ) -> UploadRequestBuilder<'a, M, O, (), E> {
+        self.request_upload_with_path(rest, &())
+    }
+
+    /// Prepares a request to an upload endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_upload_with_path<
+        'a,
+        M: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        P: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &self,
+        rest: &'a RestUpload<M, O, P, E>,
+        path_params: &P,
+    ) -> UploadRequestBuilder<'a, M, O, P, E> {
+        UploadRequestBuilder { request: self.request_with_path(rest.rest(), path_params), files: vec![] }
+    }
+}
+
+pub struct UploadRequestBuilder<
+    'a,
+    M: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+> {
+    request: RequestBuilder<'a, M, O, Rest<M, O, P, E>>,
+    files: Vec<FilePart>,
+}
+
+impl<M, O, P, E> UploadRequestBuilder<'_, M, O, P, E>
+where
+    M: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+{
+    /// Adds a file to the upload.
+    pub fn file(mut self, file: FilePart) -> Self {
+        self.files.push(file);
+        self
+    }
+
+    /// Adds a set of files to the upload.
+    pub fn files<F: IntoIterator<Item = FilePart>>(mut self, files: F) -> Self {
+        self.files.extend(files);
+        self
+    }
+
+    /// Sends the metadata and the files to the target URL as a `multipart/form-data` body,
+    /// returning a future Response.
+    pub async fn send(self, metadata: &M) -> Result<O, AjarsError<E, reqwest::Error>> {
+        let rest = self.request.rest;
+
+        // A metadata that fails the validation of the endpoint is not sent
+        #[cfg(feature = "validator")]
+        ajars_core::RestType::validate(rest, metadata).map_err(AjarsError::Validation)?;
+
+        let metadata = Part::bytes(Json::encode(metadata).map_err(AjarsError::Encode)?)
+            .mime_str(Json::CONTENT_TYPE)
+            .map_err(AjarsError::Transport)?;
+        let mut form = Form::new().part(METADATA_PART, metadata);
+        for file in self.files {
+            let file_name = file.file_name().to_owned();
+            let content_type = file.content_type().to_owned();
+            let part = Part::bytes(file.into_content()).file_name(file_name);
+            form = form.part(FILE_PART, part.mime_str(&content_type).map_err(AjarsError::Transport)?);
+        }
+
+        let response = self.request.request.multipart(form).send().await.map_err(AjarsError::Transport)?;
+        let response = check_response(rest, response).await?;
+        read_output(rest, response).await
+    }
+
+    /// Add a `Header` to this Request.
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.request = self.request.header(key, value);
+        self
+    }
+
+    /// Add a set of Headers to the existing ones on this Request.
+    ///
+    /// The headers will be merged in to any already set.
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.request = self.request.headers(headers);
+        self
+    }
+
+    /// Enable HTTP bearer authentication.
+    pub fn bearer_auth<T>(mut self, token: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        self.request = self.request.bearer_auth(token);
+        self
+    }
+}
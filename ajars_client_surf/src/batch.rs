@@ -0,0 +1,82 @@
+use std::fmt::Display;
+
+use ajars_core::{
+    AjarsError, BatchCall, BatchCalls, BatchRest, BatchResult, BatchResults, Batched, NoError, RestBatch, RestType,
+};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{AjarsClientSurf, RequestBuilder, surf};
+
+impl AjarsClientSurf {
+    /// Prepares a batch of calls, sent together in a single request to the batch endpoint.
+    pub fn batch<'a>(&self, rest: &'a RestBatch) -> BatchRequestBuilder<'a> {
+        BatchRequestBuilder { request: self.request(rest.rest()), calls: BatchCalls::default() }
+    }
+}
+
+/// The calls of a batch, sent in a single request.
+pub struct BatchRequestBuilder<'a> {
+    request: RequestBuilder<'a, Vec<BatchCall>, Vec<BatchResult>, BatchRest>,
+    calls: BatchCalls,
+}
+
+impl BatchRequestBuilder<'_> {
+    /// Adds a call to an endpoint without path parameters.
+    /// The returned handle reads the output of the call from the results of the batch.
+    pub fn call<I, O, REST>(
+        &mut self,
+        rest: &REST,
+        input: &I,
+    ) -> Result<Batched<O, REST::Error>, AjarsError<REST::Error, surf::Error>>
+    where
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        REST: RestType<I, O, PathParams = ()>,
+    {
+        self.calls.push(rest, &(), input)
+    }
+
+    /// Adds a call to an endpoint whose path template contains placeholders,
+    /// filled with the given path parameters.
+    pub fn call_with_path<I, O, REST>(
+        &mut self,
+        rest: &REST,
+        path_params: &REST::PathParams,
+        input: &I,
+    ) -> Result<Batched<O, REST::Error>, AjarsError<REST::Error, surf::Error>>
+    where
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        REST: RestType<I, O>,
+    {
+        self.calls.push(rest, path_params, input)
+    }
+
+    /// Sends all the calls in a single request and returns their results.
+    pub async fn send(self) -> Result<BatchResults<surf::Error>, AjarsError<NoError, surf::Error>> {
+        let calls = self.calls.len();
+        let results = self.request.send(&self.calls.into_calls()).await?;
+        BatchResults::new(calls, results).map_err(AjarsError::Decode)
+    }
+
+    /// Add a header to the request of the batch.
+    /// A header that is not valid makes the batch fail when it is sent.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.request = self.request.header(name, value);
+        self
+    }
+
+    /// Add a set of headers to the existing ones of the request of the batch.
+    pub fn headers<'h, H>(self, headers: H) -> Self
+    where
+        H: IntoIterator<Item = (&'h str, &'h str)>,
+    {
+        headers.into_iter().fold(self, |batch, (name, value)| batch.header(name, value))
+    }
+
+    /// Enable HTTP bearer authentication.
+    pub fn bearer_auth<T: Display>(self, token: T) -> Self {
+        self.header("Authorization", &format!("Bearer {}", token))
+    }
+}
@@ -0,0 +1,120 @@
+use std::marker::PhantomData;
+use std::ops::RangeBounds;
+
+use ajars_core::{AjarsError, ContentRange, Rest, RestDownload, content_disposition_file_name, range_header};
+use futures_util::stream::BoxStream;
+use futures_util::{StreamExt, TryStreamExt};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::stream::body_chunks;
+use crate::surf::{self, StatusCode};
+use crate::{AjarsClientSurf, RequestBuilder, check_response};
+
+impl AjarsClientSurf {
+    pub fn request_download<'a, I: Serialize + DeserializeOwned, E: Serialize + DeserializeOwned>(
+        &self,
+        rest: &'a RestDownload<I, (), E>,
+    ) -> DownloadRequestBuilder<'a, I, (), E> {
+        self.request_download_with_path(rest, &())
+    }
+
+    /// Prepares a request to a download endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_download_with_path<
+        'a,
+        I: Serialize + DeserializeOwned,
+        P: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &self,
+        rest: &'a RestDownload<I, P, E>,
+        path_params: &P,
+    ) -> DownloadRequestBuilder<'a, I, P, E> {
+        DownloadRequestBuilder { request: self.request_with_path(rest.rest(), path_params) }
+    }
+}
+
+pub struct DownloadRequestBuilder<
+    'a,
+    I: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+> {
+    request: RequestBuilder<'a, I, (), Rest<I, (), P, E>>,
+}
+
+impl<I, P, E> DownloadRequestBuilder<'_, I, P, E>
+where
+    I: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+{
+    /// Asks only for the given bytes of the content, e.g. `100..` to resume a download after its first 100 bytes.
+    pub fn range<R: RangeBounds<u64>>(mut self, range: R) -> Self {
+        self.request.request = self.request.request.header("Range", range_header(range));
+        self
+    }
+
+    /// Sends the Request to the target URL, returning a future that resolves
+    /// once the response headers are received.
+    ///
+    /// The content is not read yet: it can be read as a whole or as a stream from the returned response.
+    pub async fn send(self, data: &I) -> Result<DownloadResponse<E>, AjarsError<E, surf::Error>> {
+        let rest = self.request.rest;
+        let request = self.request.prepare(data)?;
+        let response = request.send().await.map_err(AjarsError::Transport)?;
+
+        // A '206 Partial Content' response answers a request with a `Range` header
+        let response = match response.status() {
+            StatusCode::PartialContent => response,
+            _ => check_response(rest, response).await?,
+        };
+        Ok(DownloadResponse { response, phantom_e: PhantomData })
+    }
+}
+
+/// The successful response of a download endpoint, whose content has not been read yet.
+pub struct DownloadResponse<E> {
+    response: surf::Response,
+    phantom_e: PhantomData<E>,
+}
+
+impl<E: Send + 'static> DownloadResponse<E> {
+    /// The `Content-Type` of the content.
+    pub fn content_type(&self) -> Option<&str> {
+        self.header("Content-Type")
+    }
+
+    /// The name of the file suggested by the `Content-Disposition` header of the response.
+    pub fn file_name(&self) -> Option<String> {
+        self.header("Content-Disposition").and_then(content_disposition_file_name)
+    }
+
+    /// The bytes of the content sent by the response, if it answered a request with a range.
+    pub fn content_range(&self) -> Option<ContentRange> {
+        match self.response.status() {
+            StatusCode::PartialContent => self.header("Content-Range").and_then(ContentRange::parse),
+            _ => None,
+        }
+    }
+
+    /// The number of bytes of the body, if known in advance.
+    pub fn content_length(&self) -> Option<u64> {
+        self.response.len().map(|len| len as u64)
+    }
+
+    /// Reads the whole body.
+    pub async fn bytes(mut self) -> Result<Vec<u8>, AjarsError<E, surf::Error>> {
+        self.response.body_bytes().await.map_err(AjarsError::Transport)
+    }
+
+    /// Returns the chunks of the body as soon as they arrive.
+    pub fn stream(mut self) -> BoxStream<'static, Result<Vec<u8>, AjarsError<E, surf::Error>>> {
+        body_chunks(self.response.take_body()).map_err(AjarsError::Transport).boxed()
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.response.header(name).map(|values| values.last().as_str())
+    }
+}
@@ -1,11 +1,34 @@
+use std::future::Future;
 use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
-use ajars_core::{HttpMethod, RestType};
+use ajars_core::{
+    AjarsClient, AjarsError, AjarsRequestBuilder, CircuitBreaker, Codec, CodecError, HttpMethod, InputLocation,
+    Response, RestType, RetryPolicy, empty_body_output, head_output, is_empty_body,
+};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 
+use crate::surf::http::auth::BasicAuth;
+use crate::surf::http::headers::{HeaderName, HeaderValue};
 use crate::surf::{Client, RequestBuilder as SurfRequestBuilder};
 
+mod batch;
+mod download;
+mod page;
+mod stream;
+#[cfg(feature = "upload")]
+mod upload;
+
+pub use batch::BatchRequestBuilder;
+pub use download::{DownloadRequestBuilder, DownloadResponse};
+pub use page::PaginateRequestBuilder;
+pub use stream::StreamRequestBuilder;
+#[cfg(feature = "upload")]
+pub use upload::UploadRequestBuilder;
+
 pub mod surf {
     pub use ::surf::*;
 }
@@ -14,33 +37,122 @@ pub mod surf {
 pub struct AjarsClientSurf {
     client: Client,
     base_url: String,
+    retry_policy: Arc<RetryPolicy>,
+    circuit_breaker: Option<CircuitBreaker>,
 }
 
 impl AjarsClientSurf {
+    /// Creates a client that sends the requests to `base_url`.
+    /// Trailing slashes of the base URL are ignored, as the paths of the endpoints start with `/`.
     pub fn new<S: Into<String>>(client: Client, base_url: S) -> Self {
-        Self { client, base_url: base_url.into() }
+        Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            retry_policy: Arc::new(RetryPolicy::none()),
+            circuit_breaker: None,
+        }
+    }
+
+    /// Sets the policy used to send again the requests to the idempotent endpoints that fail
+    /// for a transient reason. By default, the requests are never retried.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Arc::new(retry_policy);
+        self
     }
 
-    pub fn request<'a, I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+    /// Sets the circuit breaker that stops sending the requests to a server that keeps failing.
+    /// Its circuits are keyed by the base URL of the client or by endpoint, see [`CircuitBreaker::circuit`].
+    /// By default, the requests are always sent.
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    pub fn request<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        REST: RestType<I, O, PathParams = ()>,
+    >(
         &self,
         rest: &'a REST,
     ) -> RequestBuilder<'a, I, O, REST> {
-        let url = format!("{}{}", self.base_url, rest.path());
+        self.request_with_path(rest, &())
+    }
+
+    /// Prepares a request to an endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_with_path<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        REST: RestType<I, O>,
+    >(
+        &self,
+        rest: &'a REST,
+        path_params: &REST::PathParams,
+    ) -> RequestBuilder<'a, I, O, REST> {
+        let url = format!("{}{}", self.base_url, rest.path_with(path_params));
 
         let request = match rest.method() {
             HttpMethod::DELETE => self.client.delete(&url),
             HttpMethod::GET => self.client.get(&url),
-            HttpMethod::POST => self.client.post(&url).header("Content-Type", "application/json"),
-            HttpMethod::PUT => self.client.put(&url).header("Content-Type", "application/json"),
+            HttpMethod::HEAD => self.client.head(&url),
+            HttpMethod::OPTIONS => self.client.options(&url),
+            HttpMethod::PATCH => self.client.patch(&url),
+            HttpMethod::POST => self.client.post(&url),
+            HttpMethod::PUT => self.client.put(&url),
         };
 
-        RequestBuilder { rest, request, phantom_i: PhantomData, phantom_o: PhantomData }
+        RequestBuilder {
+            rest,
+            request,
+            client: self.client.clone(),
+            retry_policy: self.retry_policy.clone(),
+            circuit: self
+                .circuit_breaker
+                .as_ref()
+                .map(|breaker| (breaker.clone(), breaker.circuit(&self.base_url, rest))),
+            header_error: None,
+            phantom_i: PhantomData,
+            phantom_o: PhantomData,
+        }
+    }
+}
+
+impl AjarsClient for AjarsClientSurf {
+    type Error = surf::Error;
+    type RequestBuilder<'a, I, O, REST>
+        = RequestBuilder<'a, I, O, REST>
+    where
+        I: Serialize + DeserializeOwned + 'a,
+        O: Serialize + DeserializeOwned + 'a,
+        REST: RestType<I, O> + 'a;
+
+    fn request_with_path<'a, I, O, REST>(
+        &'a self,
+        rest: &'a REST,
+        path_params: &REST::PathParams,
+    ) -> RequestBuilder<'a, I, O, REST>
+    where
+        I: Serialize + DeserializeOwned + 'a,
+        O: Serialize + DeserializeOwned + 'a,
+        REST: RestType<I, O> + 'a,
+    {
+        AjarsClientSurf::request_with_path(self, rest, path_params)
     }
 }
 
 pub struct RequestBuilder<'a, I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>> {
     rest: &'a REST,
     request: SurfRequestBuilder,
+    /// The client that sends the request again when it is retried
+    client: Client,
+    retry_policy: Arc<RetryPolicy>,
+    /// The circuit breaker of the client along with the circuit of the request
+    circuit: Option<(CircuitBreaker, String)>,
+    /// The first header that is not valid, reported when the request is sent
+    header_error: Option<surf::Error>,
     phantom_i: PhantomData<I>,
     phantom_o: PhantomData<O>,
 }
@@ -50,14 +162,184 @@ impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: Res
 {
     /// Sends the Request to the target URL, returning a
     /// future Response.
-    pub async fn send(self, data: &I) -> Result<O, surf::Error> {
-        let request = match self.rest.method() {
-            HttpMethod::DELETE | HttpMethod::GET => self.request.query(data)?,
-            HttpMethod::POST | HttpMethod::PUT => {
-                self.request.header("Content-Type", "application/json").body(surf::Body::from_json(data)?)
+    pub async fn send(self, data: &I) -> Result<O, AjarsError<REST::Error, surf::Error>> {
+        self.send_full(data).await.map(Response::into_body)
+    }
+
+    /// Sends the Request to the target URL, returning a future Response
+    /// with the status and the headers of the HTTP response along with the output.
+    pub async fn send_full(self, data: &I) -> Result<Response<O>, AjarsError<REST::Error, surf::Error>> {
+        let rest = self.rest;
+        let response = self.execute(data).await?;
+        let status = u16::from(response.status()).into();
+        let headers = header_pairs(&response);
+        Ok(Response::new(status, headers, read_output(rest, response).await?))
+    }
+
+    /// Sends the request and returns the response if its status is the success status of the endpoint.
+    /// The request is sent again, as long as the retry policy allows it, if it fails for a transient reason.
+    async fn execute(self, data: &I) -> Result<surf::Response, AjarsError<REST::Error, surf::Error>> {
+        let rest = self.rest;
+        let client = self.client.clone();
+        let retry_policy = self.retry_policy.clone();
+        let circuit = self.circuit.clone();
+        let mut request = self.prepare(data)?.build();
+
+        // A cloned request has an empty body, so the body is read once and set again in every attempt
+        let body = match request.is_empty() {
+            Some(true) => None,
+            _ => {
+                let body = request.take_body();
+                let mime = body.mime().clone();
+                Some((body.into_bytes().await.map_err(AjarsError::Transport)?, mime))
             }
         };
 
-        request.send().await?.body_json().await
+        retry_policy
+            .run(
+                rest.is_idempotent(),
+                || {
+                    let mut request = request.clone();
+                    if let Some((bytes, mime)) = &body {
+                        let mut body = surf::Body::from_bytes(bytes.clone());
+                        body.set_mime(mime.clone());
+                        request.set_body(body);
+                    }
+                    let client = &client;
+                    let send = async move {
+                        let response = client.send(request).await.map_err(AjarsError::Transport)?;
+                        check_response(rest, response).await
+                    };
+                    // Every attempt goes through the circuit breaker, which fails fast while the circuit is open
+                    let circuit = &circuit;
+                    async move {
+                        match circuit {
+                            Some((breaker, circuit)) => breaker.run(circuit, send).await,
+                            None => send.await,
+                        }
+                    }
+                },
+                |delay: Duration| async_std::task::sleep(delay),
+            )
+            .await
+    }
+
+    /// Validates the input and sets it in the request.
+    fn prepare(self, data: &I) -> Result<SurfRequestBuilder, AjarsError<REST::Error, surf::Error>> {
+        if let Some(error) = self.header_error {
+            return Err(AjarsError::Transport(error));
+        }
+
+        // An input that fails the validation of the endpoint is not sent
+        #[cfg(feature = "validator")]
+        self.rest.validate(data).map_err(AjarsError::Validation)?;
+
+        // An empty input, like `()`, is sent without a query string and without a body
+        Ok(match self.rest.input_location() {
+            _ if is_empty_body(data) => self.request,
+            InputLocation::Query => self.request.query(data).map_err(encode_error)?,
+            InputLocation::Body => {
+                let mut body = surf::Body::from_bytes(REST::Codec::encode(data).map_err(AjarsError::Encode)?);
+                body.set_mime(REST::Codec::CONTENT_TYPE);
+                self.request.body(body)
+            }
+            InputLocation::Form => self.request.body(surf::Body::from_form(data).map_err(encode_error)?),
+        })
+    }
+
+    /// Add a header to this Request.
+    /// A header that is not valid makes the request fail when it is sent.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        match (HeaderName::from_str(name), HeaderValue::from_str(value)) {
+            (Ok(name), Ok(value)) => self.request = self.request.header(name, value),
+            (Err(error), _) | (_, Err(error)) => {
+                self.header_error.get_or_insert(error);
+            }
+        }
+        self
     }
+
+    /// Enable HTTP basic authentication.
+    pub fn basic_auth(mut self, username: &str, password: Option<&str>) -> Self {
+        let auth = BasicAuth::new(username, password.unwrap_or_default());
+        self.request = self.request.header(auth.name(), auth.value());
+        self
+    }
+}
+
+impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>
+    AjarsRequestBuilder<I, O, REST> for RequestBuilder<'_, I, O, REST>
+{
+    type Error = surf::Error;
+
+    fn header(self, name: &str, value: &str) -> Self {
+        RequestBuilder::header(self, name, value)
+    }
+
+    fn basic_auth(self, username: &str, password: Option<&str>) -> Self {
+        RequestBuilder::basic_auth(self, username, password)
+    }
+
+    fn send(self, input: &I) -> impl Future<Output = Result<O, AjarsError<REST::Error, surf::Error>>> {
+        RequestBuilder::send(self, input)
+    }
+
+    fn send_full(self, input: &I) -> impl Future<Output = Result<Response<O>, AjarsError<REST::Error, surf::Error>>> {
+        RequestBuilder::send_full(self, input)
+    }
+}
+
+/// Reads the output of the endpoint from a successful response.
+async fn read_output<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+    rest: &REST,
+    mut response: surf::Response,
+) -> Result<O, AjarsError<REST::Error, surf::Error>> {
+    // A response to a HEAD request never has a body
+    if *rest.method() == HttpMethod::HEAD {
+        return head_output().map_err(AjarsError::Decode);
+    }
+
+    let body = response.body_bytes().await.map_err(AjarsError::Transport)?;
+
+    // An empty body, like the one of a '204 No Content' response, carries an empty output such as `()`
+    if body.is_empty()
+        && let Some(output) = empty_body_output()
+    {
+        return Ok(output);
+    }
+    REST::Codec::decode(&body).map_err(AjarsError::Decode)
+}
+
+/// Returns the response if its status is the success status of the endpoint.
+async fn check_response<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+    rest: &REST,
+    mut response: surf::Response,
+) -> Result<surf::Response, AjarsError<REST::Error, surf::Error>> {
+    // The body of an error status is read before decoding anything, as it may not be encoded with the codec
+    let status = response.status();
+    if status.is_client_error() || status.is_server_error() {
+        let headers = header_pairs(&response);
+        let body = response.body_bytes().await.map_err(AjarsError::Transport)?;
+        return Err(AjarsError::from_error_response::<REST::Codec>(status.into(), rest.error_status(), headers, body));
+    }
+
+    // A successful status other than the one declared by the endpoint breaks the contract of the endpoint
+    let status = u16::from(status);
+    if !rest.accepts_success_status(status) {
+        return Err(AjarsError::UnexpectedStatus { status: status.into(), expected: rest.success_status().into() });
+    }
+    Ok(response)
+}
+
+/// Maps the failure of surf to encode the input, like in a query string, to the encode error.
+fn encode_error<E, C>(error: surf::Error) -> AjarsError<E, C> {
+    AjarsError::Encode(CodecError::new(error.into_inner()))
+}
+
+/// Returns the headers of a response as name and value pairs, a pair for each value.
+fn header_pairs(response: &surf::Response) -> Vec<(String, String)> {
+    response
+        .iter()
+        .flat_map(|(name, values)| values.iter().map(|value| (name.to_string(), value.to_string())).collect::<Vec<_>>())
+        .collect()
 }
@@ -0,0 +1,83 @@
+use std::marker::PhantomData;
+
+use ajars_core::{AjarsError, Page, Paginated, RestType};
+use futures_util::stream::{self, BoxStream};
+use futures_util::{StreamExt, future};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::AjarsClientSurf;
+use crate::surf;
+
+impl AjarsClientSurf {
+    pub fn paginate<'a, I, T, REST>(&'a self, rest: &'a REST) -> PaginateRequestBuilder<'a, I, T, REST>
+    where
+        I: Serialize + DeserializeOwned + Paginated,
+        T: Serialize + DeserializeOwned,
+        REST: RestType<I, Page<T>, PathParams = ()>,
+    {
+        self.paginate_with_path(rest, &())
+    }
+
+    /// Prepares the requests to a paginated endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn paginate_with_path<'a, I, T, REST>(
+        &'a self,
+        rest: &'a REST,
+        path_params: &'a REST::PathParams,
+    ) -> PaginateRequestBuilder<'a, I, T, REST>
+    where
+        I: Serialize + DeserializeOwned + Paginated,
+        T: Serialize + DeserializeOwned,
+        REST: RestType<I, Page<T>>,
+    {
+        PaginateRequestBuilder { client: self, rest, path_params, phantom: PhantomData }
+    }
+}
+
+/// The requests to a paginated endpoint, sent one page at a time.
+pub struct PaginateRequestBuilder<'a, I, T, REST>
+where
+    I: Serialize + DeserializeOwned + Paginated,
+    T: Serialize + DeserializeOwned,
+    REST: RestType<I, Page<T>>,
+{
+    client: &'a AjarsClientSurf,
+    rest: &'a REST,
+    path_params: &'a REST::PathParams,
+    phantom: PhantomData<(I, T)>,
+}
+
+impl<'a, I, T, REST> PaginateRequestBuilder<'a, I, T, REST>
+where
+    I: Serialize + DeserializeOwned + Paginated + Send + Sync + 'a,
+    T: Serialize + DeserializeOwned + Send + 'a,
+    REST: RestType<I, Page<T>> + Sync,
+    REST::PathParams: Sync,
+    REST::Error: Send + 'a,
+{
+    /// Returns the items of the pages of the listing, starting from the page asked by the input.
+    ///
+    /// A page is fetched only when the items of the previous one have been consumed,
+    /// asking for the `next` page of the previous one with the other parameters of the input;
+    /// the stream ends after the last page or after the first error.
+    pub fn send(self, input: I) -> BoxStream<'a, Result<T, AjarsError<REST::Error, surf::Error>>> {
+        let Self { client, rest, path_params, .. } = self;
+        stream::unfold(Some(input), move |input| async move {
+            let input = input?;
+            let page = client.request_with_path(rest, path_params).send(&input).await;
+            Some(match page {
+                Ok(page) => {
+                    let next = page.next.map(|next| input.with_page(next));
+                    (Ok(page.items), next)
+                }
+                Err(error) => (Err(error), None),
+            })
+        })
+        .flat_map(|page| match page {
+            Ok(items) => stream::iter(items).map(Ok).left_stream(),
+            Err(error) => stream::once(future::ready(Err(error))).right_stream(),
+        })
+        .boxed()
+    }
+}
@@ -0,0 +1,121 @@
+use ajars_core::{AjarsError, NdjsonDecoder, Rest, RestStream};
+use futures_util::io::AsyncReadExt;
+use futures_util::stream::{self, BoxStream};
+use futures_util::{Stream, StreamExt, future};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::surf;
+use crate::{AjarsClientSurf, RequestBuilder};
+
+/// The size of the chunks read from the body of a streaming response.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+impl AjarsClientSurf {
+    pub fn request_stream<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &self,
+        rest: &'a RestStream<I, O, (), E>,
+    ) -> StreamRequestBuilder<'a, I, O, (), E> {
+        self.request_stream_with_path(rest, &())
+    }
+
+    /// Prepares a request to a streaming endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_stream_with_path<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        P: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &self,
+        rest: &'a RestStream<I, O, P, E>,
+        path_params: &P,
+    ) -> StreamRequestBuilder<'a, I, O, P, E> {
+        StreamRequestBuilder { request: self.request_with_path(rest.rest(), path_params) }
+    }
+}
+
+pub struct StreamRequestBuilder<
+    'a,
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+> {
+    request: RequestBuilder<'a, I, O, Rest<I, O, P, E>>,
+}
+
+impl<I, O, P, E> StreamRequestBuilder<'_, I, O, P, E>
+where
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned + Send + 'static,
+    E: Serialize + DeserializeOwned + Send + 'static,
+    P: Serialize + DeserializeOwned,
+{
+    /// Sends the Request to the target URL, returning a future that resolves
+    /// once the response headers are received.
+    ///
+    /// The items of the stream are decoded as soon as their lines arrive; the typed error of the endpoint
+    /// is returned by the future, while a failure of the connection or of the decoding of an item ends the stream.
+    pub async fn send(
+        self,
+        data: &I,
+    ) -> Result<BoxStream<'static, Result<O, AjarsError<E, surf::Error>>>, AjarsError<E, surf::Error>> {
+        let mut response = self.request.execute(data).await?;
+        Ok(decode_ndjson(body_chunks(response.take_body())))
+    }
+}
+
+/// Reads the body of a response as a stream of chunks, ending at the first error.
+pub(crate) fn body_chunks(body: surf::Body) -> impl Stream<Item = Result<Vec<u8>, surf::Error>> + Send + 'static {
+    stream::unfold(Some(body), |body| async move {
+        let mut body = body?;
+        let mut chunk = vec![0; CHUNK_SIZE];
+        match body.read(&mut chunk).await {
+            Ok(0) => None,
+            Ok(read) => {
+                chunk.truncate(read);
+                Some((Ok(chunk), Some(body)))
+            }
+            Err(error) => Some((Err(surf::Error::from(error)), None)),
+        }
+    })
+}
+
+/// Decodes the newline-delimited JSON items of a response body; the last line can lack its trailing newline.
+fn decode_ndjson<O, E>(
+    body: impl Stream<Item = Result<Vec<u8>, surf::Error>> + Send + 'static,
+) -> BoxStream<'static, Result<O, AjarsError<E, surf::Error>>>
+where
+    O: DeserializeOwned + Send + 'static,
+    E: Send + 'static,
+{
+    body.map(Some)
+        .chain(stream::once(future::ready(None)))
+        .scan((NdjsonDecoder::default(), false), |(decoder, failed), chunk| {
+            if *failed {
+                return future::ready(None);
+            }
+            let mut items: Vec<Result<O, _>> = match chunk {
+                Some(Ok(chunk)) => {
+                    decoder.decode(&chunk).into_iter().map(|item| item.map_err(AjarsError::Decode)).collect()
+                }
+                Some(Err(error)) => vec![Err(AjarsError::Transport(error))],
+                None => decoder.finish().into_iter().map(|item| item.map_err(AjarsError::Decode)).collect(),
+            };
+            // The stream ends with its first error, dropping the items that follow it
+            if let Some(position) = items.iter().position(Result::is_err) {
+                items.truncate(position + 1);
+                *failed = true;
+            }
+            future::ready(Some(stream::iter(items)))
+        })
+        .flatten()
+        .boxed()
+}
@@ -0,0 +1,89 @@
+use ajars_core::{
+    AjarsError, FilePart, Rest, RestUpload, encode_multipart, multipart_boundary, multipart_content_type,
+};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::surf;
+use crate::{AjarsClientSurf, RequestBuilder, check_response, read_output};
+
+impl AjarsClientSurf {
+    pub fn request_upload<
+        'a,
+        M: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &self,
+        rest: &'a RestUpload<M, O, (), E>,
+    ) -> UploadRequestBuilder<'a, M, O, (), E> {
+        self.request_upload_with_path(rest, &())
+    }
+
+    /// Prepares a request to an upload endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_upload_with_path<
+        'a,
+        M: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        P: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &self,
+        rest: &'a RestUpload<M, O, P, E>,
+        path_params: &P,
+    ) -> UploadRequestBuilder<'a, M, O, P, E> {
+        UploadRequestBuilder { request: self.request_with_path(rest.rest(), path_params), files: vec![] }
+    }
+}
+
+pub struct UploadRequestBuilder<
+    'a,
+    M: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+> {
+    request: RequestBuilder<'a, M, O, Rest<M, O, P, E>>,
+    files: Vec<FilePart>,
+}
+
+impl<M, O, P, E> UploadRequestBuilder<'_, M, O, P, E>
+where
+    M: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+{
+    /// Adds a file to the upload.
+    pub fn file(mut self, file: FilePart) -> Self {
+        self.files.push(file);
+        self
+    }
+
+    /// Adds a set of files to the upload.
+    pub fn files<F: IntoIterator<Item = FilePart>>(mut self, files: F) -> Self {
+        self.files.extend(files);
+        self
+    }
+
+    /// Sends the metadata and the files to the target URL as a `multipart/form-data` body,
+    /// returning a future Response.
+    pub async fn send(self, metadata: &M) -> Result<O, AjarsError<E, surf::Error>> {
+        let rest = self.request.rest;
+
+        // A metadata that fails the validation of the endpoint is not sent
+        #[cfg(feature = "validator")]
+        ajars_core::RestType::validate(rest, metadata).map_err(AjarsError::Validation)?;
+
+        // surf has no built-in support for multipart requests, so the body is encoded at once
+        let boundary = multipart_boundary();
+        let mut body =
+            surf::Body::from_bytes(encode_multipart(&boundary, metadata, &self.files).map_err(AjarsError::Encode)?);
+        body.set_mime(multipart_content_type(&boundary).as_str());
+
+        let response = self.request.request.body(body).send().await.map_err(AjarsError::Transport)?;
+        let response = check_response(rest, response).await?;
+        read_output(rest, response).await
+    }
+}
@@ -0,0 +1,162 @@
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use ajars_core::{RestType, RestWebSocket, WebSocketError, decode_ws_message, encode_ws_message};
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue, Request, header};
+use tokio_tungstenite::tungstenite::{Error as TungsteniteError, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
+
+pub mod tungstenite {
+    pub use ::tokio_tungstenite::tungstenite::*;
+}
+
+type Connection = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+#[derive(Clone)]
+pub struct AjarsClientTungstenite {
+    base_url: String,
+}
+
+impl AjarsClientTungstenite {
+    /// Creates a client that opens the connections to `base_url`.
+    /// An `http` or `https` base URL is opened as `ws` or `wss`,
+    /// so that the base URL of the HTTP clients can be shared.
+    /// The `wss` connections need the `native-tls` feature, without which they fail to connect.
+    /// Trailing slashes of the base URL are ignored, as the paths of the endpoints start with `/`.
+    pub fn new<S: Into<String>>(base_url: S) -> Self {
+        let base_url = base_url.into();
+        let base_url = match base_url.strip_prefix("http") {
+            Some(rest) if rest.starts_with("://") || rest.starts_with("s://") => format!("ws{rest}"),
+            _ => base_url,
+        };
+        Self { base_url: base_url.trim_end_matches('/').to_owned() }
+    }
+
+    pub fn request_websocket<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned>(
+        &self,
+        websocket: &RestWebSocket<I, O, ()>,
+    ) -> WebSocketRequestBuilder<I, O> {
+        self.request_websocket_with_path(websocket, &())
+    }
+
+    /// Prepares the connection to a WebSocket endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_websocket_with_path<
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        P: Serialize + DeserializeOwned,
+    >(
+        &self,
+        websocket: &RestWebSocket<I, O, P>,
+        path_params: &P,
+    ) -> WebSocketRequestBuilder<I, O> {
+        let url = format!("{}{}", &self.base_url, websocket.rest().path_with(path_params));
+        let request = url.into_client_request().map_err(WebSocketError::connection);
+        WebSocketRequestBuilder { request, phantom: PhantomData }
+    }
+}
+
+pub struct WebSocketRequestBuilder<I, O> {
+    // An invalid URL or header fails the connection
+    request: Result<Request<()>, WebSocketError>,
+    phantom: PhantomData<fn(I) -> O>,
+}
+
+impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned> WebSocketRequestBuilder<I, O> {
+    /// Add a `Header` to the request that opens the connection.
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: std::error::Error + Send + Sync + 'static,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: std::error::Error + Send + Sync + 'static,
+    {
+        self.request = self.request.and_then(|mut request| {
+            let key = HeaderName::try_from(key).map_err(WebSocketError::connection)?;
+            let value = HeaderValue::try_from(value).map_err(WebSocketError::connection)?;
+            request.headers_mut().append(key, value);
+            Ok(request)
+        });
+        self
+    }
+
+    /// Enable HTTP bearer authentication.
+    pub fn bearer_auth<T>(self, token: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        self.header(header::AUTHORIZATION, format!("Bearer {token}"))
+    }
+
+    /// Opens the connection, returning the typed sink of the messages sent to the server
+    /// and the typed stream of the messages received from it.
+    pub async fn connect(self) -> Result<(WebSocketSender<I>, WebSocketReceiver<O>), WebSocketError> {
+        let (connection, _response) = connect_async(self.request?).await.map_err(WebSocketError::connection)?;
+        let (sink, stream) = connection.split();
+        Ok((WebSocketSender { sink, phantom: PhantomData }, WebSocketReceiver { stream, phantom: PhantomData }))
+    }
+}
+
+/// The typed sink of the messages sent to the server of a WebSocket endpoint.
+/// Closing it closes the connection.
+pub struct WebSocketSender<I> {
+    sink: SplitSink<Connection, Message>,
+    phantom: PhantomData<fn(I)>,
+}
+
+impl<I: Serialize> Sink<I> for WebSocketSender<I> {
+    type Error = WebSocketError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink.poll_ready_unpin(cx).map_err(WebSocketError::connection)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, message: I) -> Result<(), Self::Error> {
+        let text = encode_ws_message(&message)?;
+        self.sink.start_send_unpin(Message::text(text)).map_err(WebSocketError::connection)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink.poll_flush_unpin(cx).map_err(WebSocketError::connection)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink.poll_close_unpin(cx).map_err(WebSocketError::connection)
+    }
+}
+
+/// The typed stream of the messages received from the server of a WebSocket endpoint.
+/// It ends when the connection is closed.
+pub struct WebSocketReceiver<O> {
+    stream: SplitStream<Connection>,
+    phantom: PhantomData<fn() -> O>,
+}
+
+impl<O: DeserializeOwned> Stream for WebSocketReceiver<O> {
+    type Item = Result<O, WebSocketError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let message = match ready!(self.stream.poll_next_unpin(cx)) {
+                Some(Ok(message)) => message,
+                Some(Err(TungsteniteError::ConnectionClosed)) | None => return Poll::Ready(None),
+                Some(Err(error)) => return Poll::Ready(Some(Err(WebSocketError::connection(error)))),
+            };
+            let message = match &message {
+                Message::Text(text) => decode_ws_message(text.as_bytes()),
+                Message::Binary(bytes) => decode_ws_message(bytes),
+                Message::Close(_) => return Poll::Ready(None),
+                // The pings are answered by the connection itself
+                Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => continue,
+            };
+            return Poll::Ready(Some(message.map_err(WebSocketError::from)));
+        }
+    }
+}
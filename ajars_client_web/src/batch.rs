@@ -0,0 +1,82 @@
+use ajars_core::{
+    AjarsError, BatchCall, BatchCalls, BatchRest, BatchResult, BatchResults, Batched, NoError, RestBatch, RestType,
+};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::error::Error;
+use crate::{AjarsClientWeb, RequestBuilder};
+
+impl AjarsClientWeb {
+    /// Prepares a batch of calls, sent together in a single request to the batch endpoint.
+    pub fn batch<'a>(&'a self, rest: &'a RestBatch) -> BatchRequestBuilder<'a> {
+        BatchRequestBuilder { request: self.request(rest.rest()), calls: BatchCalls::default() }
+    }
+}
+
+/// The calls of a batch, sent in a single request.
+pub struct BatchRequestBuilder<'a> {
+    request: RequestBuilder<'a, Vec<BatchCall>, Vec<BatchResult>, BatchRest>,
+    calls: BatchCalls,
+}
+
+impl BatchRequestBuilder<'_> {
+    /// Adds a call to an endpoint without path parameters.
+    /// The returned handle reads the output of the call from the results of the batch.
+    pub fn call<I, O, REST>(
+        &mut self,
+        rest: &REST,
+        input: &I,
+    ) -> Result<Batched<O, REST::Error>, AjarsError<REST::Error, Error>>
+    where
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        REST: RestType<I, O, PathParams = ()>,
+    {
+        self.calls.push(rest, &(), input)
+    }
+
+    /// Adds a call to an endpoint whose path template contains placeholders,
+    /// filled with the given path parameters.
+    pub fn call_with_path<I, O, REST>(
+        &mut self,
+        rest: &REST,
+        path_params: &REST::PathParams,
+        input: &I,
+    ) -> Result<Batched<O, REST::Error>, AjarsError<REST::Error, Error>>
+    where
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        REST: RestType<I, O>,
+    {
+        self.calls.push(rest, path_params, input)
+    }
+
+    /// Sends all the calls in a single request and returns their results.
+    pub async fn send(self) -> Result<BatchResults<Error>, AjarsError<NoError, Error>> {
+        let calls = self.calls.len();
+        let results = self.request.send(&self.calls.into_calls()).await?;
+        BatchResults::new(calls, results).map_err(AjarsError::Decode)
+    }
+
+    /// Add a header to the request of the batch
+    pub fn add_header<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.request = self.request.add_header(key, value);
+        self
+    }
+
+    /// Enable HTTP basic authentication.
+    pub fn basic_auth(mut self, username: &str, password: Option<&str>) -> Result<Self, Error> {
+        self.request = self.request.basic_auth(username, password)?;
+        Ok(self)
+    }
+
+    /// Enable HTTP bearer authentication.
+    pub fn bearer_auth<T>(mut self, token: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        self.request = self.request.bearer_auth(token);
+        self
+    }
+}
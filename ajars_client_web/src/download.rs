@@ -0,0 +1,122 @@
+use std::ops::RangeBounds;
+
+use ajars_core::{
+    AjarsError, ContentRange, Json, Rest, RestDownload, RestType, content_disposition_file_name, range_header,
+};
+use gloo_net::http::Response;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::error::Error;
+use crate::{AjarsClientWeb, RequestBuilder, check_status, read_body};
+
+/// The status of a response that answers a request with a `Range` header.
+const PARTIAL_CONTENT_STATUS: u16 = 206;
+
+impl AjarsClientWeb {
+    pub fn request_download<'a, I: Serialize + DeserializeOwned, E: Serialize + DeserializeOwned>(
+        &'a self,
+        rest: &'a RestDownload<I, (), E>,
+    ) -> DownloadRequestBuilder<'a, I, (), E> {
+        self.request_download_with_path(rest, &())
+    }
+
+    /// Prepares a request to a download endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_download_with_path<
+        'a,
+        I: Serialize + DeserializeOwned,
+        P: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &'a self,
+        rest: &'a RestDownload<I, P, E>,
+        path_params: &P,
+    ) -> DownloadRequestBuilder<'a, I, P, E> {
+        DownloadRequestBuilder { request: self.request_with_path(rest.rest(), path_params) }
+    }
+}
+
+pub struct DownloadRequestBuilder<
+    'a,
+    I: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+> {
+    request: RequestBuilder<'a, I, (), Rest<I, (), P, E>>,
+}
+
+impl<I, P, E> DownloadRequestBuilder<'_, I, P, E>
+where
+    I: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+{
+    /// Asks only for the given bytes of the content, e.g. `100..` to resume a download after its first 100 bytes.
+    pub fn range<R: RangeBounds<u64>>(self, range: R) -> Self {
+        self.add_header("Range", range_header(range))
+    }
+
+    /// Add a header to the request
+    pub fn add_header<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.request = self.request.add_header(key, value);
+        self
+    }
+
+    /// Enable HTTP bearer authentication.
+    pub fn bearer_auth<T>(mut self, token: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        self.request = self.request.bearer_auth(token);
+        self
+    }
+
+    /// Sends the Request to the target URL, returning a future that resolves
+    /// once the response headers are received.
+    pub async fn send(self, data: &I) -> Result<DownloadResponse, AjarsError<E, Error>> {
+        let request = self.request.prepare(data)?;
+        let response = self.request.fetch(request).await?;
+
+        // A '206 Partial Content' response answers a request with a `Range` header
+        let status = response.status();
+        let rest = self.request.rest;
+        if status < 400 && status != PARTIAL_CONTENT_STATUS && !rest.accepts_success_status(status) {
+            return Err(AjarsError::UnexpectedStatus { status: status.into(), expected: rest.success_status().into() });
+        }
+        let response = check_status::<Json, _>(response, rest.error_status()).await?;
+        Ok(DownloadResponse { response })
+    }
+}
+
+/// The successful response of a download endpoint, whose content has not been read yet.
+pub struct DownloadResponse {
+    response: Response,
+}
+
+impl DownloadResponse {
+    /// The `Content-Type` of the content.
+    pub fn content_type(&self) -> Option<String> {
+        self.response.headers().get("Content-Type")
+    }
+
+    /// The name of the file suggested by the `Content-Disposition` header of the response.
+    pub fn file_name(&self) -> Option<String> {
+        self.response.headers().get("Content-Disposition").as_deref().and_then(content_disposition_file_name)
+    }
+
+    /// The bytes of the content sent by the response, if it answered a request with a range.
+    pub fn content_range(&self) -> Option<ContentRange> {
+        match self.response.status() {
+            PARTIAL_CONTENT_STATUS => {
+                self.response.headers().get("Content-Range").as_deref().and_then(ContentRange::parse)
+            }
+            _ => None,
+        }
+    }
+
+    /// Reads the whole body.
+    pub async fn bytes<E>(self) -> Result<Vec<u8>, AjarsError<E, Error>> {
+        read_body(self.response).await
+    }
+}
@@ -19,4 +19,9 @@ pub enum Error {
         /// The originally reported error.
         error: String,
     },
+    #[error("EventSource error. Error: {error}")]
+    EventSource {
+        /// The originally reported error.
+        error: String,
+    },
 }
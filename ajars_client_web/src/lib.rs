@@ -1,69 +1,39 @@
 use std::collections::HashMap;
-use std::fmt::Display;
+use std::future::Future;
 use std::marker::PhantomData;
 use std::rc::Rc;
+use std::time::Duration;
 
-use ajars_core::{HttpMethod, RestType};
+use ajars_core::{
+    AjarsClient, AjarsError, AjarsRequestBuilder, Codec, CodecError, HttpMethod, InputLocation, RestType, RetryPolicy,
+    empty_body_output, head_output, is_empty_body,
+};
 use error::Error;
 use gloo_net::http::{Request, Response};
 use gloo_utils::window;
 use http::Method;
+use js_sys::Uint8Array;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use web_sys::RequestMode;
 
+mod batch;
+mod download;
 pub mod error;
-
-#[derive(Debug, Clone, Copy)]
-pub struct HttpStatus(u16);
-
-impl Display for HttpStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-
-impl From<u16> for HttpStatus {
-    fn from(status: u16) -> Self {
-        Self(status)
-    }
-}
-
-impl HttpStatus {
-    #[inline]
-    pub fn status(&self) -> u16 {
-        self.0
-    }
-
-    #[inline]
-    pub fn is_informational(&self) -> bool {
-        200 > self.0 && self.0 >= 100
-    }
-
-    /// Check if status is within 200-299.
-    #[inline]
-    pub fn is_success(&self) -> bool {
-        300 > self.0 && self.0 >= 200
-    }
-
-    /// Check if status is within 300-399.
-    #[inline]
-    pub fn is_redirection(&self) -> bool {
-        400 > self.0 && self.0 >= 300
-    }
-
-    /// Check if status is within 400-499.
-    #[inline]
-    pub fn is_client_error(&self) -> bool {
-        500 > self.0 && self.0 >= 400
-    }
-
-    /// Check if status is within 500-599.
-    #[inline]
-    pub fn is_server_error(&self) -> bool {
-        600 > self.0 && self.0 >= 500
-    }
-}
+mod sse;
+#[cfg(feature = "upload")]
+mod upload;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+pub use ajars_core::HttpStatus;
+pub use batch::BatchRequestBuilder;
+pub use download::{DownloadRequestBuilder, DownloadResponse};
+pub use sse::{SseRequestBuilder, SseSubscription};
+#[cfg(feature = "upload")]
+pub use upload::UploadRequestBuilder;
+#[cfg(feature = "websocket")]
+pub use websocket::{WebSocketReceiver, WebSocketRequestBuilder, WebSocketSender};
 
 /// Allows to modify and inspect a Request/Response
 pub trait Interceptor {
@@ -86,6 +56,7 @@ impl Interceptor for DoNothingInterceptor {}
 pub struct AjarsClientWeb {
     interceptor: Rc<dyn Interceptor>,
     base_url: String,
+    retry_policy: Rc<RetryPolicy>,
 }
 
 impl AjarsClientWeb {
@@ -93,20 +64,78 @@ impl AjarsClientWeb {
         AjarsClientWeb::new_with_interceptor(base_url, Rc::new(DoNothingInterceptor {}))
     }
 
+    /// Trailing slashes of the base URL are ignored, as the paths of the endpoints start with `/`.
     pub fn new_with_interceptor<P: Into<String>>(
         base_url: P,
         interceptor: Rc<dyn Interceptor>,
     ) -> Result<AjarsClientWeb, Error> {
-        Ok(AjarsClientWeb { interceptor, base_url: base_url.into() })
+        Ok(AjarsClientWeb {
+            interceptor,
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            retry_policy: Rc::new(RetryPolicy::none()),
+        })
+    }
+
+    /// Sets the policy used to send again the requests to the idempotent endpoints that fail
+    /// for a transient reason. By default, the requests are never retried.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Rc::new(retry_policy);
+        self
+    }
+
+    pub fn request<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        REST: RestType<I, O, PathParams = ()>,
+    >(
+        &'a self,
+        rest: &'a REST,
+    ) -> RequestBuilder<'a, I, O, REST> {
+        self.request_with_path(rest, &())
     }
 
-    pub fn request<'a, I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+    /// Prepares a request to an endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_with_path<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        REST: RestType<I, O>,
+    >(
         &'a self,
         rest: &'a REST,
+        path_params: &REST::PathParams,
     ) -> RequestBuilder<'a, I, O, REST> {
-        let url = format!("{}{}", &self.base_url, rest.path());
+        let url = format!("{}{}", &self.base_url, rest.path_with(path_params));
 
-        RequestBuilder::new(rest, url, self.interceptor.as_ref()).add_header("Content-Type", "application/json")
+        RequestBuilder {
+            retry_policy: self.retry_policy.clone(),
+            ..RequestBuilder::new(rest, url, self.interceptor.as_ref())
+        }
+    }
+}
+
+impl AjarsClient for AjarsClientWeb {
+    type Error = Error;
+    type RequestBuilder<'a, I, O, REST>
+        = RequestBuilder<'a, I, O, REST>
+    where
+        I: Serialize + DeserializeOwned + 'a,
+        O: Serialize + DeserializeOwned + 'a,
+        REST: RestType<I, O> + 'a;
+
+    fn request_with_path<'a, I, O, REST>(
+        &'a self,
+        rest: &'a REST,
+        path_params: &REST::PathParams,
+    ) -> RequestBuilder<'a, I, O, REST>
+    where
+        I: Serialize + DeserializeOwned + 'a,
+        O: Serialize + DeserializeOwned + 'a,
+        REST: RestType<I, O> + 'a,
+    {
+        AjarsClientWeb::request_with_path(self, rest, path_params)
     }
 }
 
@@ -114,7 +143,10 @@ pub struct RequestBuilder<'a, I: Serialize + DeserializeOwned, O: Serialize + De
     rest: &'a REST,
     interceptor: &'a dyn Interceptor,
     headers: HashMap<String, String>,
+    /// The first header that cannot be built, reported when the request is sent
+    header_error: Option<Error>,
     url: String,
+    retry_policy: Rc<RetryPolicy>,
     phantom_i: PhantomData<I>,
     phantom_o: PhantomData<O>,
 }
@@ -128,6 +160,8 @@ impl<'a, I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST:
             interceptor,
             url,
             headers: HashMap::new(),
+            header_error: None,
+            retry_policy: Rc::new(RetryPolicy::none()),
             phantom_i: PhantomData,
             phantom_o: PhantomData,
         }
@@ -141,13 +175,8 @@ impl<'a, I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST:
 
     /// Enable HTTP basic authentication.
     pub fn basic_auth(self, username: &str, password: Option<&str>) -> Result<Self, Error> {
-        let user_pass = format!("{}:{}", username, password.unwrap_or_default());
-        let encoded_user_pass = window().btoa(&user_pass).map_err(|err| Error::Builder {
-            context: "Failed to encode in base64 the basic auth string".to_owned(),
-            error: format!("{:?}", err),
-        })?;
-
-        Ok(self.add_header("AUTHORIZATION", format!("Basic {}", encoded_user_pass)))
+        let header_value = basic_auth_header(username, password)?;
+        Ok(self.add_header("AUTHORIZATION", header_value))
     }
 
     /// Enable HTTP bearer authentication.
@@ -161,82 +190,265 @@ impl<'a, I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST:
 
     /// Sends the Request to the target URL, returning a
     /// future Response.
-    pub async fn send(self, data: &I) -> Result<O, Error> {
-        let request = match self.rest.method() {
-            HttpMethod::DELETE => as_query_string(&self.url, http::Method::DELETE, &self.headers, data),
-            HttpMethod::GET => as_query_string(&self.url, http::Method::GET, &self.headers, data),
-            HttpMethod::POST => as_body(&self.url, http::Method::POST, &self.headers, data),
-            HttpMethod::PUT => as_body(&self.url, http::Method::PUT, &self.headers, data),
-        }?;
+    pub async fn send(self, data: &I) -> Result<O, AjarsError<REST::Error, Error>> {
+        self.send_full(data).await.map(ajars_core::Response::into_body)
+    }
+
+    /// Sends the Request to the target URL, returning a future Response
+    /// with the status and the headers of the HTTP response along with the output.
+    ///
+    /// The request is sent again, as long as the retry policy of the client allows it,
+    /// if it fails for a transient reason.
+    pub async fn send_full(mut self, data: &I) -> Result<ajars_core::Response<O>, AjarsError<REST::Error, Error>> {
+        if let Some(error) = self.header_error.take() {
+            return Err(AjarsError::Transport(error));
+        }
+
+        let this = &self;
+        self.retry_policy
+            .run(
+                self.rest.is_idempotent(),
+                || async move {
+                    let request = this.prepare(data)?;
+                    this.execute(request).await
+                },
+                |delay: Duration| gloo_timers::future::sleep(delay),
+            )
+            .await
+    }
+
+    /// Validates the input and builds the request that carries it.
+    fn prepare(&self, data: &I) -> Result<Request, AjarsError<REST::Error, Error>> {
+        // An input that fails the validation of the endpoint is not sent
+        #[cfg(feature = "validator")]
+        self.rest.validate(data).map_err(AjarsError::Validation)?;
 
-        let request = self.interceptor.before_request(&self.url, request)?;
+        let method = http_method(self.rest.method());
+
+        // An empty input, like `()`, is sent without a query string and without a body
+        match self.rest.input_location() {
+            _ if is_empty_body(data) => Ok(as_empty(&self.url, method, &self.headers)),
+            InputLocation::Query => as_query_string(&self.url, method, &self.headers, data),
+            InputLocation::Body => as_body::<REST::Codec, _>(&self.url, method, &self.headers, data),
+            InputLocation::Form => as_form(&self.url, method, &self.headers, data),
+        }
+        .map_err(AjarsError::Encode)?
+        .map_err(AjarsError::Transport)
+    }
+
+    /// Sends the request through the interceptor and reads the output of the endpoint from the response.
+    async fn execute(&self, request: Request) -> Result<ajars_core::Response<O>, AjarsError<REST::Error, Error>> {
+        let response = self.fetch(request).await?;
+
+        // A successful status other than the one declared by the endpoint breaks the contract of the endpoint
+        let status = response.status();
+        if status < 400 && !self.rest.accepts_success_status(status) {
+            return Err(AjarsError::UnexpectedStatus {
+                status: status.into(),
+                expected: self.rest.success_status().into(),
+            });
+        }
+
+        let headers = response.headers().entries().collect();
+        let body =
+            into_http_response::<REST::Codec, _, _>(response, self.rest.method(), self.rest.error_status()).await?;
+        Ok(ajars_core::Response::new(status.into(), headers, body))
+    }
+
+    /// Sends the request through the interceptor.
+    async fn fetch(&self, request: Request) -> Result<Response, AjarsError<REST::Error, Error>> {
+        let request = self.interceptor.before_request(&self.url, request).map_err(AjarsError::Transport)?;
 
         let response = request.send().await.map_err(|err| Error::Builder {
             context: "Failed to send request".to_string(),
             error: format!("{:?}", err),
         });
 
-        let response = self.interceptor.after_response(response)?;
-        into_http_response(response).await
+        self.interceptor.after_response(response).map_err(AjarsError::Transport)
     }
 }
 
+impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>
+    AjarsRequestBuilder<I, O, REST> for RequestBuilder<'_, I, O, REST>
+{
+    type Error = Error;
+
+    fn header(self, name: &str, value: &str) -> Self {
+        self.add_header(name, value)
+    }
+
+    fn basic_auth(mut self, username: &str, password: Option<&str>) -> Self {
+        match basic_auth_header(username, password) {
+            Ok(header_value) => self.add_header("AUTHORIZATION", header_value),
+            Err(error) => {
+                self.header_error.get_or_insert(error);
+                self
+            }
+        }
+    }
+
+    fn bearer_auth(self, token: &str) -> Self {
+        RequestBuilder::bearer_auth(self, token)
+    }
+
+    fn send(self, input: &I) -> impl Future<Output = Result<O, AjarsError<REST::Error, Error>>> {
+        RequestBuilder::send(self, input)
+    }
+
+    fn send_full(
+        self,
+        input: &I,
+    ) -> impl Future<Output = Result<ajars_core::Response<O>, AjarsError<REST::Error, Error>>> {
+        RequestBuilder::send_full(self, input)
+    }
+}
+
+/// The value of the `Authorization` header of the basic authentication, encoded in base64 by the browser.
+fn basic_auth_header(username: &str, password: Option<&str>) -> Result<String, Error> {
+    let user_pass = format!("{}:{}", username, password.unwrap_or_default());
+    let encoded_user_pass = window().btoa(&user_pass).map_err(|err| Error::Builder {
+        context: "Failed to encode in base64 the basic auth string".to_owned(),
+        error: format!("{:?}", err),
+    })?;
+    Ok(format!("Basic {}", encoded_user_pass))
+}
+
+fn http_method(method: &HttpMethod) -> Method {
+    match method {
+        HttpMethod::DELETE => http::Method::DELETE,
+        HttpMethod::GET => http::Method::GET,
+        HttpMethod::HEAD => http::Method::HEAD,
+        HttpMethod::OPTIONS => http::Method::OPTIONS,
+        HttpMethod::PATCH => http::Method::PATCH,
+        HttpMethod::POST => http::Method::POST,
+        HttpMethod::PUT => http::Method::PUT,
+    }
+}
+
+fn as_empty(uri: &str, method: Method, headers: &HashMap<String, String>) -> Result<Request, Error> {
+    let mut request = gloo_net::http::RequestBuilder::new(uri).method(method).mode(RequestMode::Cors);
+
+    for (header_key, header_value) in headers {
+        request = request.header(header_key, header_value);
+    }
+
+    request
+        .build()
+        .map_err(|err| Error::Builder { context: "Failed to build Request".to_owned(), error: format!("{:?}", err) })
+}
+
+/// Encodes the data as a query string. The outer error is the failure of the encoding.
 fn as_query_string<I: Serialize + DeserializeOwned>(
     uri: &str,
     method: Method,
     headers: &HashMap<String, String>,
     data: &I,
-) -> Result<gloo_net::http::Request, Error> {
+) -> Result<Result<Request, Error>, CodecError> {
     let mut uri = uri.to_owned();
     uri.push('?');
-    uri.push_str(&serde_urlencoded::to_string(data).map_err(|err| Error::Builder {
-        context: "Failed to serialize data as query string".to_owned(),
-        error: format!("{:?}", err),
-    })?);
+    uri.push_str(&serde_urlencoded::to_string(data).map_err(CodecError::new)?);
     let mut request = gloo_net::http::RequestBuilder::new(&uri).method(method).mode(RequestMode::Cors);
 
     for (header_key, header_value) in headers {
         request = request.header(header_key, header_value);
     }
 
-    request
+    Ok(request
         .build()
-        .map_err(|err| Error::Builder { context: "Failed to build Request".to_owned(), error: format!("{:?}", err) })
+        .map_err(|err| Error::Builder { context: "Failed to build Request".to_owned(), error: format!("{:?}", err) }))
 }
 
-fn as_body<I: Serialize + DeserializeOwned>(
+/// Encodes the data as a form body. The outer error is the failure of the encoding.
+fn as_form<I: Serialize + DeserializeOwned>(
     uri: &str,
     method: Method,
     headers: &HashMap<String, String>,
     data: &I,
-) -> Result<Request, Error> {
-    let mut request = gloo_net::http::RequestBuilder::new(uri).method(method).mode(RequestMode::Cors);
+) -> Result<Result<Request, Error>, CodecError> {
+    let body = serde_urlencoded::to_string(data).map_err(CodecError::new)?;
+    let mut request = gloo_net::http::RequestBuilder::new(uri)
+        .method(method)
+        .mode(RequestMode::Cors)
+        .header("Content-Type", "application/x-www-form-urlencoded");
 
     for (header_key, header_value) in headers {
         request = request.header(header_key, header_value);
     }
 
-    request.json(data).map_err(|err| Error::Builder {
-        context: "Failed to serialize data as JSON body".to_owned(),
-        error: format!("{:?}", err),
-    })
+    Ok(request
+        .body(body)
+        .map_err(|err| Error::Builder { context: "Failed to build Request".to_owned(), error: format!("{:?}", err) }))
+}
+
+/// Encodes the data with the codec `C`. The outer error is the failure of the codec.
+fn as_body<C: Codec, I: Serialize + DeserializeOwned>(
+    uri: &str,
+    method: Method,
+    headers: &HashMap<String, String>,
+    data: &I,
+) -> Result<Result<Request, Error>, CodecError> {
+    let body = C::encode(data)?;
+    let mut request = gloo_net::http::RequestBuilder::new(uri)
+        .method(method)
+        .mode(RequestMode::Cors)
+        .header("Content-Type", C::CONTENT_TYPE);
+
+    for (header_key, header_value) in headers {
+        request = request.header(header_key, header_value);
+    }
+
+    Ok(request
+        .body(Uint8Array::from(body.as_slice()))
+        .map_err(|err| Error::Builder { context: "Failed to build Request".to_owned(), error: format!("{:?}", err) }))
 }
 
-async fn into_http_response<O: Serialize + DeserializeOwned>(response: Response) -> Result<O, Error> {
+/// Returns the response if its status is not an error status, decoding the typed error of the endpoint otherwise.
+async fn check_status<C: Codec, E: Serialize + DeserializeOwned>(
+    response: Response,
+    error_status: u16,
+) -> Result<Response, AjarsError<E, Error>> {
     let status = HttpStatus::from(response.status());
 
-    // This 'if' check is how it is performed by Reqwest
+    // The body of an error status is read before decoding anything, as it may not be encoded with the codec
     if status.is_client_error() || status.is_server_error() {
-        Err(Error::Response {
-            status,
-            context: format!("Error HTTP status code received: {}", status.status()),
-            error: format!("Status code error: {:?}", response),
-        })
+        let headers = response.headers().entries().collect();
+        let body = read_body(response).await?;
+        return Err(AjarsError::from_error_response::<C>(status.status(), error_status, headers, body));
+    }
+    Ok(response)
+}
+
+async fn into_http_response<C: Codec, O: Serialize + DeserializeOwned, E: Serialize + DeserializeOwned>(
+    response: Response,
+    method: &HttpMethod,
+    error_status: u16,
+) -> Result<O, AjarsError<E, Error>> {
+    let response = check_status::<C, _>(response, error_status).await?;
+
+    if *method == HttpMethod::HEAD {
+        // A response to a HEAD request never has a body
+        head_output().map_err(AjarsError::Decode)
     } else {
-        response.json().await.map_err(|err| Error::Response {
+        let body = read_body(response).await?;
+
+        // An empty body, like the one of a '204 No Content' response, carries an empty output such as `()`
+        if body.is_empty()
+            && let Some(output) = empty_body_output()
+        {
+            return Ok(output);
+        }
+        C::decode(&body).map_err(AjarsError::Decode)
+    }
+}
+
+/// Reads the whole body of a response.
+async fn read_body<E>(response: Response) -> Result<Vec<u8>, AjarsError<E, Error>> {
+    let status = HttpStatus::from(response.status());
+    response.binary().await.map_err(|err| {
+        AjarsError::Transport(Error::Response {
             status,
-            context: format!("Failed to read JSON body: {}", status.status()),
+            context: "Failed to read the response body".to_owned(),
             error: format!("{:?}", err),
         })
-    }
+    })
 }
@@ -0,0 +1,136 @@
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use ajars_core::{AjarsError, Codec, CodecError, Json, Rest, RestSse, RestType, SseEvent, is_empty_body};
+use futures_util::{Stream, StreamExt};
+use gloo_net::eventsource::futures::{EventSource, EventSourceBuilder, EventSourceSubscription};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::AjarsClientWeb;
+use crate::error::Error;
+
+impl AjarsClientWeb {
+    pub fn request_sse<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &'a self,
+        rest: &'a RestSse<I, O, (), E>,
+    ) -> SseRequestBuilder<'a, I, O, (), E> {
+        self.request_sse_with_path(rest, &())
+    }
+
+    /// Prepares a request to a Server-Sent Events endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_sse_with_path<
+        'a,
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        P: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &'a self,
+        rest: &'a RestSse<I, O, P, E>,
+        path_params: &P,
+    ) -> SseRequestBuilder<'a, I, O, P, E> {
+        let url = format!("{}{}", &self.base_url, rest.rest().path_with(path_params));
+        SseRequestBuilder { rest: rest.rest(), url, with_credentials: false }
+    }
+}
+
+/// A request to a Server-Sent Events endpoint, sent by the `EventSource` of the browser.
+///
+/// The `EventSource` cannot send custom headers, so the requests are not passed to the interceptor of the client.
+pub struct SseRequestBuilder<
+    'a,
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+> {
+    // The endpoint is needed only to validate the input
+    #[cfg_attr(not(feature = "validator"), allow(dead_code))]
+    rest: &'a Rest<I, O, P, E>,
+    url: String,
+    with_credentials: bool,
+}
+
+impl<I, O, P, E> SseRequestBuilder<'_, I, O, P, E>
+where
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+{
+    /// Sends the cookies also to a server of another origin.
+    pub fn with_credentials(mut self, with_credentials: bool) -> Self {
+        self.with_credentials = with_credentials;
+        self
+    }
+
+    /// Opens an `EventSource` to the target URL, returning the stream of its events.
+    ///
+    /// The browser reconnects when the connection is lost, sending the ID of the last event received
+    /// in the `Last-Event-ID` header. The stream ends after yielding the error that makes the browser stop
+    /// reconnecting; the `EventSource` is closed when the stream is dropped.
+    pub fn send(self, data: &I) -> Result<SseSubscription<O, E>, AjarsError<E, Error>> {
+        // An input that fails the validation of the endpoint is not sent
+        #[cfg(feature = "validator")]
+        self.rest.validate(data).map_err(AjarsError::Validation)?;
+
+        // An empty input, like `()`, is sent without a query string
+        let url = if is_empty_body(data) {
+            self.url
+        } else {
+            let query = serde_urlencoded::to_string(data).map_err(|err| AjarsError::Encode(CodecError::new(err)))?;
+            format!("{}?{}", self.url, query)
+        };
+
+        let mut event_source = EventSourceBuilder::new()
+            .with_credentials(self.with_credentials)
+            .build(&url)
+            .map_err(|err| AjarsError::Transport(Error::EventSource { error: format!("{:?}", err) }))?;
+        let messages = event_source
+            .subscribe("message")
+            .map_err(|err| AjarsError::Transport(Error::EventSource { error: format!("{:?}", err) }))?;
+
+        Ok(SseSubscription { _event_source: event_source, messages, closed: false, phantom: PhantomData })
+    }
+}
+
+/// The stream of the events received by an `EventSource`.
+pub struct SseSubscription<O, E> {
+    // The EventSource is closed when it is dropped
+    _event_source: EventSource,
+    messages: EventSourceSubscription,
+    closed: bool,
+    phantom: PhantomData<fn() -> (O, E)>,
+}
+
+impl<O: DeserializeOwned, E> Stream for SseSubscription<O, E> {
+    type Item = Result<SseEvent<O>, AjarsError<E, Error>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.closed {
+            return Poll::Ready(None);
+        }
+        let event = match ready!(self.messages.poll_next_unpin(cx)) {
+            Some(Ok((_, message))) => {
+                let data = message.data().as_string().unwrap_or_default();
+                let id = Some(message.last_event_id()).filter(|id| !id.is_empty());
+                Json::decode(data.as_bytes()).map(|data| SseEvent { id, data }).map_err(AjarsError::Decode)
+            }
+            // The browser reports only the errors after which it stops reconnecting
+            Some(Err(err)) => {
+                self.closed = true;
+                Err(AjarsError::Transport(Error::EventSource { error: format!("{:?}", err) }))
+            }
+            None => return Poll::Ready(None),
+        };
+        Poll::Ready(Some(event))
+    }
+}
@@ -0,0 +1,138 @@
+use ajars_core::{AjarsError, Codec, FILE_PART, FilePart, Json, METADATA_PART, Rest, RestType, RestUpload};
+use gloo_net::http::Request;
+use js_sys::{Array, Uint8Array};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use web_sys::{Blob, BlobPropertyBag, FormData, RequestMode};
+
+use crate::error::Error;
+use crate::{AjarsClientWeb, RequestBuilder, http_method};
+
+impl AjarsClientWeb {
+    pub fn request_upload<
+        'a,
+        M: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &'a self,
+        rest: &'a RestUpload<M, O, (), E>,
+    ) -> UploadRequestBuilder<'a, M, O, (), E> {
+        self.request_upload_with_path(rest, &())
+    }
+
+    /// Prepares a request to an upload endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_upload_with_path<
+        'a,
+        M: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        P: Serialize + DeserializeOwned,
+        E: Serialize + DeserializeOwned,
+    >(
+        &'a self,
+        rest: &'a RestUpload<M, O, P, E>,
+        path_params: &P,
+    ) -> UploadRequestBuilder<'a, M, O, P, E> {
+        UploadRequestBuilder { request: self.request_with_path(rest.rest(), path_params), files: vec![] }
+    }
+}
+
+/// A request to an upload endpoint, whose body is a `FormData` with the metadata and a `Blob` for each file.
+pub struct UploadRequestBuilder<
+    'a,
+    M: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+> {
+    request: RequestBuilder<'a, M, O, Rest<M, O, P, E>>,
+    files: Vec<FilePart>,
+}
+
+impl<M, O, P, E> UploadRequestBuilder<'_, M, O, P, E>
+where
+    M: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+{
+    /// Adds a file to the upload.
+    pub fn file(mut self, file: FilePart) -> Self {
+        self.files.push(file);
+        self
+    }
+
+    /// Adds a set of files to the upload.
+    pub fn files<F: IntoIterator<Item = FilePart>>(mut self, files: F) -> Self {
+        self.files.extend(files);
+        self
+    }
+
+    /// Add a header to the request
+    pub fn add_header<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.request = self.request.add_header(key, value);
+        self
+    }
+
+    /// Enable HTTP bearer authentication.
+    pub fn bearer_auth<T>(mut self, token: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        self.request = self.request.bearer_auth(token);
+        self
+    }
+
+    /// Sends the metadata and the files to the target URL as a `multipart/form-data` body,
+    /// returning a future Response.
+    pub async fn send(self, metadata: &M) -> Result<O, AjarsError<E, Error>> {
+        // A metadata that fails the validation of the endpoint is not sent
+        #[cfg(feature = "validator")]
+        self.request.rest.validate(metadata).map_err(AjarsError::Validation)?;
+
+        let metadata =
+            String::from_utf8(Json::encode(metadata).map_err(AjarsError::Encode)?).expect("JSON should be valid UTF-8");
+        let request = as_multipart(&self.request, &metadata, &self.files).map_err(AjarsError::Transport)?;
+        self.request.execute(request).await.map(ajars_core::Response::into_body)
+    }
+}
+
+fn as_multipart<M, O, P, E>(
+    builder: &RequestBuilder<'_, M, O, Rest<M, O, P, E>>,
+    metadata: &str,
+    files: &[FilePart],
+) -> Result<Request, Error>
+where
+    M: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+{
+    let failure = |context: &str, err| Error::Builder { context: context.to_owned(), error: format!("{:?}", err) };
+
+    let form = FormData::new().map_err(|err| failure("Failed to create the FormData", err))?;
+    form.append_with_str(METADATA_PART, metadata).map_err(|err| failure("Failed to append the metadata", err))?;
+    for file in files {
+        let options = BlobPropertyBag::new();
+        options.set_type(file.content_type());
+        let blob =
+            Blob::new_with_u8_array_sequence_and_options(&Array::of1(&Uint8Array::from(file.content())), &options)
+                .map_err(|err| failure("Failed to create the Blob of a file", err))?;
+        form.append_with_blob_and_filename(FILE_PART, &blob, file.file_name())
+            .map_err(|err| failure("Failed to append a file", err))?;
+    }
+
+    // The browser sets the `Content-Type` header, with the boundary of the parts
+    let mut request = gloo_net::http::RequestBuilder::new(&builder.url)
+        .method(http_method(builder.rest.method()))
+        .mode(RequestMode::Cors);
+
+    for (header_key, header_value) in &builder.headers {
+        request = request.header(header_key, header_value);
+    }
+
+    request
+        .body(form)
+        .map_err(|err| Error::Builder { context: "Failed to build Request".to_owned(), error: format!("{:?}", err) })
+}
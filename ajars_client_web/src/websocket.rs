@@ -0,0 +1,112 @@
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use ajars_core::{RestType, RestWebSocket, WebSocketError, decode_ws_message, encode_ws_message};
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use gloo_net::websocket::futures::WebSocket;
+use gloo_net::websocket::{Message, WebSocketError as GlooWebSocketError};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::AjarsClientWeb;
+
+impl AjarsClientWeb {
+    pub fn request_websocket<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned>(
+        &self,
+        websocket: &RestWebSocket<I, O, ()>,
+    ) -> WebSocketRequestBuilder<I, O> {
+        self.request_websocket_with_path(websocket, &())
+    }
+
+    /// Prepares the connection to a WebSocket endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    pub fn request_websocket_with_path<
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        P: Serialize + DeserializeOwned,
+    >(
+        &self,
+        websocket: &RestWebSocket<I, O, P>,
+        path_params: &P,
+    ) -> WebSocketRequestBuilder<I, O> {
+        // The connection is opened to the same host as the HTTP requests
+        let base_url = match self.base_url.strip_prefix("http") {
+            Some(rest) if rest.starts_with("://") || rest.starts_with("s://") => format!("ws{rest}"),
+            _ => self.base_url.clone(),
+        };
+        let url = format!("{}{}", base_url, websocket.rest().path_with(path_params));
+        WebSocketRequestBuilder { url, phantom: PhantomData }
+    }
+}
+
+/// The connection to a WebSocket endpoint, opened by the `WebSocket` of the browser.
+///
+/// The `WebSocket` cannot send custom headers, so the requests are not passed to the interceptor of the client.
+pub struct WebSocketRequestBuilder<I, O> {
+    url: String,
+    phantom: PhantomData<fn(I) -> O>,
+}
+
+impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned> WebSocketRequestBuilder<I, O> {
+    /// Opens a `WebSocket` to the target URL, returning the typed sink of the messages sent to the server
+    /// and the typed stream of the messages received from it.
+    ///
+    /// The messages sent before the connection is established wait for it; the `WebSocket` is closed
+    /// when both the sender and the receiver are dropped.
+    pub fn connect(self) -> Result<(WebSocketSender<I>, WebSocketReceiver<O>), WebSocketError> {
+        let socket = WebSocket::open(&self.url).map_err(|err| WebSocketError::connection(err.to_string()))?;
+        let (sink, stream) = socket.split();
+        Ok((WebSocketSender { sink, phantom: PhantomData }, WebSocketReceiver { stream, phantom: PhantomData }))
+    }
+}
+
+/// The typed sink of the messages sent to the server of a WebSocket endpoint.
+pub struct WebSocketSender<I> {
+    sink: SplitSink<WebSocket, Message>,
+    phantom: PhantomData<fn(I)>,
+}
+
+impl<I: Serialize> Sink<I> for WebSocketSender<I> {
+    type Error = WebSocketError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink.poll_ready_unpin(cx).map_err(|err| WebSocketError::connection(err.to_string()))
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, message: I) -> Result<(), Self::Error> {
+        let text = encode_ws_message(&message)?;
+        self.sink.start_send_unpin(Message::Text(text)).map_err(|err| WebSocketError::connection(err.to_string()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink.poll_flush_unpin(cx).map_err(|err| WebSocketError::connection(err.to_string()))
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink.poll_close_unpin(cx).map_err(|err| WebSocketError::connection(err.to_string()))
+    }
+}
+
+/// The typed stream of the messages received from the server of a WebSocket endpoint.
+/// It ends when the connection is closed cleanly.
+pub struct WebSocketReceiver<O> {
+    stream: SplitStream<WebSocket>,
+    phantom: PhantomData<fn() -> O>,
+}
+
+impl<O: DeserializeOwned> Stream for WebSocketReceiver<O> {
+    type Item = Result<O, WebSocketError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let message = match ready!(self.stream.poll_next_unpin(cx)) {
+            Some(Ok(Message::Text(text))) => decode_ws_message(text.as_bytes()),
+            Some(Ok(Message::Bytes(bytes))) => decode_ws_message(&bytes),
+            Some(Err(GlooWebSocketError::ConnectionClose(event))) if event.was_clean => return Poll::Ready(None),
+            Some(Err(error)) => return Poll::Ready(Some(Err(WebSocketError::connection(error.to_string())))),
+            None => return Poll::Ready(None),
+        };
+        Poll::Ready(Some(message.map_err(WebSocketError::from)))
+    }
+}
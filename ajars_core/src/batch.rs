@@ -0,0 +1,591 @@
+use std::collections::HashMap;
+use std::future::{Future, ready};
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error as ThisError;
+
+use crate::codec::tracked_error;
+use crate::{
+    AjarsError, CodecError, DEFAULT_SUCCESS_STATUS, NO_CONTENT_STATUS, Rest, RestType, accepts_status, is_empty_body,
+    is_success_status,
+};
+
+/// The HTTP status code of the results of the calls to endpoints that have no handler in the batch.
+pub const UNKNOWN_ENDPOINT_STATUS: u16 = 404;
+
+/// The HTTP status code of the results of the calls whose path parameters or input cannot be decoded.
+pub const INVALID_CALL_STATUS: u16 = 400;
+
+/// The HTTP status code of the results of the calls whose output or error cannot be encoded.
+pub const ENCODING_ERROR_STATUS: u16 = 500;
+
+/// The HTTP status code of the batches that have more calls than allowed by their [`BatchHandlers`].
+pub const TOO_MANY_CALLS_STATUS: u16 = 413;
+
+/// The maximum number of calls of a batch, unless the [`BatchHandlers`] allow another one.
+pub const DEFAULT_MAX_BATCH_CALLS: usize = 100;
+
+/// The endpoint that sends the calls of a [`RestBatch`] and receives their results.
+pub type BatchRest = Rest<Vec<BatchCall>, Vec<BatchResult>>;
+
+/// An endpoint that carries several calls to other endpoints in a single `POST` request, and their results in its response.
+///
+/// The calls are identified by the [`RestType::id`] of their endpoints and are dispatched to the handlers registered
+/// in a [`BatchHandlers`]. Their path parameters, inputs, outputs and errors are always encoded as JSON,
+/// whatever the codec of their endpoints.
+pub struct RestBatch {
+    rest: BatchRest,
+}
+
+impl Clone for RestBatch {
+    fn clone(&self) -> Self {
+        Self { rest: self.rest.clone() }
+    }
+}
+
+impl RestBatch {
+    /// # Panics
+    ///
+    /// Panics if the path is not valid according to [`check_path`](crate::check_path);
+    /// for a `const` endpoint it fails the build.
+    pub const fn post(path: &'static str) -> Self {
+        Self { rest: Rest::post(path) }
+    }
+
+    /// The endpoint that sends the calls of the batch and receives their results.
+    pub fn rest(&self) -> &BatchRest {
+        &self.rest
+    }
+}
+
+/// A call of a batch: the id of its endpoint, its path parameters and its input.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BatchCall {
+    /// The id of the endpoint, see [`RestType::id`].
+    pub endpoint: String,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub path_params: Value,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub input: Value,
+}
+
+/// The result of a call of a batch: the HTTP status code and the body that its endpoint would have answered with.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BatchResult {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub body: Value,
+}
+
+impl BatchResult {
+    fn new<T: Serialize>(status: u16, body: &T) -> Self {
+        match serde_json::to_value(body) {
+            Ok(body) => Self { status, body },
+            Err(error) => Self::failure(ENCODING_ERROR_STATUS, format!("Failed to encode the result: {}", error)),
+        }
+    }
+
+    fn failure(status: u16, message: String) -> Self {
+        Self { status, body: Value::String(message) }
+    }
+
+    /// The body as a message: the text of the string bodies, the JSON encoding of the others.
+    fn message(&self) -> String {
+        match &self.body {
+            Value::String(message) => message.clone(),
+            body => body.to_string(),
+        }
+    }
+}
+
+/// The parts of the request that carries a batch, shared by all its calls.
+///
+/// The calls of a batch do not go through the routes of their endpoints, so neither the extractors
+/// nor the middleware of those routes run for them; the handlers that need the headers of the request,
+/// like the credentials, receive them from the context.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchContext {
+    headers: Vec<(String, String)>,
+}
+
+impl BatchContext {
+    pub fn new(headers: Vec<(String, String)>) -> Self {
+        Self { headers }
+    }
+
+    /// The headers of the request as name and value pairs, a pair for each value.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Returns the first value of a header. The case of the name is ignored.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(header, _)| header.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+}
+
+/// The error of a batch that has more calls than allowed, answered with [`TOO_MANY_CALLS_STATUS`].
+#[derive(Debug, Clone, PartialEq, Eq, ThisError)]
+#[error("The batch has {calls} calls, more than the maximum of {max_calls}")]
+pub struct TooManyBatchCalls {
+    pub calls: usize,
+    pub max_calls: usize,
+}
+
+type BatchFuture = Pin<Box<dyn Future<Output = BatchResult> + Send>>;
+
+type BatchHandler = Box<dyn Fn(BatchContext, BatchCall) -> BatchFuture + Send + Sync>;
+
+/// The handlers of the endpoints that can be called through a [`RestBatch`], by endpoint id.
+///
+/// A handler receives the path parameters, if any, and the input of the call, and fails with the error type
+/// of the endpoint. With the `validator` feature, the inputs are validated before calling the handlers.
+///
+/// The handlers are called instead of the routes of their endpoints, so the middleware of those routes,
+/// like an authentication layer, does not run for the calls of a batch: only the middleware of the batch route does.
+/// The handlers registered with [`BatchHandlers::endpoint_with_context`] receive the headers of the batch request
+/// to check them on their own.
+pub struct BatchHandlers {
+    handlers: HashMap<String, BatchHandler>,
+    max_calls: usize,
+}
+
+impl Default for BatchHandlers {
+    fn default() -> Self {
+        Self { handlers: HashMap::new(), max_calls: DEFAULT_MAX_BATCH_CALLS }
+    }
+}
+
+impl BatchHandlers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of calls of a batch, [`DEFAULT_MAX_BATCH_CALLS`] by default.
+    /// A batch with more calls is rejected as a whole, before calling any handler.
+    pub fn with_max_calls(mut self, max_calls: usize) -> Self {
+        self.max_calls = max_calls;
+        self
+    }
+
+    /// The maximum number of calls of a batch.
+    pub fn max_calls(&self) -> usize {
+        self.max_calls
+    }
+
+    /// Registers the handler of an endpoint without path parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the endpoint already has a handler.
+    pub fn endpoint<I, O, REST, H, R>(self, rest: &REST, handler: H) -> Self
+    where
+        I: Serialize + DeserializeOwned + Send + 'static,
+        O: Serialize + DeserializeOwned + 'static,
+        REST: RestType<I, O, PathParams = ()>,
+        REST::Error: 'static,
+        H: Fn(I) -> R + Send + Sync + 'static,
+        R: Future<Output = Result<O, REST::Error>> + Send + 'static,
+    {
+        self.endpoint_with_path(rest, move |_: (), input| handler(input))
+    }
+
+    /// Registers the handler of an endpoint with path parameters.
+    /// The handler receives the path parameters right before the input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the endpoint already has a handler.
+    pub fn endpoint_with_path<I, O, REST, H, R>(self, rest: &REST, handler: H) -> Self
+    where
+        I: Serialize + DeserializeOwned + Send + 'static,
+        O: Serialize + DeserializeOwned + 'static,
+        REST: RestType<I, O>,
+        REST::PathParams: 'static,
+        REST::Error: 'static,
+        H: Fn(REST::PathParams, I) -> R + Send + Sync + 'static,
+        R: Future<Output = Result<O, REST::Error>> + Send + 'static,
+    {
+        self.endpoint_with_context(rest, move |_: BatchContext, path_params, input| handler(path_params, input))
+    }
+
+    /// Registers the handler of an endpoint that receives the context of the batch request,
+    /// like its headers, right before the path parameters and the input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the endpoint already has a handler.
+    pub fn endpoint_with_context<I, O, REST, H, R>(mut self, rest: &REST, handler: H) -> Self
+    where
+        I: Serialize + DeserializeOwned + Send + 'static,
+        O: Serialize + DeserializeOwned + 'static,
+        REST: RestType<I, O>,
+        REST::PathParams: 'static,
+        REST::Error: 'static,
+        H: Fn(BatchContext, REST::PathParams, I) -> R + Send + Sync + 'static,
+        R: Future<Output = Result<O, REST::Error>> + Send + 'static,
+    {
+        let id = rest.id();
+        assert!(!self.handlers.contains_key(&id), "The endpoint [{}] already has a batch handler", id);
+
+        let success_status = rest.success_status();
+        let error_status = rest.error_status();
+        #[cfg(feature = "validator")]
+        let validator = rest.input_validator();
+
+        let handler = move |context: BatchContext, call: BatchCall| -> BatchFuture {
+            let decoded = serde_json::from_value::<REST::PathParams>(call.path_params)
+                .and_then(|path_params| serde_json::from_value::<I>(call.input).map(|input| (path_params, input)));
+            let (path_params, input) = match decoded {
+                Ok(decoded) => decoded,
+                Err(error) => {
+                    let message = format!("Failed to decode the call: {}", error);
+                    return Box::pin(ready(BatchResult::failure(INVALID_CALL_STATUS, message)));
+                }
+            };
+
+            #[cfg(feature = "validator")]
+            if let Err(errors) = validator(&input) {
+                return Box::pin(ready(BatchResult::new(crate::VALIDATION_ERROR_STATUS, &errors)));
+            }
+
+            let result = handler(context, path_params, input);
+            Box::pin(async move {
+                match result.await {
+                    // An empty output, like `()`, of an endpoint with the default success status is sent as '204 No Content'
+                    Ok(output) if success_status == DEFAULT_SUCCESS_STATUS && is_empty_body(&output) => {
+                        BatchResult { status: NO_CONTENT_STATUS, body: Value::Null }
+                    }
+                    Ok(output) => BatchResult::new(success_status, &output),
+                    Err(error) => BatchResult::new(error_status, &error),
+                }
+            })
+        };
+        self.handlers.insert(id, Box::new(handler));
+        self
+    }
+
+    /// Calls the handlers of the calls one after the other, and returns their results in the same order.
+    /// The calls to endpoints without a handler fail with [`UNKNOWN_ENDPOINT_STATUS`].
+    /// A batch with more calls than the maximum fails as a whole.
+    pub async fn dispatch(
+        &self,
+        context: BatchContext,
+        calls: Vec<BatchCall>,
+    ) -> Result<Vec<BatchResult>, TooManyBatchCalls> {
+        if calls.len() > self.max_calls {
+            return Err(TooManyBatchCalls { calls: calls.len(), max_calls: self.max_calls });
+        }
+
+        let mut results = Vec::with_capacity(calls.len());
+        for call in calls {
+            results.push(match self.handlers.get(&call.endpoint) {
+                Some(handler) => handler(context.clone(), call).await,
+                None => BatchResult::failure(
+                    UNKNOWN_ENDPOINT_STATUS,
+                    format!("The endpoint [{}] cannot be called in a batch", call.endpoint),
+                ),
+            });
+        }
+        Ok(results)
+    }
+}
+
+/// The calls of a batch, collected by the clients before sending them.
+#[derive(Debug, Default)]
+pub struct BatchCalls {
+    calls: Vec<BatchCall>,
+}
+
+impl BatchCalls {
+    /// Adds a call to the batch and returns the handle that reads its output from the results of the batch.
+    /// With the `validator` feature, an input that fails the validation of the endpoint is not added.
+    pub fn push<I, O, REST, C>(
+        &mut self,
+        rest: &REST,
+        path_params: &REST::PathParams,
+        input: &I,
+    ) -> Result<Batched<O, REST::Error>, AjarsError<REST::Error, C>>
+    where
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        REST: RestType<I, O>,
+    {
+        #[cfg(feature = "validator")]
+        rest.validate(input).map_err(AjarsError::Validation)?;
+
+        self.calls.push(BatchCall {
+            endpoint: rest.id(),
+            path_params: serde_json::to_value(path_params)
+                .map_err(|error| AjarsError::Encode(CodecError::new(error)))?,
+            input: serde_json::to_value(input).map_err(|error| AjarsError::Encode(CodecError::new(error)))?,
+        });
+        Ok(Batched {
+            index: self.calls.len() - 1,
+            success_status: rest.success_status(),
+            error_status: rest.error_status(),
+            phantom: PhantomData,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    pub fn into_calls(self) -> Vec<BatchCall> {
+        self.calls
+    }
+}
+
+/// A call added to a batch, with the output type `O` and the error type `E` of its endpoint.
+/// It reads its result from the [`BatchResults`] of the batch.
+#[derive(Debug)]
+pub struct Batched<O, E> {
+    index: usize,
+    success_status: u16,
+    error_status: u16,
+    phantom: PhantomData<fn() -> (O, E)>,
+}
+
+/// The results of the calls of a batch, in the order of the calls.
+/// `C` is the error type of the client backend that sent the batch.
+#[derive(Debug)]
+pub struct BatchResults<C> {
+    results: Vec<BatchResult>,
+    phantom: PhantomData<fn() -> C>,
+}
+
+impl<C> BatchResults<C> {
+    /// Fails if the number of results is not the number of calls of the batch.
+    pub fn new(calls: usize, results: Vec<BatchResult>) -> Result<Self, CodecError> {
+        if results.len() != calls {
+            return Err(CodecError::new(format!(
+                "The batch has {} calls but its response has {} results",
+                calls,
+                results.len()
+            )));
+        }
+        Ok(Self { results, phantom: PhantomData })
+    }
+
+    /// Returns the output of the call, or its error: the typed error of its endpoint when the result has
+    /// the error status of the endpoint, [`AjarsError::BatchCall`] for the other error statuses.
+    /// A call added to another batch, that has more calls than this one, fails with [`AjarsError::Decode`].
+    pub fn get<O: DeserializeOwned, E: DeserializeOwned>(&self, call: Batched<O, E>) -> Result<O, AjarsError<E, C>> {
+        let result = self.results.get(call.index).ok_or_else(|| {
+            AjarsError::Decode(CodecError::new(format!(
+                "The batch has no result for the call {}, as it has {} results",
+                call.index,
+                self.results.len()
+            )))
+        })?;
+        let status = result.status;
+
+        if is_success_status(status) {
+            // A successful status other than the one declared by the endpoint breaks the contract of the endpoint
+            if !accepts_status(call.success_status, status) {
+                return Err(AjarsError::UnexpectedStatus {
+                    status: status.into(),
+                    expected: call.success_status.into(),
+                });
+            }
+            return serde_path_to_error::deserialize(&result.body)
+                .map_err(|error| AjarsError::Decode(tracked_error(error)));
+        }
+
+        if status == call.error_status
+            && let Ok(error) = E::deserialize(&result.body)
+        {
+            return Err(AjarsError::Endpoint { status: status.into(), error });
+        }
+        Err(AjarsError::BatchCall { status: status.into(), message: result.message() })
+    }
+
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// The raw results of the calls, in the order of the calls.
+    pub fn results(&self) -> &[BatchResult] {
+        &self.results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    use super::*;
+    use crate::NoError;
+
+    const DOUBLE: Rest<u64, u64> = Rest::get("/double");
+    const ITEM: Rest<String, String, u64, String> = Rest::get("/items/{id}").with_error_status(409);
+    const CREATE: Rest<String, String> = Rest::post("/items").with_success_status(201);
+    const PING: Rest<(), ()> = Rest::post("/ping");
+
+    fn handlers() -> BatchHandlers {
+        BatchHandlers::new()
+            .endpoint(&DOUBLE, |input: u64| async move { Ok::<_, NoError>(input * 2) })
+            .endpoint_with_path(&ITEM, |id: u64, input: String| async move {
+                if id == 0 { Err("Not found".to_owned()) } else { Ok(format!("{}: {}", id, input)) }
+            })
+            .endpoint(&PING, |_: ()| async { Ok::<_, NoError>(()) })
+    }
+
+    /// Polls a future whose handlers never wait.
+    fn ready_now<F: Future>(future: F) -> F::Output {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("The future should be ready"),
+        }
+    }
+
+    fn call<I: Serialize, P: Serialize>(endpoint: &str, path_params: P, input: I) -> BatchCall {
+        BatchCall {
+            endpoint: endpoint.to_owned(),
+            path_params: serde_json::to_value(path_params).unwrap(),
+            input: serde_json::to_value(input).unwrap(),
+        }
+    }
+
+    #[test]
+    fn should_identify_an_endpoint_by_method_and_path() {
+        assert_eq!("GET /double", DOUBLE.id());
+        assert_eq!("GET /items/{id}", ITEM.id());
+        assert_eq!("POST /items", CREATE.id());
+    }
+
+    #[test]
+    fn should_dispatch_the_calls_to_their_handlers() {
+        let results = ready_now(handlers().dispatch(
+            BatchContext::default(),
+            vec![
+                call("GET /double", (), 21),
+                call("GET /items/{id}", 7, "seven"),
+                call("GET /items/{id}", 0, "zero"),
+                call("POST /ping", (), ()),
+            ],
+        ))
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                BatchResult { status: 200, body: Value::from(42) },
+                BatchResult { status: 200, body: Value::from("7: seven") },
+                BatchResult { status: 409, body: Value::from("Not found") },
+                BatchResult { status: 204, body: Value::Null },
+            ],
+            results
+        );
+    }
+
+    #[test]
+    fn should_fail_the_calls_that_cannot_be_dispatched() {
+        let results = ready_now(handlers().dispatch(
+            BatchContext::default(),
+            vec![call("GET /unknown", (), ()), call("GET /double", (), "not a number"), call("GET /double", (), 1)],
+        ))
+        .unwrap();
+
+        assert_eq!(UNKNOWN_ENDPOINT_STATUS, results[0].status);
+        assert_eq!(INVALID_CALL_STATUS, results[1].status);
+        assert_eq!(BatchResult { status: 200, body: Value::from(2) }, results[2]);
+    }
+
+    #[test]
+    fn should_pass_the_context_of_the_batch_request_to_the_handlers() {
+        const WHOAMI: Rest<(), String> = Rest::get("/whoami");
+        let handlers = handlers().endpoint_with_context(&WHOAMI, |context: BatchContext, _: (), _: ()| async move {
+            Ok::<_, NoError>(context.header("Authorization").unwrap_or_default().to_owned())
+        });
+        let context = BatchContext::new(vec![("authorization".to_owned(), "Bearer token".to_owned())]);
+
+        let results = ready_now(handlers.dispatch(context, vec![call("GET /whoami", (), ())])).unwrap();
+
+        assert_eq!(vec![BatchResult { status: 200, body: Value::from("Bearer token") }], results);
+    }
+
+    #[test]
+    fn should_reject_a_batch_with_too_many_calls() {
+        let handlers = handlers().with_max_calls(2);
+        let calls = vec![call("GET /double", (), 1), call("GET /double", (), 2), call("GET /double", (), 3)];
+
+        let result = ready_now(handlers.dispatch(BatchContext::default(), calls));
+
+        assert_eq!(Err(TooManyBatchCalls { calls: 3, max_calls: 2 }), result);
+    }
+
+    #[test]
+    #[should_panic(expected = "The endpoint [GET /double] already has a batch handler")]
+    fn should_panic_if_an_endpoint_has_two_handlers() {
+        handlers().endpoint(&DOUBLE, |input: u64| async move { Ok::<_, NoError>(input) });
+    }
+
+    #[test]
+    fn should_read_the_typed_results_of_the_calls() {
+        let mut calls = BatchCalls::default();
+        let double = calls.push::<_, _, _, ()>(&DOUBLE, &(), &21).unwrap();
+        let found = calls.push::<_, _, _, ()>(&ITEM, &7, &"seven".to_owned()).unwrap();
+        let missing = calls.push::<_, _, _, ()>(&ITEM, &0, &"zero".to_owned()).unwrap();
+        let ping = calls.push::<_, _, _, ()>(&PING, &(), &()).unwrap();
+        let create = calls.push::<_, _, _, ()>(&CREATE, &(), &"new".to_owned()).unwrap();
+
+        let results = ready_now(handlers().dispatch(BatchContext::default(), calls.into_calls())).unwrap();
+        let results = BatchResults::<()>::new(5, results).unwrap();
+
+        assert_eq!(42, results.get(double).unwrap());
+        assert_eq!("7: seven", results.get(found).unwrap());
+        match results.get(missing) {
+            Err(AjarsError::Endpoint { status, error }) if status == 409 => assert_eq!("Not found", error),
+            result => panic!("Expected the endpoint error, got {:?}", result),
+        }
+        results.get(ping).unwrap();
+        match results.get(create) {
+            Err(AjarsError::BatchCall { status, message }) if status == 404 => {
+                assert_eq!("The endpoint [POST /items] cannot be called in a batch", message)
+            }
+            result => panic!("Expected the batch call error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_fail_if_a_result_is_missing() {
+        assert!(BatchResults::<()>::new(2, vec![BatchResult { status: 204, body: Value::Null }]).is_err());
+    }
+
+    #[test]
+    fn should_fail_to_read_a_call_of_another_batch() {
+        let mut calls = BatchCalls::default();
+        calls.push::<_, _, _, ()>(&PING, &(), &()).unwrap();
+        let other = calls.push::<_, _, _, ()>(&PING, &(), &()).unwrap();
+
+        let results = BatchResults::<()>::new(1, vec![BatchResult { status: 204, body: Value::Null }]).unwrap();
+
+        match results.get(other) {
+            Err(AjarsError::Decode(error)) => assert!(error.to_string().contains("no result for the call 1")),
+            result => panic!("Expected a decode error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_omit_the_empty_fields_of_a_call() {
+        assert_eq!("{\"endpoint\":\"POST /ping\"}", serde_json::to_string(&call("POST /ping", (), ())).unwrap());
+        assert_eq!(call("POST /ping", (), ()), serde_json::from_str("{\"endpoint\":\"POST /ping\"}").unwrap());
+    }
+}
@@ -0,0 +1,427 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use web_time::Instant;
+
+use crate::{AjarsError, RestType};
+
+/// The state of a circuit of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircuitState {
+    /// The requests are sent, counting the consecutive failures of the server
+    Closed,
+    /// The requests fail fast with [`AjarsError::CircuitOpen`], without being sent, until the open duration is over
+    Open,
+    /// The open duration is over: a single request at a time is sent to probe the server,
+    /// closing the circuit when enough probes succeed and opening it again as soon as one fails
+    HalfOpen,
+}
+
+/// Stops sending requests to a server that keeps failing, so that the calls fail fast instead of waiting
+/// for a timeout, and lets a few requests probe the server once it had time to recover.
+///
+/// The requests are grouped in circuits, one for each base URL of the clients
+/// or, with [`CircuitBreaker::per_endpoint`], one for each endpoint. A failure is a failure of the transport
+/// or a '5xx' status whose body is not the typed error of the endpoint, see [`CircuitBreaker::is_failure`].
+///
+/// The clones of a circuit breaker share its circuits, so a clone kept aside reports the states
+/// of the circuits of the clients it was given to, e.g. in a health endpoint.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    success_threshold: u32,
+    open_duration: Duration,
+    per_endpoint: bool,
+    circuits: Arc<Mutex<HashMap<String, Circuit>>>,
+}
+
+impl Default for CircuitBreaker {
+    /// Opens a circuit after 5 consecutive failures for 30 seconds, closing it after a successful probe.
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            success_threshold: 1,
+            open_duration: Duration::from_secs(30),
+            per_endpoint: false,
+            circuits: Arc::default(),
+        }
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of consecutive failures that open a closed circuit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the threshold is zero.
+    pub fn with_failure_threshold(mut self, failure_threshold: u32) -> Self {
+        assert!(failure_threshold > 0, "The failure threshold must be at least 1");
+        self.failure_threshold = failure_threshold;
+        self
+    }
+
+    /// Sets the number of successful probes that close a half-open circuit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the threshold is zero.
+    pub fn with_success_threshold(mut self, success_threshold: u32) -> Self {
+        assert!(success_threshold > 0, "The success threshold must be at least 1");
+        self.success_threshold = success_threshold;
+        self
+    }
+
+    /// Sets how long a circuit stays open before letting the requests probe the server.
+    pub fn with_open_duration(mut self, open_duration: Duration) -> Self {
+        self.open_duration = open_duration;
+        self
+    }
+
+    /// Keeps a circuit for each endpoint instead of one for each base URL,
+    /// so that a failing endpoint does not stop the calls to the other endpoints of the same server.
+    pub fn per_endpoint(mut self) -> Self {
+        self.per_endpoint = true;
+        self
+    }
+
+    /// Returns the key of the circuit of the requests sent to an endpoint through a client with the given base URL:
+    /// the base URL or, per endpoint, the method followed by the base URL and the path template of the endpoint,
+    /// e.g. `GET http://127.0.0.1:8080/api/users/{id}`.
+    pub fn circuit<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+        &self,
+        base_url: &str,
+        rest: &REST,
+    ) -> String {
+        match self.per_endpoint {
+            true => format!("{:?} {}{}", rest.method(), base_url, rest.path()),
+            false => base_url.to_owned(),
+        }
+    }
+
+    /// Returns the state of a circuit; a circuit without requests yet is closed.
+    pub fn state(&self, circuit: &str) -> CircuitState {
+        self.circuits().get(circuit).map_or(CircuitState::Closed, |state| state.state_at(Instant::now(), self))
+    }
+
+    /// Returns the states of all the circuits with requests, sorted by key.
+    pub fn states(&self) -> Vec<(String, CircuitState)> {
+        let now = Instant::now();
+        let mut states = self
+            .circuits()
+            .iter()
+            .map(|(circuit, state)| (circuit.clone(), state.state_at(now, self)))
+            .collect::<Vec<_>>();
+        states.sort_by(|(first, _), (second, _)| first.cmp(second));
+        states
+    }
+
+    /// Whether the error tells that the server is failing: a failure of the transport, like a refused connection,
+    /// or a '5xx' status whose body is not the typed error of the endpoint.
+    pub fn is_failure<E, C>(error: &AjarsError<E, C>) -> bool {
+        match error {
+            AjarsError::Transport(_) => true,
+            AjarsError::Status { status, .. } => status.is_server_error(),
+            _ => false,
+        }
+    }
+
+    /// Sends a request of a circuit with `send` if the circuit lets it through, recording whether it failed;
+    /// fails with [`AjarsError::CircuitOpen`] otherwise.
+    pub async fn run<T, E, C, SendFuture>(&self, circuit: &str, send: SendFuture) -> Result<T, AjarsError<E, C>>
+    where
+        SendFuture: Future<Output = Result<T, AjarsError<E, C>>>,
+    {
+        let Some(probe) = self.acquire(circuit, Instant::now()) else {
+            return Err(AjarsError::CircuitOpen { circuit: circuit.to_owned() });
+        };
+
+        let mut permit = Permit { breaker: self, circuit, probe, recorded: false };
+        let result = send.await;
+        permit.record(matches!(&result, Err(error) if Self::is_failure(error)));
+        result
+    }
+
+    /// Whether a request of the circuit can be sent at the given time: `None` if it cannot,
+    /// otherwise whether the request is the probe of a half-open circuit.
+    fn acquire(&self, circuit: &str, now: Instant) -> Option<bool> {
+        let mut circuits = self.circuits();
+        let state = circuits.entry(circuit.to_owned()).or_default();
+        match state.state_at(now, self) {
+            CircuitState::Closed => Some(false),
+            CircuitState::Open => None,
+            // A single probe at a time is sent
+            CircuitState::HalfOpen if state.probing => None,
+            CircuitState::HalfOpen => {
+                if state.state == CircuitState::Open {
+                    state.state = CircuitState::HalfOpen;
+                    state.successes = 0;
+                }
+                state.probing = true;
+                Some(true)
+            }
+        }
+    }
+
+    /// Records the outcome of a request of the circuit, the probe of the circuit or not, at the given time.
+    fn record(&self, circuit: &str, failed: bool, probe: bool, now: Instant) {
+        let mut circuits = self.circuits();
+        let state = circuits.entry(circuit.to_owned()).or_default();
+        match (state.state, probe, failed) {
+            (CircuitState::Closed, _, false) => state.failures = 0,
+            (CircuitState::Closed, _, true) => {
+                state.failures += 1;
+                if state.failures >= self.failure_threshold {
+                    state.open(now);
+                }
+            }
+            (CircuitState::HalfOpen, true, false) => {
+                state.probing = false;
+                state.successes += 1;
+                if state.successes >= self.success_threshold {
+                    *state = Circuit::default();
+                }
+            }
+            (CircuitState::HalfOpen, true, true) => state.open(now),
+            // A request sent before the circuit opened does not change it, even once it is half-open:
+            // only the probes do
+            (CircuitState::Open, _, _) | (CircuitState::HalfOpen, false, _) => {}
+        }
+    }
+
+    /// Lets another request probe the circuit, when the probe was dropped before its outcome was known.
+    fn release(&self, circuit: &str) {
+        if let Some(state) = self.circuits().get_mut(circuit) {
+            state.probing = false;
+        }
+    }
+
+    fn circuits(&self) -> MutexGuard<'_, HashMap<String, Circuit>> {
+        // The circuits are left consistent by every update, so a panic while holding the lock does not break them
+        self.circuits.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The state of a circuit along with its counters.
+#[derive(Debug)]
+struct Circuit {
+    state: CircuitState,
+    failures: u32,
+    successes: u32,
+    opened_at: Instant,
+    probing: bool,
+}
+
+impl Default for Circuit {
+    fn default() -> Self {
+        Self { state: CircuitState::Closed, failures: 0, successes: 0, opened_at: Instant::now(), probing: false }
+    }
+}
+
+impl Circuit {
+    /// The state at the given time: an open circuit is half-open once its open duration is over.
+    fn state_at(&self, now: Instant, breaker: &CircuitBreaker) -> CircuitState {
+        match self.state {
+            CircuitState::Open if now.saturating_duration_since(self.opened_at) >= breaker.open_duration => {
+                CircuitState::HalfOpen
+            }
+            state => state,
+        }
+    }
+
+    fn open(&mut self, now: Instant) {
+        *self = Self { state: CircuitState::Open, opened_at: now, ..Self::default() };
+    }
+}
+
+/// A request let through a circuit, whose outcome must be recorded.
+struct Permit<'a> {
+    breaker: &'a CircuitBreaker,
+    circuit: &'a str,
+    /// Whether the request is the probe of a half-open circuit
+    probe: bool,
+    recorded: bool,
+}
+
+impl Permit<'_> {
+    fn record(&mut self, failed: bool) {
+        self.breaker.record(self.circuit, failed, self.probe, Instant::now());
+        self.recorded = true;
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if self.probe && !self.recorded {
+            self.breaker.release(self.circuit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    use super::*;
+    use crate::{CodecError, HttpStatus, NoError, Rest};
+
+    type Error = AjarsError<NoError, String>;
+
+    const CIRCUIT: &str = "http://127.0.0.1:8080";
+
+    fn status(status: u16) -> Error {
+        AjarsError::Status { status: HttpStatus::from(status), headers: vec![], body: vec![] }
+    }
+
+    /// Polls a future whose steps never wait.
+    fn ready_now<F: Future>(future: F) -> F::Output {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("The future should be ready"),
+        }
+    }
+
+    #[test]
+    fn should_count_only_the_failures_of_the_server() {
+        assert!(CircuitBreaker::is_failure(&Error::Transport("Connection refused".to_owned())));
+        assert!(CircuitBreaker::is_failure(&status(503)));
+        assert!(CircuitBreaker::is_failure(&status(500)));
+        assert!(!CircuitBreaker::is_failure(&status(404)));
+        assert!(!CircuitBreaker::is_failure(&Error::Decode(CodecError::new("Not JSON"))));
+        assert!(!CircuitBreaker::is_failure(&AjarsError::<String, String>::Endpoint {
+            status: HttpStatus::from(500),
+            error: "Down".to_owned()
+        }));
+    }
+
+    #[test]
+    fn should_open_after_the_consecutive_failures() {
+        let breaker = CircuitBreaker::new().with_failure_threshold(3);
+        let now = Instant::now();
+
+        for failed in [true, true, false, true, true] {
+            assert_eq!(Some(false), breaker.acquire(CIRCUIT, now));
+            breaker.record(CIRCUIT, failed, false, now);
+        }
+        assert_eq!(CircuitState::Closed, breaker.state(CIRCUIT));
+
+        assert_eq!(Some(false), breaker.acquire(CIRCUIT, now));
+        breaker.record(CIRCUIT, true, false, now);
+        assert_eq!(CircuitState::Open, breaker.state(CIRCUIT));
+        assert_eq!(None, breaker.acquire(CIRCUIT, now));
+    }
+
+    #[test]
+    fn should_probe_a_request_at_a_time_once_the_open_duration_is_over() {
+        let breaker = CircuitBreaker::new()
+            .with_failure_threshold(1)
+            .with_success_threshold(2)
+            .with_open_duration(Duration::from_secs(10));
+        let now = Instant::now();
+        let later = now + Duration::from_secs(10);
+
+        breaker.acquire(CIRCUIT, now);
+        breaker.record(CIRCUIT, true, false, now);
+        assert_eq!(None, breaker.acquire(CIRCUIT, now + Duration::from_secs(9)));
+
+        assert_eq!(Some(true), breaker.acquire(CIRCUIT, later));
+        assert_eq!(None, breaker.acquire(CIRCUIT, later));
+        breaker.record(CIRCUIT, false, true, later);
+        assert_eq!(CircuitState::HalfOpen, breaker.state(CIRCUIT));
+
+        assert_eq!(Some(true), breaker.acquire(CIRCUIT, later));
+        breaker.record(CIRCUIT, false, true, later);
+        assert_eq!(CircuitState::Closed, breaker.state(CIRCUIT));
+    }
+
+    #[test]
+    fn should_open_again_when_a_probe_fails() {
+        let breaker = CircuitBreaker::new().with_failure_threshold(1).with_open_duration(Duration::from_secs(10));
+        let now = Instant::now();
+        let later = now + Duration::from_secs(10);
+
+        breaker.acquire(CIRCUIT, now);
+        breaker.record(CIRCUIT, true, false, now);
+        assert_eq!(Some(true), breaker.acquire(CIRCUIT, later));
+        breaker.record(CIRCUIT, true, true, later);
+
+        assert_eq!(None, breaker.acquire(CIRCUIT, later + Duration::from_secs(9)));
+        assert_eq!(Some(true), breaker.acquire(CIRCUIT, later + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn should_let_only_the_probe_change_a_half_open_circuit() {
+        let breaker = CircuitBreaker::new().with_failure_threshold(1).with_open_duration(Duration::from_secs(10));
+        let now = Instant::now();
+        let later = now + Duration::from_secs(10);
+
+        // A request sent while the circuit is closed is still pending when the circuit opens and turns half-open
+        assert_eq!(Some(false), breaker.acquire(CIRCUIT, now));
+        assert_eq!(Some(false), breaker.acquire(CIRCUIT, now));
+        breaker.record(CIRCUIT, true, false, now);
+        assert_eq!(Some(true), breaker.acquire(CIRCUIT, later));
+
+        // Its outcome neither closes the circuit nor lets a second probe through
+        breaker.record(CIRCUIT, false, false, later);
+        assert_eq!(CircuitState::HalfOpen, breaker.state(CIRCUIT));
+        assert_eq!(None, breaker.acquire(CIRCUIT, later));
+        breaker.record(CIRCUIT, true, false, later);
+        assert_eq!(CircuitState::HalfOpen, breaker.state(CIRCUIT));
+
+        breaker.record(CIRCUIT, false, true, later);
+        assert_eq!(CircuitState::Closed, breaker.state(CIRCUIT));
+    }
+
+    #[test]
+    fn should_key_the_circuits_per_base_url_or_per_endpoint() {
+        let rest = Rest::<(), String, u64>::get("/api/users/{id}");
+
+        assert_eq!(CIRCUIT, CircuitBreaker::new().circuit(CIRCUIT, &rest));
+        assert_eq!(
+            "GET http://127.0.0.1:8080/api/users/{id}",
+            CircuitBreaker::new().per_endpoint().circuit(CIRCUIT, &rest)
+        );
+    }
+
+    #[test]
+    fn should_fail_fast_while_the_circuit_is_open() {
+        let breaker = CircuitBreaker::new().with_failure_threshold(1);
+        let observer = breaker.clone();
+
+        let first: Result<(), Error> = ready_now(breaker.run(CIRCUIT, async { Err(status(503)) }));
+        let second: Result<(), Error> =
+            ready_now(breaker.run(CIRCUIT, async { panic!("The request should not be sent") }));
+
+        assert_eq!(Some(503), first.unwrap_err().status().map(u16::from));
+        match second {
+            Err(AjarsError::CircuitOpen { circuit }) => assert_eq!(CIRCUIT, circuit),
+            other => panic!("The circuit should be open, got {:?}", other),
+        }
+        assert_eq!(vec![(CIRCUIT.to_owned(), CircuitState::Open)], observer.states());
+    }
+
+    #[test]
+    fn should_let_another_probe_through_when_a_probe_is_dropped() {
+        let breaker = CircuitBreaker::new().with_failure_threshold(1).with_open_duration(Duration::ZERO);
+        let now = Instant::now();
+
+        breaker.acquire(CIRCUIT, now);
+        breaker.record(CIRCUIT, true, false, now);
+        {
+            let probe = pin!(breaker.run::<(), NoError, String, _>(CIRCUIT, std::future::pending()));
+            assert!(probe.poll(&mut Context::from_waker(Waker::noop())).is_pending());
+            assert_eq!(None, breaker.acquire(CIRCUIT, Instant::now()));
+        }
+
+        assert_eq!(Some(true), breaker.acquire(CIRCUIT, Instant::now()));
+    }
+}
@@ -0,0 +1,79 @@
+use std::future::Future;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{AjarsError, Response, RestType};
+
+/// A client that sends the requests of the endpoints, whatever its HTTP backend.
+///
+/// Library code can take an `impl AjarsClient` to run unchanged on every backend, natively and in WASM.
+pub trait AjarsClient {
+    /// The error type of the client backend.
+    type Error;
+
+    /// The builder of the requests to an endpoint.
+    type RequestBuilder<'a, I, O, REST>: AjarsRequestBuilder<I, O, REST, Error = Self::Error>
+    where
+        Self: 'a,
+        I: Serialize + DeserializeOwned + 'a,
+        O: Serialize + DeserializeOwned + 'a,
+        REST: RestType<I, O> + 'a;
+
+    /// Prepares a request to an endpoint whose path template contains placeholders,
+    /// filling them with the given path parameters.
+    fn request_with_path<'a, I, O, REST>(
+        &'a self,
+        rest: &'a REST,
+        path_params: &REST::PathParams,
+    ) -> Self::RequestBuilder<'a, I, O, REST>
+    where
+        I: Serialize + DeserializeOwned + 'a,
+        O: Serialize + DeserializeOwned + 'a,
+        REST: RestType<I, O> + 'a;
+
+    /// Prepares a request to an endpoint without path parameters.
+    fn request<'a, I, O, REST>(&'a self, rest: &'a REST) -> Self::RequestBuilder<'a, I, O, REST>
+    where
+        I: Serialize + DeserializeOwned + 'a,
+        O: Serialize + DeserializeOwned + 'a,
+        REST: RestType<I, O, PathParams = ()> + 'a,
+    {
+        self.request_with_path(rest, &())
+    }
+}
+
+/// A request to an endpoint, prepared by an [`AjarsClient`].
+///
+/// The futures returned by `send` and `send_full` are not required to be `Send`, as the ones of the web client
+/// are not, so generic code cannot move them to another thread, e.g. with `tokio::spawn`.
+/// The futures of the reqwest and surf request builders are `Send`: native code that spawns the requests
+/// can take these clients instead of an `impl AjarsClient`.
+pub trait AjarsRequestBuilder<I, O, REST>: Sized
+where
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    REST: RestType<I, O>,
+{
+    /// The error type of the client backend.
+    type Error;
+
+    /// Adds a header to the request.
+    /// A header that is not valid makes the request fail when it is sent.
+    fn header(self, name: &str, value: &str) -> Self;
+
+    /// Enables HTTP basic authentication.
+    fn basic_auth(self, username: &str, password: Option<&str>) -> Self;
+
+    /// Enables HTTP bearer authentication.
+    fn bearer_auth(self, token: &str) -> Self {
+        self.header("Authorization", &format!("Bearer {}", token))
+    }
+
+    /// Sends the request with the given input and returns the output of the endpoint.
+    fn send(self, input: &I) -> impl Future<Output = Result<O, AjarsError<REST::Error, Self::Error>>>;
+
+    /// Sends the request with the given input and returns the output of the endpoint
+    /// along with the status and the headers of the response.
+    fn send_full(self, input: &I) -> impl Future<Output = Result<Response<O>, AjarsError<REST::Error, Self::Error>>>;
+}
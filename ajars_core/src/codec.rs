@@ -0,0 +1,229 @@
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// The wire format of the request and response bodies of an endpoint.
+/// Servers and clients pick the codec of the endpoint, so they always agree on the `Content-Type` and on the encoding.
+pub trait Codec: Send + Sync + 'static {
+    /// The `Content-Type` of the encoded bodies.
+    const CONTENT_TYPE: &'static str;
+
+    fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, CodecError>;
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CodecError>;
+
+    /// Returns whether the value of a `Content-Type` header denotes this codec. Media type parameters are ignored.
+    fn matches_content_type(content_type: &str) -> bool {
+        content_type
+            .split(';')
+            .next()
+            .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case(Self::CONTENT_TYPE))
+    }
+}
+
+/// The error of a body that cannot be encoded or decoded.
+/// The errors of the decoding carry the path of the value that could not be decoded, like `items[2].name`.
+#[derive(Debug)]
+pub struct CodecError {
+    error: Box<dyn std::error::Error + Send + Sync>,
+    path: Option<String>,
+}
+
+impl CodecError {
+    pub fn new<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> Self {
+        Self { error: error.into(), path: None }
+    }
+
+    /// Creates the error of the value found at `path` that could not be decoded.
+    pub fn at_path<E: Into<Box<dyn std::error::Error + Send + Sync>>, P: Into<String>>(error: E, path: P) -> Self {
+        Self { error: error.into(), path: Some(path.into()) }
+    }
+
+    /// Returns the path of the value that could not be decoded, if it is not the whole body.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+}
+
+impl Display for CodecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{}: {}", path, self.error),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for CodecError {
+    fn from(error: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        Self::new(error)
+    }
+}
+
+/// Keeps the path of the value that could not be decoded, unless it is the whole body or it is not known.
+pub(crate) fn tracked_error<E: Into<Box<dyn std::error::Error + Send + Sync>>>(
+    error: serde_path_to_error::Error<E>,
+) -> CodecError {
+    let path = error.path().to_string();
+    match path.as_str() {
+        "." | "?" => CodecError::new(error.into_inner()),
+        _ => CodecError::at_path(error.into_inner(), path),
+    }
+}
+
+/// The JSON codec. It is the codec of the endpoints that do not declare one.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json;
+
+impl Codec for Json {
+    const CONTENT_TYPE: &'static str = "application/json";
+
+    fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, CodecError> {
+        serde_json::to_vec(value).map_err(CodecError::new)
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CodecError> {
+        let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+        let value = serde_path_to_error::deserialize(&mut deserializer).map_err(tracked_error)?;
+        deserializer.end().map_err(CodecError::new)?;
+        Ok(value)
+    }
+}
+
+/// The CBOR codec (RFC 8949).
+#[cfg(feature = "cbor")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cbor;
+
+#[cfg(feature = "cbor")]
+impl Codec for Cbor {
+    const CONTENT_TYPE: &'static str = "application/cbor";
+
+    fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, CodecError> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(value, &mut bytes).map_err(CodecError::new)?;
+        Ok(bytes)
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CodecError> {
+        ciborium::from_reader::<Tracked<T>, _>(bytes).map_err(CodecError::new)?.0
+    }
+}
+
+/// A value decoded from a deserializer that is not public, like the one of CBOR,
+/// keeping the path of the value that could not be decoded.
+#[cfg(feature = "cbor")]
+struct Tracked<T>(Result<T, CodecError>);
+
+#[cfg(feature = "cbor")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Tracked<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self(serde_path_to_error::deserialize(deserializer).map_err(|error| {
+            tracked_error(serde_path_to_error::Error::new(error.path().clone(), error.inner().to_string()))
+        })))
+    }
+}
+
+/// The MessagePack codec. Structs are encoded as maps, so adding fields does not break older peers.
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MessagePack;
+
+#[cfg(feature = "msgpack")]
+impl Codec for MessagePack {
+    const CONTENT_TYPE: &'static str = "application/msgpack";
+
+    fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, CodecError> {
+        rmp_serde::to_vec_named(value).map_err(CodecError::new)
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CodecError> {
+        serde_path_to_error::deserialize(&mut rmp_serde::Deserializer::from_read_ref(bytes)).map_err(tracked_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Message {
+        id: u64,
+        text: String,
+        tags: Vec<String>,
+    }
+
+    fn message() -> Message {
+        Message { id: 12, text: "hello".to_owned(), tags: vec!["a".to_owned(), "b".to_owned()] }
+    }
+
+    fn roundtrip<C: Codec>() {
+        let bytes = C::encode(&message()).unwrap();
+        assert_eq!(message(), C::decode::<Message>(&bytes).unwrap());
+    }
+
+    #[test]
+    fn should_encode_and_decode_json() {
+        roundtrip::<Json>();
+        assert_eq!(br#"{"id":12,"text":"hello","tags":["a","b"]}"#.to_vec(), Json::encode(&message()).unwrap());
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn should_encode_and_decode_cbor() {
+        roundtrip::<Cbor>();
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn should_encode_and_decode_msgpack() {
+        roundtrip::<MessagePack>();
+    }
+
+    #[test]
+    fn should_fail_to_decode_invalid_bytes() {
+        assert!(Json::decode::<Message>(b"{not json").is_err());
+    }
+
+    #[test]
+    fn should_report_the_path_of_the_value_that_fails_to_decode() {
+        fn assert_path<C: Codec>(bytes: &[u8]) {
+            let error = C::decode::<Message>(bytes).unwrap_err();
+            assert_eq!(Some("tags[1]"), error.path());
+            assert!(error.to_string().starts_with("tags[1]: "), "{}", error);
+        }
+
+        #[derive(Serialize)]
+        struct Wrong {
+            id: u64,
+            text: String,
+            tags: (String, u64),
+        }
+        let wrong = Wrong { id: 12, text: "hello".to_owned(), tags: ("a".to_owned(), 3) };
+
+        assert_path::<Json>(&Json::encode(&wrong).unwrap());
+        #[cfg(feature = "cbor")]
+        assert_path::<Cbor>(&Cbor::encode(&wrong).unwrap());
+        #[cfg(feature = "msgpack")]
+        assert_path::<MessagePack>(&MessagePack::encode(&wrong).unwrap());
+        assert_eq!(None, Json::decode::<Message>(b"{not json").unwrap_err().path());
+    }
+
+    #[test]
+    fn should_match_the_content_type() {
+        assert!(Json::matches_content_type("application/json"));
+        assert!(Json::matches_content_type("Application/JSON; charset=utf-8"));
+        assert!(!Json::matches_content_type("application/cbor"));
+        assert!(!Json::matches_content_type("text/plain"));
+    }
+}
@@ -0,0 +1,373 @@
+use std::fmt::{self, Display};
+use std::io;
+use std::ops::{Bound, RangeBounds};
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use futures_core::Stream;
+
+use crate::{HttpMethod, InputLocation, NoError, Rest};
+
+/// The body of a download produced on the fly.
+pub type DownloadStream = Pin<Box<dyn Stream<Item = io::Result<Vec<u8>>> + Send>>;
+
+/// An endpoint whose output is the raw content of a resource, selected by the input of type `I`,
+/// sent with its own `Content-Type` and an optional `Content-Disposition`.
+///
+/// The handler of the endpoint returns a `Result<Download, E>`. The downloads of bytes and files
+/// answer the `Range` requests with '206 Partial Content', so that the clients can resume them.
+pub struct RestDownload<I, P = (), E = NoError> {
+    rest: Rest<I, (), P, E>,
+}
+
+impl<I, P, E> Clone for RestDownload<I, P, E> {
+    fn clone(&self) -> Self {
+        Self { rest: self.rest.clone() }
+    }
+}
+
+impl<I, P, E> RestDownload<I, P, E> {
+    /// # Panics
+    ///
+    /// Panics if the path is not valid according to [`check_path`](crate::check_path);
+    /// for a `const` endpoint it fails the build.
+    pub const fn new(method: HttpMethod, path: &'static str) -> Self {
+        Self { rest: Rest::new(method, path) }
+    }
+
+    /// Sets the HTTP status code of the responses that carry the typed error of the endpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the status is not a client or server error status (400-599).
+    pub const fn with_error_status(self, status: u16) -> Self {
+        Self { rest: self.rest.with_error_status(status) }
+    }
+
+    /// Sets where the input of the endpoint is sent, overriding the default location of the method.
+    pub const fn with_input_location(self, location: InputLocation) -> Self {
+        Self { rest: self.rest.with_input_location(location) }
+    }
+
+    pub const fn get(path: &'static str) -> Self {
+        Self::new(HttpMethod::GET, path)
+    }
+
+    pub const fn post(path: &'static str) -> Self {
+        Self::new(HttpMethod::POST, path)
+    }
+
+    /// The endpoint that sends the request; it has no typed output, as the body of its responses is raw.
+    pub fn rest(&self) -> &Rest<I, (), P, E> {
+        &self.rest
+    }
+}
+
+/// The content returned by the handler of a download endpoint.
+pub struct Download {
+    content_type: String,
+    content_disposition: Option<String>,
+    body: DownloadBody,
+}
+
+/// Where the content of a download comes from.
+pub enum DownloadBody {
+    /// Content already in memory.
+    Bytes(Vec<u8>),
+    /// A file, streamed from the disk.
+    File(PathBuf),
+    /// Content produced on the fly, whose size is not known in advance: the `Range` requests are ignored.
+    Stream(DownloadStream),
+}
+
+impl Download {
+    pub fn bytes<C: Into<String>, B: Into<Vec<u8>>>(content_type: C, bytes: B) -> Self {
+        Self::new(content_type, DownloadBody::Bytes(bytes.into()))
+    }
+
+    pub fn file<C: Into<String>, F: Into<PathBuf>>(content_type: C, path: F) -> Self {
+        Self::new(content_type, DownloadBody::File(path.into()))
+    }
+
+    pub fn stream<C: Into<String>, S: Stream<Item = io::Result<Vec<u8>>> + Send + 'static>(
+        content_type: C,
+        stream: S,
+    ) -> Self {
+        Self::new(content_type, DownloadBody::Stream(Box::pin(stream)))
+    }
+
+    fn new<C: Into<String>>(content_type: C, body: DownloadBody) -> Self {
+        Self { content_type: content_type.into(), content_disposition: None, body }
+    }
+
+    /// Asks the clients to save the content as a file with the given name.
+    pub fn attachment<S: AsRef<str>>(mut self, file_name: S) -> Self {
+        self.content_disposition = Some(content_disposition("attachment", file_name.as_ref()));
+        self
+    }
+
+    /// Asks the clients to display the content, suggesting the given name if it is saved.
+    pub fn inline<S: AsRef<str>>(mut self, file_name: S) -> Self {
+        self.content_disposition = Some(content_disposition("inline", file_name.as_ref()));
+        self
+    }
+
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    /// The value of the `Content-Disposition` header, if any.
+    pub fn content_disposition(&self) -> Option<&str> {
+        self.content_disposition.as_deref()
+    }
+
+    pub fn into_body(self) -> DownloadBody {
+        self.body
+    }
+}
+
+/// Builds a `Content-Disposition` header value, with an ASCII fallback of the file name
+/// and its UTF-8 encoding as defined by RFC 6266.
+fn content_disposition(kind: &str, file_name: &str) -> String {
+    let fallback = file_name
+        .chars()
+        .map(|char| if char.is_ascii_graphic() && char != '"' && char != '\\' || char == ' ' { char } else { '_' })
+        .collect::<String>();
+    let mut encoded = String::new();
+    for byte in file_name.bytes() {
+        if byte.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    format!("{kind}; filename=\"{fallback}\"; filename*=UTF-8''{encoded}")
+}
+
+/// Returns the file name of a `Content-Disposition` header value,
+/// preferring its UTF-8 encoding to its ASCII fallback.
+pub fn content_disposition_file_name(value: &str) -> Option<String> {
+    let mut fallback = None;
+    for parameter in value.split(';').skip(1) {
+        let Some((name, parameter_value)) = parameter.split_once('=') else {
+            continue;
+        };
+        let parameter_value = parameter_value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "filename*" => {
+                if let Some((_, encoded)) = parameter_value.split_once("''")
+                    && let Some(decoded) = percent_decode(encoded)
+                {
+                    return Some(decoded);
+                }
+            }
+            "filename" => fallback = Some(parameter_value.trim_matches('"').to_owned()),
+            _ => {}
+        }
+    }
+    fallback
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut chars = value.bytes();
+    while let Some(byte) = chars.next() {
+        if byte == b'%' {
+            let hex = [chars.next()?, chars.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// The bytes of a resource sent by a '206 Partial Content' response, as described by its `Content-Range` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentRange {
+    /// The first byte sent.
+    pub start: u64,
+    /// The last byte sent, included.
+    pub end: u64,
+    /// The size of the whole resource, if known.
+    pub size: Option<u64>,
+}
+
+impl ContentRange {
+    /// The number of bytes sent.
+    pub fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// A range is never empty, as its last byte is included.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Parses a `Content-Range` header value like `bytes 0-99/1000`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (range, size) = value.trim().strip_prefix("bytes ")?.split_once('/')?;
+        let (start, end) = range.split_once('-')?;
+        let (start, end) = (start.trim().parse().ok()?, end.trim().parse().ok()?);
+        let size = match size.trim() {
+            "*" => None,
+            size => Some(size.parse().ok()?),
+        };
+        (start <= end).then_some(Self { start, end, size })
+    }
+}
+
+impl Display for ContentRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.size {
+            Some(size) => write!(f, "bytes {}-{}/{}", self.start, self.end, size),
+            None => write!(f, "bytes {}-{}/*", self.start, self.end),
+        }
+    }
+}
+
+/// The `Range` of a request that cannot be served, as it starts after the end of the resource.
+/// It is answered with '416 Range Not Satisfiable' and the `Content-Range` header returned by [`Display`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeNotSatisfiable {
+    pub size: u64,
+}
+
+impl Display for RangeNotSatisfiable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bytes */{}", self.size)
+    }
+}
+
+/// Resolves the `Range` header of a request for a resource of the given size.
+///
+/// Returns `None` if the whole resource must be sent: when there is no header, or when it is malformed
+/// or asks for several ranges, which are not supported.
+pub fn resolve_range(header: Option<&str>, size: u64) -> Result<Option<ContentRange>, RangeNotSatisfiable> {
+    let Some(range) = header.and_then(|header| header.trim().strip_prefix("bytes=")) else {
+        return Ok(None);
+    };
+    if range.contains(',') {
+        return Ok(None);
+    }
+    let Some((start, end)) = range.split_once('-') else {
+        return Ok(None);
+    };
+    let (start, end) = (start.trim(), end.trim());
+
+    let (start, end) = if start.is_empty() {
+        // A suffix range asks for the last bytes of the resource
+        let Ok(suffix) = end.parse::<u64>() else {
+            return Ok(None);
+        };
+        if suffix == 0 || size == 0 {
+            return Err(RangeNotSatisfiable { size });
+        }
+        (size.saturating_sub(suffix), size - 1)
+    } else {
+        let Ok(start) = start.parse::<u64>() else {
+            return Ok(None);
+        };
+        let end = match end {
+            "" => u64::MAX,
+            end => match end.parse::<u64>() {
+                Ok(end) if end >= start => end,
+                _ => return Ok(None),
+            },
+        };
+        if start >= size {
+            return Err(RangeNotSatisfiable { size });
+        }
+        (start, end.min(size - 1))
+    };
+    Ok(Some(ContentRange { start, end, size: Some(size) }))
+}
+
+/// Builds the `Range` header value that asks for the given bytes, e.g. `100..` to resume a download
+/// after the first 100 bytes.
+pub fn range_header<R: RangeBounds<u64>>(range: R) -> String {
+    let start = match range.start_bound() {
+        Bound::Included(start) => *start,
+        Bound::Excluded(start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    match range.end_bound() {
+        Bound::Included(end) => format!("bytes={}-{}", start, end),
+        Bound::Excluded(end) => format!("bytes={}-{}", start, end.saturating_sub(1)),
+        Bound::Unbounded => format!("bytes={}-", start),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::RestType;
+
+    #[test]
+    fn should_describe_the_endpoint_of_the_download() {
+        const REPORT: RestDownload<(), u64, String> = RestDownload::get("/reports/{id}").with_error_status(404);
+
+        let rest = REPORT.clone();
+        assert_eq!(&HttpMethod::GET, rest.rest().method());
+        assert_eq!("/reports/7", rest.rest().path_with(&7));
+        assert_eq!(404, rest.rest().error_status());
+    }
+
+    #[test]
+    fn should_resolve_the_range_of_a_request() {
+        assert_eq!(Ok(None), resolve_range(None, 10));
+        assert_eq!(Ok(Some(ContentRange { start: 2, end: 5, size: Some(10) })), resolve_range(Some("bytes=2-5"), 10));
+        assert_eq!(Ok(Some(ContentRange { start: 2, end: 9, size: Some(10) })), resolve_range(Some("bytes=2-"), 10));
+        assert_eq!(Ok(Some(ContentRange { start: 2, end: 9, size: Some(10) })), resolve_range(Some("bytes=2-99"), 10));
+        assert_eq!(Ok(Some(ContentRange { start: 7, end: 9, size: Some(10) })), resolve_range(Some("bytes=-3"), 10));
+        assert_eq!(Ok(Some(ContentRange { start: 0, end: 9, size: Some(10) })), resolve_range(Some("bytes=-30"), 10));
+    }
+
+    #[test]
+    fn should_ignore_the_ranges_that_are_not_supported() {
+        assert_eq!(Ok(None), resolve_range(Some("bytes=0-1,4-5"), 10));
+        assert_eq!(Ok(None), resolve_range(Some("bytes=5-2"), 10));
+        assert_eq!(Ok(None), resolve_range(Some("lines=1-2"), 10));
+        assert_eq!(Ok(None), resolve_range(Some("bytes=a-"), 10));
+    }
+
+    #[test]
+    fn should_not_satisfy_a_range_after_the_end() {
+        assert_eq!(Err(RangeNotSatisfiable { size: 10 }), resolve_range(Some("bytes=10-"), 10));
+        assert_eq!(Err(RangeNotSatisfiable { size: 0 }), resolve_range(Some("bytes=-1"), 0));
+        assert_eq!("bytes */10", RangeNotSatisfiable { size: 10 }.to_string());
+    }
+
+    #[test]
+    fn should_format_and_parse_a_content_range() {
+        let range = ContentRange { start: 2, end: 5, size: Some(10) };
+
+        assert_eq!("bytes 2-5/10", range.to_string());
+        assert_eq!(Some(range), ContentRange::parse("bytes 2-5/10"));
+        assert_eq!(Some(ContentRange { start: 2, end: 5, size: None }), ContentRange::parse("bytes 2-5/*"));
+        assert_eq!(None, ContentRange::parse("bytes */10"));
+        assert_eq!(4, range.len());
+    }
+
+    #[test]
+    fn should_build_the_range_header() {
+        assert_eq!("bytes=100-", range_header(100..));
+        assert_eq!("bytes=0-99", range_header(..100));
+        assert_eq!("bytes=10-19", range_header(10..=19));
+    }
+
+    #[test]
+    fn should_encode_and_decode_the_file_name_of_a_download() {
+        let download = Download::bytes("text/csv", "a,b").attachment("report \"2024\" è.csv");
+        let disposition = download.content_disposition().unwrap();
+
+        assert_eq!(
+            "attachment; filename=\"report _2024_ _.csv\"; filename*=UTF-8''report%20%222024%22%20%C3%A8.csv",
+            disposition
+        );
+        assert_eq!(Some("report \"2024\" è.csv".to_owned()), content_disposition_file_name(disposition));
+        assert_eq!(Some("a.txt".to_owned()), content_disposition_file_name("inline; filename=\"a.txt\""));
+        assert_eq!(None, content_disposition_file_name("inline"));
+    }
+}
@@ -0,0 +1,197 @@
+use std::fmt::Display;
+
+use serde::de::value::UnitDeserializer;
+use serde::de::{DeserializeOwned, IntoDeserializer};
+use serde::ser::{Impossible, Serialize, Serializer};
+
+use crate::CodecError;
+
+/// Builds the output of a response that carries no body (e.g. the response to a `HEAD` request).
+/// Returns `None` if `O` cannot be represented without a body; only types like `()` or `Option<T>` can.
+pub fn empty_body_output<O: DeserializeOwned>() -> Option<O> {
+    let deserializer: UnitDeserializer<serde::de::value::Error> = ().into_deserializer();
+    O::deserialize(deserializer).ok()
+}
+
+/// Builds the output of the response to a `HEAD` request, that never has a body.
+/// Fails if `O` cannot be represented without a body, as `HEAD` endpoints have no output.
+pub fn head_output<O: DeserializeOwned>() -> Result<O, CodecError> {
+    empty_body_output().ok_or_else(|| {
+        CodecError::new("The response to a HEAD request has no body, so the output of the endpoint must be `()`")
+    })
+}
+
+/// Returns whether a value is sent without a body and without a query string: it is the case of `()` and `None`.
+/// Such values are read back by [`empty_body_output`].
+pub fn is_empty_body<T: Serialize + ?Sized>(value: &T) -> bool {
+    value.serialize(EmptyDetector).unwrap_or(false)
+}
+
+/// A serializer that only tells whether the value is `()` or `None`, without serializing anything else.
+struct EmptyDetector;
+
+#[derive(Debug)]
+struct NotEmpty;
+
+impl Display for NotEmpty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the value is not empty")
+    }
+}
+
+impl std::error::Error for NotEmpty {}
+
+impl serde::ser::Error for NotEmpty {
+    fn custom<M: Display>(_msg: M) -> Self {
+        NotEmpty
+    }
+}
+
+macro_rules! not_empty {
+    ($($method:ident($($arg:ty),*)),* $(,)?) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<bool, NotEmpty> {
+                Ok(false)
+            }
+        )*
+    };
+}
+
+impl Serializer for EmptyDetector {
+    type Ok = bool;
+    type Error = NotEmpty;
+    type SerializeSeq = Impossible<bool, NotEmpty>;
+    type SerializeTuple = Impossible<bool, NotEmpty>;
+    type SerializeTupleStruct = Impossible<bool, NotEmpty>;
+    type SerializeTupleVariant = Impossible<bool, NotEmpty>;
+    type SerializeMap = Impossible<bool, NotEmpty>;
+    type SerializeStruct = Impossible<bool, NotEmpty>;
+    type SerializeStructVariant = Impossible<bool, NotEmpty>;
+
+    fn serialize_unit(self) -> Result<bool, NotEmpty> {
+        Ok(true)
+    }
+
+    fn serialize_none(self) -> Result<bool, NotEmpty> {
+        Ok(true)
+    }
+
+    not_empty! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_unit_struct(&'static str),
+        serialize_unit_variant(&'static str, u32, &'static str),
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<bool, NotEmpty> {
+        Ok(false)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _value: &T,
+    ) -> Result<bool, NotEmpty> {
+        Ok(false)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<bool, NotEmpty> {
+        Ok(false)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, NotEmpty> {
+        Err(NotEmpty)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, NotEmpty> {
+        Err(NotEmpty)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, NotEmpty> {
+        Err(NotEmpty)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, NotEmpty> {
+        Err(NotEmpty)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, NotEmpty> {
+        Err(NotEmpty)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, NotEmpty> {
+        Err(NotEmpty)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, NotEmpty> {
+        Err(NotEmpty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Empty {}
+
+    #[test]
+    fn should_detect_empty_values() {
+        assert!(is_empty_body(&()));
+        assert!(is_empty_body(&Option::<u64>::None));
+        assert!(!is_empty_body(&Some(())));
+        assert!(!is_empty_body(&Empty {}));
+        assert!(!is_empty_body(&0));
+        assert!(!is_empty_body(""));
+        assert!(!is_empty_body(&Vec::<u64>::new()));
+    }
+
+    #[test]
+    fn should_build_the_output_of_an_empty_body() {
+        assert_eq!(Some(()), empty_body_output::<()>());
+        assert_eq!(Some(None), empty_body_output::<Option<u64>>());
+        assert_eq!(None, empty_body_output::<Empty>());
+        assert_eq!(None, empty_body_output::<u64>());
+    }
+
+    #[test]
+    fn should_build_the_output_of_a_head_request() {
+        assert_eq!((), head_output::<()>().unwrap());
+        assert!(head_output::<Empty>().unwrap_err().to_string().contains("HEAD request has no body"));
+    }
+}
@@ -0,0 +1,180 @@
+use std::fmt::{Display, Formatter};
+
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error as ThisError;
+
+use crate::{Codec, CodecError};
+
+/// The error type of the endpoints that do not declare one.
+/// It has no values, so these endpoints never send or receive a typed error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoError {}
+
+impl Serialize for NoError {
+    fn serialize<S: Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {}
+    }
+}
+
+impl<'de> Deserialize<'de> for NoError {
+    fn deserialize<D: Deserializer<'de>>(_deserializer: D) -> Result<Self, D::Error> {
+        Err(D::Error::custom("the endpoint does not declare an error type"))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for NoError {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "NoError".into()
+    }
+
+    /// No value is valid, as `NoError` has none.
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        false.into()
+    }
+}
+
+/// An HTTP status code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HttpStatus(u16);
+
+impl Display for HttpStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u16> for HttpStatus {
+    fn from(status: u16) -> Self {
+        Self(status)
+    }
+}
+
+impl From<HttpStatus> for u16 {
+    fn from(status: HttpStatus) -> Self {
+        status.0
+    }
+}
+
+impl PartialEq<u16> for HttpStatus {
+    fn eq(&self, other: &u16) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<HttpStatus> for u16 {
+    fn eq(&self, other: &HttpStatus) -> bool {
+        *self == other.0
+    }
+}
+
+impl HttpStatus {
+    #[inline]
+    pub fn status(&self) -> u16 {
+        self.0
+    }
+
+    #[inline]
+    pub fn is_informational(&self) -> bool {
+        200 > self.0 && self.0 >= 100
+    }
+
+    /// Check if status is within 200-299.
+    #[inline]
+    pub fn is_success(&self) -> bool {
+        300 > self.0 && self.0 >= 200
+    }
+
+    /// Check if status is within 300-399.
+    #[inline]
+    pub fn is_redirection(&self) -> bool {
+        400 > self.0 && self.0 >= 300
+    }
+
+    /// Check if status is within 400-499.
+    #[inline]
+    pub fn is_client_error(&self) -> bool {
+        500 > self.0 && self.0 >= 400
+    }
+
+    /// Check if status is within 500-599.
+    #[inline]
+    pub fn is_server_error(&self) -> bool {
+        600 > self.0 && self.0 >= 500
+    }
+}
+
+/// The error returned by the clients, the same whatever the client backend.
+/// `E` is the error type declared by the endpoint and `C` is the error type of the client backend,
+/// only used for the failures of the transport.
+#[derive(Debug, ThisError)]
+pub enum AjarsError<E, C> {
+    /// The server answered with the typed error of the endpoint
+    #[error("Endpoint error. HTTP status: {status}. Error: {error:?}")]
+    Endpoint { status: HttpStatus, error: E },
+    /// The server answered with a successful status that is not the success status of the endpoint
+    #[error("Unexpected HTTP status: {status}. Expected: {expected}")]
+    UnexpectedStatus { status: HttpStatus, expected: HttpStatus },
+    /// The server answered with an error status and a body that is not the typed error of the endpoint,
+    /// like the HTML page of a proxy. The headers and the raw body are kept to tell what went wrong.
+    #[error("HTTP status error. HTTP status: {status}. Body: {}", String::from_utf8_lossy(body))]
+    Status { status: HttpStatus, headers: Vec<(String, String)>, body: Vec<u8> },
+    /// The input could not be encoded with the codec of the endpoint, so the request was not sent
+    #[error("Encode error: {0}")]
+    Encode(CodecError),
+    /// The response body could not be decoded with the codec of the endpoint.
+    /// The error carries the path of the value that could not be decoded
+    #[error("Decode error: {0}")]
+    Decode(CodecError),
+    /// A call of a batch failed with a status that carries neither its output nor its typed error,
+    /// like the call to an endpoint without a batch handler
+    #[error("Batch call error. HTTP status: {status}. Error: {message}")]
+    BatchCall { status: HttpStatus, message: String },
+    /// The request could not be sent, or the response could not be received, by the client backend
+    #[error("Transport error: {0}")]
+    Transport(C),
+    /// The request was not sent because the circuit breaker of the client is open for its circuit,
+    /// after too many failures of the server; the circuit is named by its key
+    #[error("Circuit open: {circuit}")]
+    CircuitOpen { circuit: String },
+    /// The input failed the validation of the endpoint, so the request was not sent
+    #[cfg(feature = "validator")]
+    #[error("Validation error: {0}")]
+    Validation(crate::ValidationErrors),
+}
+
+impl<E: DeserializeOwned, C> AjarsError<E, C> {
+    /// Builds the error of a response with an error status: the typed error of the endpoint when the response
+    /// has the error status of the endpoint and a body that decodes to it, [`AjarsError::Status`] otherwise.
+    pub fn from_error_response<CODEC: Codec>(
+        status: u16,
+        error_status: u16,
+        headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    ) -> Self {
+        if status == error_status
+            && let Ok(error) = CODEC::decode(&body)
+        {
+            return AjarsError::Endpoint { status: status.into(), error };
+        }
+        AjarsError::Status { status: status.into(), headers, body }
+    }
+}
+
+impl<E, C> AjarsError<E, C> {
+    /// Returns the HTTP status of the response, if the server answered.
+    pub fn status(&self) -> Option<HttpStatus> {
+        match self {
+            AjarsError::Endpoint { status, .. }
+            | AjarsError::UnexpectedStatus { status, .. }
+            | AjarsError::Status { status, .. }
+            | AjarsError::BatchCall { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
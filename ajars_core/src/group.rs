@@ -0,0 +1,448 @@
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use thiserror::Error as ThisError;
+
+use crate::path::path_shape;
+use crate::{Codec, HttpMethod, InputLocation, PathError, RestType, check_path};
+
+/// The untyped description of an endpoint: everything but its input, output, path parameters and error types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointDescriptor {
+    method: HttpMethod,
+    path: String,
+    input_location: InputLocation,
+    error_status: u16,
+    success_status: u16,
+    content_type: &'static str,
+}
+
+impl EndpointDescriptor {
+    pub fn of<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+        rest: &REST,
+    ) -> Self {
+        Self {
+            method: rest.method().clone(),
+            path: rest.path().to_owned(),
+            input_location: rest.input_location(),
+            error_status: rest.error_status(),
+            success_status: rest.success_status(),
+            content_type: REST::Codec::CONTENT_TYPE,
+        }
+    }
+
+    pub fn method(&self) -> &HttpMethod {
+        &self.method
+    }
+
+    /// The stable id of the endpoint, see [`RestType::id`].
+    pub fn id(&self) -> String {
+        self.to_string()
+    }
+
+    /// The path template of the endpoint.
+    /// It includes the prefixes of the groups when the descriptor is returned by [`ApiGroup::endpoints`].
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn input_location(&self) -> InputLocation {
+        self.input_location
+    }
+
+    pub fn error_status(&self) -> u16 {
+        self.error_status
+    }
+
+    pub fn success_status(&self) -> u16 {
+        self.success_status
+    }
+
+    /// The `Content-Type` of the codec of the endpoint.
+    pub fn content_type(&self) -> &'static str {
+        self.content_type
+    }
+
+    fn matches<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+        &self,
+        rest: &REST,
+    ) -> bool {
+        &self.method == rest.method() && self.path == rest.path()
+    }
+}
+
+impl Display for EndpointDescriptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} {}", self.method, self.path)
+    }
+}
+
+/// A conflict between the routes of a set of endpoints.
+#[derive(Debug, Clone, PartialEq, Eq, ThisError)]
+pub enum RouteError {
+    #[error("The path of the endpoint [{endpoint}] is not valid: {error}")]
+    InvalidPath { endpoint: String, error: PathError },
+    #[error("The endpoints [{first}] and [{second}] have the same method and path")]
+    Duplicate { first: String, second: String },
+    #[error("The endpoints [{first}] and [{second}] have path templates that differ only in the placeholder names")]
+    Ambiguous { first: String, second: String },
+}
+
+/// Checks that the endpoints can be served together: every path is valid, no two endpoints share method and path,
+/// and no two path templates differ only in the names of their placeholders, as routers cannot tell them apart.
+pub fn validate_endpoints<'a, E: IntoIterator<Item = &'a EndpointDescriptor>>(endpoints: E) -> Result<(), RouteError> {
+    let mut checked: Vec<(&EndpointDescriptor, String)> = Vec::new();
+    for endpoint in endpoints {
+        check_path(endpoint.path())
+            .map_err(|error| RouteError::InvalidPath { endpoint: endpoint.to_string(), error })?;
+
+        let shape = path_shape(endpoint.path());
+        for (other, other_shape) in &checked {
+            if other.path() == endpoint.path() && other.method() == endpoint.method() {
+                return Err(RouteError::Duplicate { first: other.to_string(), second: endpoint.to_string() });
+            }
+            if other.path() != endpoint.path() && *other_shape == shape {
+                return Err(RouteError::Ambiguous { first: other.to_string(), second: endpoint.to_string() });
+            }
+        }
+        checked.push((endpoint, shape));
+    }
+    Ok(())
+}
+
+/// A set of endpoints served under a shared path prefix, such as `/api/v1`.
+///
+/// The endpoints keep their own paths, relative to the group; the group resolves their full paths.
+/// Groups can be nested: the prefix of a sub-group is appended to the prefix of its parent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiGroup {
+    prefix: String,
+    endpoints: Vec<EndpointDescriptor>,
+    groups: Vec<ApiGroup>,
+}
+
+impl ApiGroup {
+    pub fn new<S: Into<String>>(prefix: S) -> Self {
+        Self { prefix: normalize_prefix(&prefix.into()), endpoints: vec![], groups: vec![] }
+    }
+
+    /// Adds an endpoint to the group.
+    pub fn endpoint<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+        mut self,
+        rest: &REST,
+    ) -> Self {
+        self.endpoints.push(EndpointDescriptor::of(rest));
+        self
+    }
+
+    /// Nests a sub-group. Its prefix, and the prefixes of its own sub-groups, become relative to this group.
+    pub fn group(mut self, mut group: ApiGroup) -> Self {
+        group.prepend_prefix(&self.prefix);
+        self.groups.push(group);
+        self
+    }
+
+    /// The full prefix of the group, including the prefixes of the parent groups it was nested in.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn groups(&self) -> &[ApiGroup] {
+        &self.groups
+    }
+
+    /// Returns the endpoints of the group and of all its sub-groups, with their full paths.
+    pub fn endpoints(&self) -> Vec<EndpointDescriptor> {
+        let mut endpoints = Vec::new();
+        self.collect_endpoints(&mut endpoints);
+        endpoints
+    }
+
+    /// Checks the full paths of all the endpoints of the group and of its sub-groups with [`validate_endpoints`].
+    pub fn validate(&self) -> Result<(), RouteError> {
+        validate_endpoints(&self.endpoints())
+    }
+
+    /// Returns the endpoint with its full path, as registered in this group or in one of its sub-groups.
+    /// The returned endpoint can be used by the clients and the servers in place of the original one.
+    ///
+    /// When the endpoint was added to several groups, the path of the first one is returned;
+    /// the other paths are returned by [`ApiGroup::scopes`], or by scoping it through its group with
+    /// [`ApiGroup::subgroup`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the endpoint was not added to the group or to any of its sub-groups.
+    pub fn scope<'a, I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+        &self,
+        rest: &'a REST,
+    ) -> ScopedRest<'a, REST> {
+        self.scopes(rest).into_iter().next().unwrap_or_else(|| {
+            panic!("The endpoint [{:?} {}] is not part of the group [{}]", rest.method(), rest.path(), self.prefix)
+        })
+    }
+
+    /// Returns the endpoint with each of its full paths, one for every group, among this group and its sub-groups,
+    /// it was added to.
+    pub fn scopes<'a, I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+        &self,
+        rest: &'a REST,
+    ) -> Vec<ScopedRest<'a, REST>> {
+        let mut prefixes = Vec::new();
+        self.collect_prefixes(rest, &mut prefixes);
+        prefixes.into_iter().map(|prefix| ScopedRest { path: join_path(prefix, rest.path()), rest }).collect()
+    }
+
+    /// Returns whether the endpoint was added to this group or to one of its sub-groups.
+    pub fn contains<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+        &self,
+        rest: &REST,
+    ) -> bool {
+        !self.scopes(rest).is_empty()
+    }
+
+    /// Returns the sub-group, nested at any depth, with the given prefix relative to this group,
+    /// such as `/v2` or `/v2/shop`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group has no such sub-group.
+    pub fn subgroup(&self, prefix: &str) -> &ApiGroup {
+        let prefix = normalize_prefix(&format!("{}{}", self.prefix, normalize_prefix(prefix)));
+        self.find_group(&prefix).unwrap_or_else(|| panic!("The group [{}] has no sub-group [{}]", self.prefix, prefix))
+    }
+
+    fn find_group(&self, prefix: &str) -> Option<&ApiGroup> {
+        self.groups.iter().find_map(|group| if group.prefix == prefix { Some(group) } else { group.find_group(prefix) })
+    }
+
+    fn collect_prefixes<'s, I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>>(
+        &'s self,
+        rest: &REST,
+        prefixes: &mut Vec<&'s str>,
+    ) {
+        if self.endpoints.iter().any(|endpoint| endpoint.matches(rest)) {
+            prefixes.push(&self.prefix);
+        }
+        for group in &self.groups {
+            group.collect_prefixes(rest, prefixes);
+        }
+    }
+
+    fn collect_endpoints(&self, endpoints: &mut Vec<EndpointDescriptor>) {
+        endpoints.extend(
+            self.endpoints.iter().map(|endpoint| EndpointDescriptor {
+                path: join_path(&self.prefix, &endpoint.path),
+                ..endpoint.clone()
+            }),
+        );
+        for group in &self.groups {
+            group.collect_endpoints(endpoints);
+        }
+    }
+
+    fn prepend_prefix(&mut self, parent: &str) {
+        self.prefix = normalize_prefix(&format!("{}{}", parent, self.prefix));
+        for group in &mut self.groups {
+            group.prepend_prefix(parent);
+        }
+    }
+}
+
+/// An endpoint of an [`ApiGroup`], with the prefixes of the group prepended to its path.
+/// It is created by [`ApiGroup::scope`].
+pub struct ScopedRest<'a, REST> {
+    path: String,
+    rest: &'a REST,
+}
+
+impl<'a, REST> ScopedRest<'a, REST> {
+    /// The endpoint, with its path relative to the group.
+    pub fn inner(&self) -> &'a REST {
+        self.rest
+    }
+}
+
+impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, REST: RestType<I, O>> RestType<I, O>
+    for ScopedRest<'_, REST>
+{
+    type PathParams = REST::PathParams;
+    type Error = REST::Error;
+    type Codec = REST::Codec;
+
+    fn path(&self) -> &str {
+        &self.path
+    }
+
+    fn method(&self) -> &HttpMethod {
+        self.rest.method()
+    }
+
+    fn input_location(&self) -> InputLocation {
+        self.rest.input_location()
+    }
+
+    fn error_status(&self) -> u16 {
+        self.rest.error_status()
+    }
+
+    fn success_status(&self) -> u16 {
+        self.rest.success_status()
+    }
+
+    fn is_idempotent(&self) -> bool {
+        self.rest.is_idempotent()
+    }
+
+    #[cfg(feature = "validator")]
+    fn input_validator(&self) -> crate::InputValidator<I> {
+        self.rest.input_validator()
+    }
+}
+
+/// Removes the trailing slashes and makes sure a non empty prefix starts with a slash.
+fn normalize_prefix(prefix: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    if prefix.is_empty() || prefix.starts_with('/') { prefix.to_owned() } else { format!("/{}", prefix) }
+}
+
+fn join_path(prefix: &str, path: &str) -> String {
+    if path.starts_with('/') { format!("{}{}", prefix, path) } else { format!("{}/{}", prefix, path) }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Rest;
+
+    const LIST_USERS: Rest<(), Vec<String>> = Rest::get("/users");
+    const CREATE_USER: Rest<String, String> = Rest::post("/users");
+    const GET_ITEM: Rest<(), String, u64> = Rest::get("/items/{id}");
+    const HEALTH: Rest<(), ()> = Rest::get("/health");
+
+    fn api() -> ApiGroup {
+        ApiGroup::new("/api/")
+            .endpoint(&HEALTH)
+            .group(ApiGroup::new("v1").endpoint(&LIST_USERS).endpoint(&CREATE_USER))
+            .group(ApiGroup::new("/v2").group(ApiGroup::new("/shop").endpoint(&GET_ITEM)))
+    }
+
+    #[test]
+    fn should_join_the_prefixes_of_nested_groups() {
+        let api = api();
+
+        assert_eq!("/api", api.prefix());
+        assert_eq!("/api/v1", api.groups()[0].prefix());
+        assert_eq!("/api/v2", api.groups()[1].prefix());
+        assert_eq!("/api/v2/shop", api.groups()[1].groups()[0].prefix());
+    }
+
+    #[test]
+    fn should_list_all_endpoints_with_their_full_paths() {
+        let endpoints = api().endpoints();
+        let paths = endpoints.iter().map(|endpoint| (endpoint.method(), endpoint.path())).collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                (&HttpMethod::GET, "/api/health"),
+                (&HttpMethod::GET, "/api/v1/users"),
+                (&HttpMethod::POST, "/api/v1/users"),
+                (&HttpMethod::GET, "/api/v2/shop/items/{id}"),
+            ],
+            paths
+        );
+        assert_eq!(InputLocation::Body, endpoints[2].input_location());
+        assert_eq!("application/json", endpoints[2].content_type());
+    }
+
+    #[test]
+    fn should_resolve_the_full_path_of_an_endpoint() {
+        let api = api();
+
+        assert_eq!("/api/health", api.scope(&HEALTH).path());
+        assert_eq!("/api/v1/users", api.scope(&CREATE_USER).path());
+        assert_eq!("/api/v2/shop/items/7", api.scope(&GET_ITEM).path_with(&7));
+        assert_eq!("/api/v2/shop/items/{id}", api.groups()[1].scope(&GET_ITEM).path());
+        assert_eq!(&HttpMethod::POST, api.scope(&CREATE_USER).method());
+    }
+
+    #[test]
+    fn should_validate_the_endpoints_of_a_group() {
+        assert_eq!(Ok(()), api().validate());
+    }
+
+    #[test]
+    fn should_reject_duplicate_endpoints() {
+        const OTHER_USERS: Rest<String, ()> = Rest::post("/v1/users");
+
+        let api = api().endpoint(&OTHER_USERS);
+
+        assert_eq!(
+            Err(RouteError::Duplicate {
+                first: "POST /api/v1/users".to_owned(),
+                second: "POST /api/v1/users".to_owned()
+            }),
+            api.validate()
+        );
+    }
+
+    #[test]
+    fn should_reject_ambiguous_endpoints() {
+        const DELETE_ITEM: Rest<(), (), u64> = Rest::delete("/shop/items/{item_id}");
+
+        let api = api().group(ApiGroup::new("/v2").endpoint(&DELETE_ITEM));
+
+        let error = api.validate().unwrap_err();
+        assert_eq!(
+            RouteError::Ambiguous {
+                first: "GET /api/v2/shop/items/{id}".to_owned(),
+                second: "DELETE /api/v2/shop/items/{item_id}".to_owned()
+            },
+            error
+        );
+        assert!(error.to_string().contains("[GET /api/v2/shop/items/{id}] and [DELETE /api/v2/shop/items/{item_id}]"));
+    }
+
+    #[test]
+    fn should_reject_invalid_full_paths() {
+        let api = ApiGroup::new("/api?v=1").endpoint(&HEALTH);
+
+        assert_eq!(
+            Err(RouteError::InvalidPath {
+                endpoint: "GET /api?v=1/health".to_owned(),
+                error: PathError::InvalidCharacter
+            }),
+            api.validate()
+        );
+    }
+
+    #[test]
+    fn should_resolve_every_path_of_an_endpoint_added_to_several_groups() {
+        let api = ApiGroup::new("/api")
+            .group(ApiGroup::new("/v1").endpoint(&LIST_USERS))
+            .group(ApiGroup::new("/v2").endpoint(&LIST_USERS));
+
+        let paths = api.scopes(&LIST_USERS).iter().map(|scoped| scoped.path().to_owned()).collect::<Vec<_>>();
+        assert_eq!(vec!["/api/v1/users", "/api/v2/users"], paths);
+        assert_eq!("/api/v1/users", api.scope(&LIST_USERS).path());
+        assert_eq!("/api/v2/users", api.subgroup("/v2").scope(&LIST_USERS).path());
+        assert_eq!("/api/v2/shop/items/{id}", self::api().subgroup("v2/shop/").scope(&GET_ITEM).path());
+        assert_eq!(Ok(()), api.validate());
+    }
+
+    #[test]
+    #[should_panic(expected = "has no sub-group")]
+    fn should_panic_when_looking_up_an_unknown_subgroup() {
+        api().subgroup("/v3");
+    }
+
+    #[test]
+    #[should_panic(expected = "is not part of the group")]
+    fn should_panic_when_scoping_an_unknown_endpoint() {
+        let api = ApiGroup::new("/api").endpoint(&HEALTH);
+        assert!(!api.contains(&LIST_USERS));
+        api.scope(&LIST_USERS);
+    }
+}
@@ -3,37 +3,227 @@ use std::marker::PhantomData;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 
-#[derive(Clone)]
+mod batch;
+mod circuit;
+mod client;
+mod codec;
+mod download;
+mod empty;
+mod error;
+mod group;
+mod page;
+mod path;
+mod response;
+mod retry;
+mod sse;
+mod stream;
+mod upload;
+#[cfg(feature = "validator")]
+mod validation;
+mod websocket;
+
+pub use batch::{
+    BatchCall, BatchCalls, BatchContext, BatchHandlers, BatchRest, BatchResult, BatchResults, Batched,
+    DEFAULT_MAX_BATCH_CALLS, ENCODING_ERROR_STATUS, INVALID_CALL_STATUS, RestBatch, TOO_MANY_CALLS_STATUS,
+    TooManyBatchCalls, UNKNOWN_ENDPOINT_STATUS,
+};
+pub use circuit::{CircuitBreaker, CircuitState};
+pub use client::{AjarsClient, AjarsRequestBuilder};
+#[cfg(feature = "cbor")]
+pub use codec::Cbor;
+#[cfg(feature = "msgpack")]
+pub use codec::MessagePack;
+pub use codec::{Codec, CodecError, Json};
+pub use download::{
+    ContentRange, Download, DownloadBody, DownloadStream, RangeNotSatisfiable, RestDownload,
+    content_disposition_file_name, range_header, resolve_range,
+};
+pub use empty::{empty_body_output, head_output, is_empty_body};
+pub use error::{AjarsError, HttpStatus, NoError};
+pub use group::{ApiGroup, EndpointDescriptor, RouteError, ScopedRest, validate_endpoints};
+pub use page::{Page, PageRequest, Paginated};
+pub use path::{PathError, check_path, fill_path};
+pub use response::Response;
+pub use retry::{DEFAULT_RETRYABLE_STATUSES, RetryPolicy};
+pub use sse::{LAST_EVENT_ID_HEADER, RestSse, SSE_CONTENT_TYPE, SseDecoder, SseEvent, encode_sse_event};
+pub use stream::{NDJSON_CONTENT_TYPE, NdjsonDecoder, RestStream, encode_ndjson_item};
+pub use upload::{
+    DEFAULT_FILE_CONTENT_TYPE, FILE_PART, FilePart, METADATA_PART, PersistError, RestUpload, UploadedFile,
+    encode_multipart, multipart_boundary, multipart_content_type,
+};
+#[cfg(feature = "validator")]
+pub use validation::{InputValidator, VALIDATION_ERROR_STATUS, Validate, ValidationErrors};
+pub use websocket::{RestWebSocket, WebSocketError, decode_ws_message, encode_ws_message};
+
+/// The HTTP status code used by default to send the typed error of an endpoint.
+pub const DEFAULT_ERROR_STATUS: u16 = 400;
+
+/// The HTTP status code used by default to send the output of an endpoint.
+/// An empty output, like `()`, is sent as '204 No Content' instead.
+pub const DEFAULT_SUCCESS_STATUS: u16 = 200;
+
+/// The HTTP status code of the responses without body sent for the empty outputs, like `()`.
+pub const NO_CONTENT_STATUS: u16 = 204;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum HttpMethod {
     DELETE,
     GET,
+    HEAD,
+    OPTIONS,
+    PATCH,
     POST,
     PUT,
 }
 
+impl HttpMethod {
+    /// The location of the input of the endpoints that do not set one:
+    /// the query string for `DELETE`, `GET`, `HEAD` and `OPTIONS`, the body for `PATCH`, `POST` and `PUT`.
+    pub const fn default_input_location(&self) -> InputLocation {
+        match self {
+            HttpMethod::DELETE | HttpMethod::GET | HttpMethod::HEAD | HttpMethod::OPTIONS => InputLocation::Query,
+            HttpMethod::PATCH | HttpMethod::POST | HttpMethod::PUT => InputLocation::Body,
+        }
+    }
+
+    /// Whether the endpoints with this method are idempotent unless declared otherwise,
+    /// so that the clients can retry their requests: `true` for `GET`, `PUT` and `DELETE`.
+    pub const fn is_idempotent(&self) -> bool {
+        matches!(self, HttpMethod::DELETE | HttpMethod::GET | HttpMethod::PUT)
+    }
+}
+
+/// Where the input of an endpoint is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputLocation {
+    /// The URL query string
+    Query,
+    /// The request body, encoded with the codec of the endpoint
+    Body,
+    /// The request body, encoded as `application/x-www-form-urlencoded`
+    Form,
+}
+
 pub trait RestType<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned> {
-    fn build<P: Into<String>>(method: HttpMethod, path: P) -> RestFluent<I, O> {
+    /// The type that fills the `{name}` placeholders of the path template.
+    /// It is `()` for endpoints without path parameters.
+    type PathParams: Serialize + DeserializeOwned;
+
+    /// The error type that the server sends when the handler fails and that the clients decode.
+    /// It is [`NoError`] for endpoints without a typed error.
+    type Error: Serialize + DeserializeOwned;
+
+    /// The codec of the request and response bodies.
+    /// It is [`Json`] for endpoints that do not declare one.
+    type Codec: Codec;
+
+    fn build<S: Into<String>>(method: HttpMethod, path: S) -> RestFluent<I, O> {
         RestFluent::new(method, path)
     }
 
     fn path(&self) -> &str;
     fn method(&self) -> &HttpMethod;
+
+    /// The stable id of the endpoint: its method and its path template, like `GET /api/items/{id}`.
+    /// It identifies the endpoint in the calls of a [`RestBatch`].
+    fn id(&self) -> String {
+        format!("{:?} {}", self.method(), self.path())
+    }
+
+    /// Where the input of the endpoint is sent.
+    /// It defaults to [`HttpMethod::default_input_location`].
+    fn input_location(&self) -> InputLocation;
+
+    /// The HTTP status code of the responses that carry the typed error of the endpoint.
+    fn error_status(&self) -> u16;
+
+    /// The HTTP status code of the responses that carry the output of the endpoint.
+    fn success_status(&self) -> u16;
+
+    /// Whether the status of a successful response fulfills the contract of the endpoint:
+    /// it must be the success status of the endpoint, or '204 No Content' when the success status is the default one.
+    fn accepts_success_status(&self, status: u16) -> bool {
+        accepts_status(self.success_status(), status)
+    }
+
+    /// Whether sending a request to the endpoint more than once has the same effect as sending it once,
+    /// so that the clients can retry it according to their [`RetryPolicy`].
+    /// It defaults to [`HttpMethod::is_idempotent`].
+    fn is_idempotent(&self) -> bool {
+        self.method().is_idempotent()
+    }
+
+    /// The validator of the input of the endpoint.
+    /// It defaults to a validator that accepts every input.
+    #[cfg(feature = "validator")]
+    fn input_validator(&self) -> InputValidator<I> {
+        validation::no_validation
+    }
+
+    /// Validates the input of the endpoint with its [`RestType::input_validator`].
+    #[cfg(feature = "validator")]
+    fn validate(&self, input: &I) -> Result<(), ValidationErrors> {
+        (self.input_validator())(input)
+    }
+
+    /// Returns the path with the placeholders replaced by the given path parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path parameters do not provide a value for every placeholder of the path.
+    fn path_with(&self, params: &Self::PathParams) -> String {
+        fill_path(self.path(), params)
+    }
 }
 
-pub struct Rest<I, O> {
+pub struct Rest<I, O, P = (), E = NoError, C = Json> {
     path: &'static str,
     method: HttpMethod,
+    input_location: InputLocation,
+    error_status: u16,
+    success_status: u16,
+    idempotent: bool,
+    #[cfg(feature = "validator")]
+    input_validator: InputValidator<I>,
     input: PhantomData<I>,
     output: PhantomData<O>,
+    path_params: PhantomData<P>,
+    error: PhantomData<E>,
+    codec: PhantomData<C>,
 }
 
-impl<I, O> Clone for Rest<I, O> {
+impl<I, O, P, E, C> Clone for Rest<I, O, P, E, C> {
     fn clone(&self) -> Self {
-        Self { path: self.path, method: self.method.clone(), input: PhantomData, output: PhantomData }
+        Self {
+            path: self.path,
+            method: self.method.clone(),
+            input_location: self.input_location,
+            error_status: self.error_status,
+            success_status: self.success_status,
+            idempotent: self.idempotent,
+            #[cfg(feature = "validator")]
+            input_validator: self.input_validator,
+            input: PhantomData,
+            output: PhantomData,
+            path_params: PhantomData,
+            error: PhantomData,
+            codec: PhantomData,
+        }
     }
 }
 
-impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned> RestType<I, O> for Rest<I, O> {
+impl<
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+    C: Codec,
+> RestType<I, O> for Rest<I, O, P, E, C>
+{
+    type PathParams = P;
+    type Error = E;
+    type Codec = C;
+
     fn path(&self) -> &str {
         self.path
     }
@@ -41,11 +231,99 @@ impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned> RestType<
     fn method(&self) -> &HttpMethod {
         &self.method
     }
+
+    fn input_location(&self) -> InputLocation {
+        self.input_location
+    }
+
+    fn error_status(&self) -> u16 {
+        self.error_status
+    }
+
+    fn success_status(&self) -> u16 {
+        self.success_status
+    }
+
+    fn is_idempotent(&self) -> bool {
+        self.idempotent
+    }
+
+    #[cfg(feature = "validator")]
+    fn input_validator(&self) -> InputValidator<I> {
+        self.input_validator
+    }
 }
 
-impl<I, O> Rest<I, O> {
+impl<I, O, P, E, C> Rest<I, O, P, E, C> {
+    /// # Panics
+    ///
+    /// Panics if the path is not valid according to [`check_path`]; for a `const` endpoint it fails the build.
     pub const fn new(method: HttpMethod, path: &'static str) -> Self {
-        Self { method, path, input: PhantomData, output: PhantomData }
+        if let Err(error) = check_path(path) {
+            panic!("{}", error.message());
+        }
+        Self {
+            input_location: method.default_input_location(),
+            idempotent: method.is_idempotent(),
+            method,
+            path,
+            error_status: DEFAULT_ERROR_STATUS,
+            success_status: DEFAULT_SUCCESS_STATUS,
+            #[cfg(feature = "validator")]
+            input_validator: validation::no_validation,
+            input: PhantomData,
+            output: PhantomData,
+            path_params: PhantomData,
+            error: PhantomData,
+            codec: PhantomData,
+        }
+    }
+
+    /// Sets the HTTP status code of the responses that carry the typed error of the endpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the status is not a client or server error status (400-599).
+    pub const fn with_error_status(mut self, status: u16) -> Self {
+        assert!(is_error_status(status), "The error status must be within 400-599");
+        self.error_status = status;
+        self
+    }
+
+    /// Sets the HTTP status code of the responses that carry the output of the endpoint,
+    /// like `201 Created` or `202 Accepted`. The clients report any other successful status as unexpected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the status is not a success status (200-299).
+    pub const fn with_success_status(mut self, status: u16) -> Self {
+        assert!(is_success_status(status), "The success status must be within 200-299");
+        self.success_status = status;
+        self
+    }
+
+    /// Sets where the input of the endpoint is sent, overriding the default location of the method.
+    pub const fn with_input_location(mut self, location: InputLocation) -> Self {
+        self.input_location = location;
+        self
+    }
+
+    /// Declares the endpoint idempotent, like a `POST` that creates a resource with an id chosen by the client,
+    /// so that the clients retry its requests according to their [`RetryPolicy`].
+    pub const fn idempotent(mut self) -> Self {
+        self.idempotent = true;
+        self
+    }
+
+    /// Validates the input of the endpoint with its [`Validate`] implementation,
+    /// on the client before sending the request and on the server before calling the handler.
+    #[cfg(feature = "validator")]
+    pub const fn with_validation(mut self) -> Self
+    where
+        I: Validate,
+    {
+        self.input_validator = validation::validate;
+        self
     }
 
     pub const fn delete(path: &'static str) -> Self {
@@ -56,6 +334,14 @@ impl<I, O> Rest<I, O> {
         Self::new(HttpMethod::GET, path)
     }
 
+    pub const fn options(path: &'static str) -> Self {
+        Self::new(HttpMethod::OPTIONS, path)
+    }
+
+    pub const fn patch(path: &'static str) -> Self {
+        Self::new(HttpMethod::PATCH, path)
+    }
+
     pub const fn post(path: &'static str) -> Self {
         Self::new(HttpMethod::POST, path)
     }
@@ -65,20 +351,74 @@ impl<I, O> Rest<I, O> {
     }
 }
 
-pub struct RestFluent<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned> {
+impl<I, P, E, C> Rest<I, (), P, E, C> {
+    /// A `HEAD` endpoint has no output, as the responses to a `HEAD` request never have a body.
+    pub const fn head(path: &'static str) -> Self {
+        Self::new(HttpMethod::HEAD, path)
+    }
+}
+
+pub struct RestFluent<
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned = (),
+    E: Serialize + DeserializeOwned = NoError,
+    C: Codec = Json,
+> {
     path: String,
     method: HttpMethod,
+    input_location: InputLocation,
+    error_status: u16,
+    success_status: u16,
+    idempotent: bool,
+    #[cfg(feature = "validator")]
+    input_validator: InputValidator<I>,
     input: PhantomData<I>,
     output: PhantomData<O>,
+    path_params: PhantomData<P>,
+    error: PhantomData<E>,
+    codec: PhantomData<C>,
 }
 
-impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned> Clone for RestFluent<I, O> {
+impl<
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+    C: Codec,
+> Clone for RestFluent<I, O, P, E, C>
+{
     fn clone(&self) -> Self {
-        Self { path: self.path.clone(), method: self.method.clone(), input: PhantomData, output: PhantomData }
+        Self {
+            path: self.path.clone(),
+            method: self.method.clone(),
+            input_location: self.input_location,
+            error_status: self.error_status,
+            success_status: self.success_status,
+            idempotent: self.idempotent,
+            #[cfg(feature = "validator")]
+            input_validator: self.input_validator,
+            input: PhantomData,
+            output: PhantomData,
+            path_params: PhantomData,
+            error: PhantomData,
+            codec: PhantomData,
+        }
     }
 }
 
-impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned> RestType<I, O> for RestFluent<I, O> {
+impl<
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+    C: Codec,
+> RestType<I, O> for RestFluent<I, O, P, E, C>
+{
+    type PathParams = P;
+    type Error = E;
+    type Codec = C;
+
     fn path(&self) -> &str {
         &self.path
     }
@@ -86,26 +426,219 @@ impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned> RestType<
     fn method(&self) -> &HttpMethod {
         &self.method
     }
+
+    fn input_location(&self) -> InputLocation {
+        self.input_location
+    }
+
+    fn error_status(&self) -> u16 {
+        self.error_status
+    }
+
+    fn success_status(&self) -> u16 {
+        self.success_status
+    }
+
+    fn is_idempotent(&self) -> bool {
+        self.idempotent
+    }
+
+    #[cfg(feature = "validator")]
+    fn input_validator(&self) -> InputValidator<I> {
+        self.input_validator
+    }
 }
 
-impl<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned> RestFluent<I, O> {
-    pub fn new<P: Into<String>>(method: HttpMethod, path: P) -> Self {
-        Self { method, path: path.into(), input: PhantomData, output: PhantomData }
+impl<
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+    P: Serialize + DeserializeOwned,
+    E: Serialize + DeserializeOwned,
+    C: Codec,
+> RestFluent<I, O, P, E, C>
+{
+    /// # Panics
+    ///
+    /// Panics if the path is not valid according to [`check_path`].
+    pub fn new<S: Into<String>>(method: HttpMethod, path: S) -> Self {
+        let path = path.into();
+        if let Err(error) = check_path(&path) {
+            panic!("Invalid path [{}]: {}", path, error);
+        }
+        Self {
+            input_location: method.default_input_location(),
+            idempotent: method.is_idempotent(),
+            method,
+            path,
+            error_status: DEFAULT_ERROR_STATUS,
+            success_status: DEFAULT_SUCCESS_STATUS,
+            #[cfg(feature = "validator")]
+            input_validator: validation::no_validation,
+            input: PhantomData,
+            output: PhantomData,
+            path_params: PhantomData,
+            error: PhantomData,
+            codec: PhantomData,
+        }
+    }
+
+    /// Sets the HTTP status code of the responses that carry the typed error of the endpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the status is not a client or server error status (400-599).
+    pub fn with_error_status(mut self, status: u16) -> Self {
+        assert!(is_error_status(status), "The error status must be within 400-599");
+        self.error_status = status;
+        self
+    }
+
+    /// Sets the HTTP status code of the responses that carry the output of the endpoint,
+    /// like `201 Created` or `202 Accepted`. The clients report any other successful status as unexpected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the status is not a success status (200-299).
+    pub fn with_success_status(mut self, status: u16) -> Self {
+        assert!(is_success_status(status), "The success status must be within 200-299");
+        self.success_status = status;
+        self
+    }
+
+    /// Sets where the input of the endpoint is sent, overriding the default location of the method.
+    pub fn with_input_location(mut self, location: InputLocation) -> Self {
+        self.input_location = location;
+        self
+    }
+
+    /// Declares the endpoint idempotent, like a `POST` that creates a resource with an id chosen by the client,
+    /// so that the clients retry its requests according to their [`RetryPolicy`].
+    pub fn idempotent(mut self) -> Self {
+        self.idempotent = true;
+        self
     }
 
-    pub fn delete<P: Into<String>>(path: P) -> Self {
+    /// Validates the input of the endpoint with its [`Validate`] implementation,
+    /// on the client before sending the request and on the server before calling the handler.
+    #[cfg(feature = "validator")]
+    pub fn with_validation(mut self) -> Self
+    where
+        I: Validate,
+    {
+        self.input_validator = validation::validate;
+        self
+    }
+
+    pub fn delete<S: Into<String>>(path: S) -> Self {
         Self::new(HttpMethod::DELETE, path)
     }
 
-    pub fn get<P: Into<String>>(path: P) -> Self {
+    pub fn get<S: Into<String>>(path: S) -> Self {
         Self::new(HttpMethod::GET, path)
     }
 
-    pub fn post<P: Into<String>>(path: P) -> Self {
+    pub fn options<S: Into<String>>(path: S) -> Self {
+        Self::new(HttpMethod::OPTIONS, path)
+    }
+
+    pub fn patch<S: Into<String>>(path: S) -> Self {
+        Self::new(HttpMethod::PATCH, path)
+    }
+
+    pub fn post<S: Into<String>>(path: S) -> Self {
         Self::new(HttpMethod::POST, path)
     }
 
-    pub fn put<P: Into<String>>(path: P) -> Self {
+    pub fn put<S: Into<String>>(path: S) -> Self {
         Self::new(HttpMethod::PUT, path)
     }
 }
+
+impl<I: Serialize + DeserializeOwned, P: Serialize + DeserializeOwned, E: Serialize + DeserializeOwned, C: Codec>
+    RestFluent<I, (), P, E, C>
+{
+    /// A `HEAD` endpoint has no output, as the responses to a `HEAD` request never have a body.
+    pub fn head<S: Into<String>>(path: S) -> Self {
+        Self::new(HttpMethod::HEAD, path)
+    }
+}
+
+const fn is_error_status(status: u16) -> bool {
+    400 <= status && status < 600
+}
+
+const fn is_success_status(status: u16) -> bool {
+    200 <= status && status < 300
+}
+
+/// Whether a successful status fulfills the given success status of an endpoint, see [`RestType::accepts_success_status`].
+const fn accepts_status(success_status: u16, status: u16) -> bool {
+    status == success_status || (status == NO_CONTENT_STATUS && success_status == DEFAULT_SUCCESS_STATUS)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_send_the_input_to_the_default_location_of_the_method() {
+        assert_eq!(InputLocation::Query, Rest::<(), ()>::get("/api").input_location());
+        assert_eq!(InputLocation::Query, Rest::<(), ()>::delete("/api").input_location());
+        assert_eq!(InputLocation::Body, Rest::<(), ()>::post("/api").input_location());
+        assert_eq!(InputLocation::Body, RestFluent::<(), ()>::put("/api").input_location());
+    }
+
+    #[test]
+    fn should_retry_only_the_idempotent_endpoints() {
+        const CREATE: Rest<(), ()> = Rest::post("/api").idempotent();
+
+        assert!(Rest::<(), ()>::get("/api").is_idempotent());
+        assert!(Rest::<(), ()>::put("/api").is_idempotent());
+        assert!(RestFluent::<(), ()>::delete("/api").is_idempotent());
+        assert!(!Rest::<(), ()>::post("/api").is_idempotent());
+        assert!(!RestFluent::<(), ()>::patch("/api").is_idempotent());
+        assert!(CREATE.clone().is_idempotent());
+        assert!(RestFluent::<(), ()>::post("/api").idempotent().is_idempotent());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid path [api/users]: The path must start with '/'")]
+    fn should_panic_if_the_path_is_not_valid() {
+        RestFluent::<(), ()>::get("api/users");
+    }
+
+    #[test]
+    fn should_accept_the_success_status_of_the_endpoint() {
+        const CREATE: Rest<(), ()> = Rest::post("/api").with_success_status(201);
+        let get = RestFluent::<(), ()>::get("/api");
+
+        assert_eq!(201, CREATE.clone().success_status());
+        assert!(CREATE.accepts_success_status(201));
+        assert!(!CREATE.accepts_success_status(200));
+        assert!(!CREATE.accepts_success_status(204));
+
+        assert_eq!(DEFAULT_SUCCESS_STATUS, get.success_status());
+        assert!(get.accepts_success_status(200));
+        assert!(get.accepts_success_status(204));
+        assert!(!get.accepts_success_status(202));
+    }
+
+    #[test]
+    #[should_panic(expected = "The success status must be within 200-299")]
+    fn should_panic_if_the_success_status_is_not_valid() {
+        RestFluent::<(), ()>::post("/api").with_success_status(302);
+    }
+
+    #[test]
+    fn should_override_the_input_location() {
+        const DELETE_WITH_BODY: Rest<(), ()> = Rest::delete("/api").with_input_location(InputLocation::Body);
+
+        assert_eq!(InputLocation::Body, DELETE_WITH_BODY.input_location());
+        assert_eq!(InputLocation::Body, DELETE_WITH_BODY.clone().input_location());
+        assert_eq!(
+            InputLocation::Form,
+            RestFluent::<(), ()>::post("/api").with_input_location(InputLocation::Form).input_location()
+        );
+    }
+}
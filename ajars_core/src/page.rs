@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+
+use crate::CodecError;
+
+/// The page of a listing asked by a request, in the cursor or in the offset style.
+///
+/// The first page is asked without cursor and offset; the next ones with the `next` page returned by the previous one.
+/// Every field is optional, so that it can be sent in the query string of the request.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PageRequest {
+    /// The opaque position, returned by the server, after which the page starts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// The number of items that precede the page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// The maximum number of items of the page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+impl PageRequest {
+    /// Asks for the first page, with at most `limit` items.
+    pub fn first(limit: u64) -> Self {
+        Self { cursor: None, offset: None, limit: Some(limit) }
+    }
+
+    /// Asks for the page that starts after the given cursor.
+    pub fn after<S: Into<String>>(cursor: S, limit: Option<u64>) -> Self {
+        Self { cursor: Some(cursor.into()), offset: None, limit }
+    }
+
+    /// Asks for the page that starts after the given number of items.
+    pub fn at_offset(offset: u64, limit: Option<u64>) -> Self {
+        Self { cursor: None, offset: Some(offset), limit }
+    }
+
+    /// The number of items that precede the page, zero if not set.
+    pub fn offset_or_zero(&self) -> u64 {
+        self.offset.unwrap_or(0)
+    }
+
+    /// The maximum number of items of the page: `default` if not set, never more than `max`.
+    pub fn limit_or(&self, default: u64, max: u64) -> u64 {
+        self.limit.unwrap_or(default).min(max)
+    }
+}
+
+/// The input of a paginated endpoint, which carries the page asked by the request
+/// along with any other parameter of the listing, like its filters.
+///
+/// It is used by the clients to ask for the next pages with the same parameters,
+/// and by [`Page::with_next_link`] to build the link to the next page.
+pub trait Paginated: Sized {
+    /// Returns the same input asking for another page.
+    fn with_page(self, page: PageRequest) -> Self;
+}
+
+impl Paginated for PageRequest {
+    fn with_page(self, page: PageRequest) -> Self {
+        page
+    }
+}
+
+/// A page of a listing: its items and, if it is not the last one, the request of the next page.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// The request of the next page; there is no next page if it is missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<PageRequest>,
+    /// The path and the query string of the request of the next page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_link: Option<String>,
+    /// The number of items of the whole listing, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, next: Option<PageRequest>) -> Self {
+        Self { items, next, next_link: None, total: None }
+    }
+
+    /// The last page of a listing.
+    pub fn last(items: Vec<T>) -> Self {
+        Self::new(items, None)
+    }
+
+    /// A page in the cursor style, followed by the page that starts after `next_cursor`, if any,
+    /// with the same limit as the request.
+    pub fn with_cursor(items: Vec<T>, request: &PageRequest, next_cursor: Option<String>) -> Self {
+        Self::new(items, next_cursor.map(|cursor| PageRequest::after(cursor, request.limit)))
+    }
+
+    /// A page in the offset style of a listing of `total` items, followed by the page that starts after its items
+    /// as long as there are items left.
+    pub fn with_offset(items: Vec<T>, request: &PageRequest, total: u64) -> Self {
+        let end = request.offset_or_zero() + items.len() as u64;
+        let next = (!items.is_empty() && end < total).then(|| PageRequest::at_offset(end, request.limit));
+        Self::new(items, next).with_total(total)
+    }
+
+    pub fn with_total(mut self, total: u64) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Sets the link to the next page, if any: the given path with the query string of the input,
+    /// which asks for the next page. The path is the one of the request, e.g. `rest.path_with(&path_params)`.
+    pub fn with_next_link<I: Paginated + Serialize + Clone>(
+        mut self,
+        path: &str,
+        input: &I,
+    ) -> Result<Self, CodecError> {
+        self.next_link = match &self.next {
+            Some(next) => {
+                let query =
+                    serde_urlencoded::to_string(input.clone().with_page(next.clone())).map_err(CodecError::new)?;
+                Some(format!("{}?{}", path, query))
+            }
+            None => None,
+        };
+        Ok(self)
+    }
+
+    /// Whether there is no next page.
+    pub fn is_last(&self) -> bool {
+        self.next.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct Search {
+        text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cursor: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        limit: Option<u64>,
+    }
+
+    impl Paginated for Search {
+        fn with_page(self, page: PageRequest) -> Self {
+            Self { cursor: page.cursor, limit: page.limit, ..self }
+        }
+    }
+
+    #[test]
+    fn should_send_the_page_request_in_the_query_string() {
+        assert_eq!("limit=10", serde_urlencoded::to_string(PageRequest::first(10)).unwrap());
+        assert_eq!("cursor=abc", serde_urlencoded::to_string(PageRequest::after("abc", None)).unwrap());
+        assert_eq!(
+            PageRequest::at_offset(20, Some(10)),
+            serde_urlencoded::from_str::<PageRequest>("offset=20&limit=10").unwrap()
+        );
+        assert_eq!(PageRequest::default(), serde_urlencoded::from_str::<PageRequest>("").unwrap());
+    }
+
+    #[test]
+    fn should_bound_the_limit_of_the_page() {
+        assert_eq!(20, PageRequest::default().limit_or(20, 100));
+        assert_eq!(100, PageRequest::first(1000).limit_or(20, 100));
+        assert_eq!(5, PageRequest::first(5).limit_or(20, 100));
+    }
+
+    #[test]
+    fn should_follow_a_page_with_the_next_cursor() {
+        let page = Page::with_cursor(vec![1, 2], &PageRequest::first(2), Some("2".to_owned()));
+        assert_eq!(Some(PageRequest::after("2", Some(2))), page.next);
+
+        let page = Page::with_cursor(vec![3], &PageRequest::after("2", Some(2)), None);
+        assert!(page.is_last());
+    }
+
+    #[test]
+    fn should_follow_a_page_with_the_next_offset() {
+        let page = Page::with_offset(vec![1, 2], &PageRequest::first(2), 5);
+        assert_eq!(Some(PageRequest::at_offset(2, Some(2))), page.next);
+        assert_eq!(Some(5), page.total);
+
+        assert!(Page::with_offset(vec![5], &PageRequest::at_offset(4, Some(2)), 5).is_last());
+        assert!(Page::<u64>::with_offset(vec![], &PageRequest::at_offset(2, Some(2)), 5).is_last());
+    }
+
+    #[test]
+    fn should_build_the_link_to_the_next_page() {
+        let search = Search { text: "a b".to_owned(), cursor: None, limit: Some(2) };
+
+        let page = Page::with_cursor(vec![1, 2], &PageRequest::first(2), Some("2".to_owned()))
+            .with_next_link("/search", &search);
+        assert_eq!(Some("/search?text=a+b&cursor=2&limit=2".to_owned()), page.unwrap().next_link);
+
+        let page = Page::last(vec![3]).with_next_link("/search", &search);
+        assert_eq!(None, page.unwrap().next_link);
+    }
+
+    #[test]
+    fn should_omit_the_missing_fields_of_a_page() {
+        let page = Page::last(vec![1]);
+        assert_eq!("{\"items\":[1]}", serde_json::to_string(&page).unwrap());
+        assert_eq!(page, serde_json::from_str("{\"items\":[1]}").unwrap());
+    }
+}
@@ -0,0 +1,266 @@
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// The reason why a path template is not valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathError {
+    MissingLeadingSlash,
+    EmptySegment,
+    InvalidCharacter,
+    UnclosedPlaceholder,
+    UnopenedPlaceholder,
+    EmptyPlaceholder,
+    AmbiguousPlaceholders,
+    DuplicatePlaceholder,
+}
+
+impl PathError {
+    pub const fn message(&self) -> &'static str {
+        match self {
+            PathError::MissingLeadingSlash => "The path must start with '/'",
+            PathError::EmptySegment => "The path must not contain empty segments ('//')",
+            PathError::InvalidCharacter => "The path must not contain '?', '#' or whitespace",
+            PathError::UnclosedPlaceholder => "The path contains a '{' without a matching '}'",
+            PathError::UnopenedPlaceholder => "The path contains a '}' without a matching '{'",
+            PathError::EmptyPlaceholder => "The path contains a placeholder without a name",
+            PathError::AmbiguousPlaceholders => "A segment of the path contains more than one placeholder",
+            PathError::DuplicatePlaceholder => "The path contains the same placeholder more than once",
+        }
+    }
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for PathError {}
+
+/// Checks that a path template is well formed: it starts with `/`, it has no empty segments, no query string,
+/// fragment or whitespace, and its `{name}` placeholders are closed, named, unique and alone in their segment.
+///
+/// It is a `const fn`, so the paths of the `const` endpoints are checked at compile time:
+///
+/// ```compile_fail
+/// const USERS: ajars_core::Rest<(), ()> = ajars_core::Rest::get("users/{id");
+/// ```
+pub const fn check_path(path: &str) -> Result<(), PathError> {
+    let bytes = path.as_bytes();
+    if bytes.is_empty() || bytes[0] != b'/' {
+        return Err(PathError::MissingLeadingSlash);
+    }
+
+    let mut index = 0;
+    let mut segment_placeholders = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'/' => {
+                if index + 1 < bytes.len() && bytes[index + 1] == b'/' {
+                    return Err(PathError::EmptySegment);
+                }
+                segment_placeholders = 0;
+            }
+            b'?' | b'#' | b' ' | b'\t' | b'\r' | b'\n' => return Err(PathError::InvalidCharacter),
+            b'}' => return Err(PathError::UnopenedPlaceholder),
+            b'{' => {
+                let start = index + 1;
+                let mut end = start;
+                while end < bytes.len() && bytes[end] != b'}' {
+                    if bytes[end] == b'{' || bytes[end] == b'/' {
+                        return Err(PathError::UnclosedPlaceholder);
+                    }
+                    end += 1;
+                }
+                if end == bytes.len() {
+                    return Err(PathError::UnclosedPlaceholder);
+                }
+                if end == start {
+                    return Err(PathError::EmptyPlaceholder);
+                }
+                segment_placeholders += 1;
+                if segment_placeholders > 1 {
+                    return Err(PathError::AmbiguousPlaceholders);
+                }
+                if contains_placeholder(bytes, end + 1, start, end) {
+                    return Err(PathError::DuplicatePlaceholder);
+                }
+                index = end;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    Ok(())
+}
+
+/// Returns whether `bytes[from..]` contains a placeholder named `bytes[name_start..name_end]`.
+const fn contains_placeholder(bytes: &[u8], from: usize, name_start: usize, name_end: usize) -> bool {
+    let length = name_end - name_start;
+    let mut index = from;
+    while index + length + 1 < bytes.len() {
+        if bytes[index] == b'{' && bytes[index + length + 1] == b'}' {
+            let mut offset = 0;
+            while offset < length && bytes[index + 1 + offset] == bytes[name_start + offset] {
+                offset += 1;
+            }
+            if offset == length {
+                return true;
+            }
+        }
+        index += 1;
+    }
+    false
+}
+
+/// Returns the path template with the names of the placeholders removed.
+/// Two templates with the same shape match exactly the same paths.
+pub(crate) fn path_shape(template: &str) -> String {
+    let mut shape = String::with_capacity(template.len());
+    let mut in_placeholder = false;
+    for char in template.chars() {
+        match char {
+            '{' => {
+                in_placeholder = true;
+                shape.push_str("{}");
+            }
+            '}' => in_placeholder = false,
+            _ if in_placeholder => {}
+            _ => shape.push(char),
+        }
+    }
+    shape
+}
+
+/// Replaces the `{name}` placeholders of a path template with the values of the path parameters.
+///
+/// The parameters are matched by field name when they serialize as a struct or a map,
+/// by position when they serialize as a tuple or a sequence,
+/// and a single scalar value fills a template with exactly one placeholder.
+/// Every value is percent-encoded.
+///
+/// # Panics
+///
+/// Panics if the parameters do not provide a scalar value for every placeholder of the template.
+pub fn fill_path<P: Serialize>(template: &str, params: &P) -> String {
+    let params = serde_json::to_value(params)
+        .unwrap_or_else(|err| panic!("Cannot serialize the path parameters of [{}]: {}", template, err));
+
+    let mut path = String::with_capacity(template.len());
+    let mut remaining = template;
+    let mut position = 0;
+
+    while let Some(start) = remaining.find('{') {
+        let end = remaining[start..]
+            .find('}')
+            .map(|end| start + end)
+            .unwrap_or_else(|| panic!("Unclosed placeholder in the path [{}]", template));
+        let name = &remaining[start + 1..end];
+
+        let value = match &params {
+            Value::Object(fields) => fields.get(name),
+            Value::Array(values) => values.get(position),
+            Value::Null => None,
+            scalar => (position == 0).then_some(scalar),
+        };
+
+        path.push_str(&remaining[..start]);
+        match value {
+            Some(Value::String(value)) => encode_segment(&mut path, value),
+            Some(Value::Number(value)) => encode_segment(&mut path, &value.to_string()),
+            Some(Value::Bool(value)) => encode_segment(&mut path, &value.to_string()),
+            _ => panic!("Missing scalar value for the parameter [{}] of the path [{}]", name, template),
+        }
+
+        remaining = &remaining[end + 1..];
+        position += 1;
+    }
+
+    path.push_str(remaining);
+    path
+}
+
+/// Appends the percent-encoded value, leaving only the RFC 3986 unreserved characters as they are.
+fn encode_segment(path: &mut String, value: &str) {
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => path.push(byte as char),
+            _ => path.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct UserPath {
+        id: u64,
+        name: String,
+    }
+
+    #[test]
+    fn should_leave_a_path_without_placeholders_untouched() {
+        assert_eq!("/api/users", fill_path("/api/users", &()));
+    }
+
+    #[test]
+    fn should_fill_placeholders_by_name() {
+        let params = UserPath { id: 12, name: "John Doe".to_owned() };
+        assert_eq!("/api/users/12/John%20Doe/details", fill_path("/api/users/{id}/{name}/details", &params));
+    }
+
+    #[test]
+    fn should_fill_placeholders_by_position() {
+        assert_eq!("/api/users/12/a%2Fb", fill_path("/api/users/{id}/{name}", &(12, "a/b")));
+    }
+
+    #[test]
+    fn should_fill_a_single_placeholder_with_a_scalar() {
+        assert_eq!("/api/users/true", fill_path("/api/users/{active}", &true));
+    }
+
+    #[test]
+    fn should_accept_valid_paths() {
+        assert_eq!(Ok(()), check_path("/"));
+        assert_eq!(Ok(()), check_path("/api/users"));
+        assert_eq!(Ok(()), check_path("/api/users/"));
+        assert_eq!(Ok(()), check_path("/api/users/{id}/items/{item_id}"));
+        assert_eq!(Ok(()), check_path("/api/files/{name}.json"));
+        assert_eq!(Ok(()), check_path("/api/{id}/{i}"));
+    }
+
+    #[test]
+    fn should_reject_malformed_paths() {
+        assert_eq!(Err(PathError::MissingLeadingSlash), check_path(""));
+        assert_eq!(Err(PathError::MissingLeadingSlash), check_path("api/users"));
+        assert_eq!(Err(PathError::EmptySegment), check_path("/api//users"));
+        assert_eq!(Err(PathError::InvalidCharacter), check_path("/api/users?id=1"));
+        assert_eq!(Err(PathError::InvalidCharacter), check_path("/api/my users"));
+        assert_eq!(Err(PathError::UnclosedPlaceholder), check_path("/api/users/{id"));
+        assert_eq!(Err(PathError::UnclosedPlaceholder), check_path("/api/users/{id/items"));
+        assert_eq!(Err(PathError::UnopenedPlaceholder), check_path("/api/users/id}"));
+        assert_eq!(Err(PathError::EmptyPlaceholder), check_path("/api/users/{}"));
+        assert_eq!(Err(PathError::AmbiguousPlaceholders), check_path("/api/users/{first}-{last}"));
+        assert_eq!(Err(PathError::DuplicatePlaceholder), check_path("/api/{id}/items/{id}"));
+    }
+
+    #[test]
+    fn should_remove_the_names_of_the_placeholders() {
+        assert_eq!("/api/users/{}/items/{}", path_shape("/api/users/{id}/items/{item_id}"));
+        assert_eq!("/api/users", path_shape("/api/users"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Missing scalar value for the parameter [other]")]
+    fn should_panic_if_a_placeholder_has_no_value() {
+        let params = UserPath { id: 12, name: "John".to_owned() };
+        fill_path("/api/users/{id}/{other}", &params);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::HttpStatus;
+
+/// The response of an endpoint: its typed output along with the status and the headers of the HTTP response,
+/// like the `ETag` or the rate limits sent by the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response<O> {
+    status: HttpStatus,
+    headers: Vec<(String, String)>,
+    body: O,
+}
+
+impl<O> Response<O> {
+    pub fn new(status: HttpStatus, headers: Vec<(String, String)>, body: O) -> Self {
+        Self { status, headers, body }
+    }
+
+    /// The HTTP status of the response.
+    pub fn status(&self) -> HttpStatus {
+        self.status
+    }
+
+    /// The headers of the response as name and value pairs, a pair for each value.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Returns the first value of a header. The case of the name is ignored.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(header, _)| header.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+
+    /// Returns all the values of a header. The case of the name is ignored.
+    pub fn header_values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.headers
+            .iter()
+            .filter(move |(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The output of the endpoint.
+    pub fn body(&self) -> &O {
+        &self.body
+    }
+
+    /// Returns the output of the endpoint, dropping the status and the headers.
+    pub fn into_body(self) -> O {
+        self.body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_find_the_headers_ignoring_the_case_of_their_names() {
+        let response = Response::new(
+            HttpStatus::from(200),
+            vec![
+                ("etag".to_owned(), "\"v1\"".to_owned()),
+                ("set-cookie".to_owned(), "a=1".to_owned()),
+                ("set-cookie".to_owned(), "b=2".to_owned()),
+            ],
+            "body",
+        );
+
+        assert_eq!(200, response.status().status());
+        assert_eq!(Some("\"v1\""), response.header("ETag"));
+        assert_eq!(Some("a=1"), response.header("Set-Cookie"));
+        assert_eq!(vec!["a=1", "b=2"], response.header_values("SET-COOKIE").collect::<Vec<_>>());
+        assert_eq!(None, response.header("X-RateLimit-Remaining"));
+        assert_eq!("body", response.into_body());
+    }
+}
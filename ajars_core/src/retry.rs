@@ -0,0 +1,291 @@
+use std::future::Future;
+use std::time::Duration;
+
+use crate::AjarsError;
+
+/// The statuses retried by default: '408 Request Timeout', '429 Too Many Requests', '502 Bad Gateway',
+/// '503 Service Unavailable' and '504 Gateway Timeout'.
+pub const DEFAULT_RETRYABLE_STATUSES: &[u16] = &[408, 429, 502, 503, 504];
+
+/// When and how the clients send again a request that failed for a transient reason,
+/// like a '503 Service Unavailable' or a connection reset.
+///
+/// Only the requests to the idempotent endpoints are retried, see [`crate::RestType::is_idempotent`].
+/// The delay before each retry doubles at every attempt, up to a maximum, and is randomized
+/// so that the clients that failed together do not retry together;
+/// a longer delay asked by the server with a `Retry-After` header in seconds is honored up to the same maximum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retryable_statuses: Vec<u16>,
+    retry_transport_errors: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, the second one after about 100 milliseconds, retrying the transport errors
+    /// and the [`DEFAULT_RETRYABLE_STATUSES`].
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+            retryable_statuses: DEFAULT_RETRYABLE_STATUSES.to_vec(),
+            retry_transport_errors: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The policy that never retries, used by the clients unless they are given another one.
+    pub fn none() -> Self {
+        Self { max_attempts: 1, ..Self::default() }
+    }
+
+    /// Sets the number of attempts of a request, the first one included.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of attempts is zero.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        assert!(max_attempts > 0, "The max attempts must be at least 1");
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the delay before the first retry and the maximum delay before any retry.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Sets whether the delays are randomized, between half and the whole of their exponential value.
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the error statuses that are retried, when their body is not the typed error of the endpoint.
+    pub fn with_retryable_statuses<S: IntoIterator<Item = u16>>(mut self, statuses: S) -> Self {
+        self.retryable_statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Sets whether the requests that fail in the client backend, like for a connection reset, are retried.
+    pub fn with_transport_errors(mut self, retry: bool) -> Self {
+        self.retry_transport_errors = retry;
+        self
+    }
+
+    /// The number of attempts of a request, the first one included.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Whether the error is transient according to the policy, whatever the endpoint and the attempt.
+    pub fn is_retryable<E, C>(&self, error: &AjarsError<E, C>) -> bool {
+        match error {
+            AjarsError::Transport(_) => self.retry_transport_errors,
+            AjarsError::Status { status, .. } => self.retryable_statuses.contains(&status.status()),
+            _ => false,
+        }
+    }
+
+    /// Returns the delay before retrying a request to an endpoint that failed with the given error at the given
+    /// attempt, starting from 1; `None` if it must not be retried, because the endpoint is not idempotent,
+    /// the attempts are over or the error is not transient.
+    pub fn retry_delay<E, C>(&self, idempotent: bool, attempt: u32, error: &AjarsError<E, C>) -> Option<Duration> {
+        if !idempotent || attempt >= self.max_attempts || !self.is_retryable(error) {
+            return None;
+        }
+
+        let exponential = self.initial_backoff.saturating_mul(2u32.saturating_pow(attempt - 1)).min(self.max_backoff);
+        let delay = match self.jitter {
+            true => exponential / 2 + (exponential / 2).mul_f64(random_fraction()),
+            false => exponential,
+        };
+        Some(retry_after(error).map_or(delay, |after| delay.max(after.min(self.max_backoff))))
+    }
+
+    /// Sends a request to an endpoint with `send` until it succeeds, fails with an error that must not be retried
+    /// or the attempts are over, waiting with `sleep` before each retry.
+    /// The clients pass the timer of their runtime as `sleep`.
+    pub async fn run<T, E, C, F, Fut, S, SFut>(
+        &self,
+        idempotent: bool,
+        mut send: F,
+        sleep: S,
+    ) -> Result<T, AjarsError<E, C>>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, AjarsError<E, C>>>,
+        S: Fn(Duration) -> SFut,
+        SFut: Future<Output = ()>,
+    {
+        let mut attempt = 1;
+        loop {
+            let error = match send().await {
+                Ok(output) => return Ok(output),
+                Err(error) => error,
+            };
+            match self.retry_delay(idempotent, attempt, &error) {
+                Some(delay) => sleep(delay).await,
+                None => return Err(error),
+            }
+            attempt += 1;
+        }
+    }
+}
+
+/// The delay asked by the server with a `Retry-After` header in seconds; the HTTP dates are not supported.
+fn retry_after<E, C>(error: &AjarsError<E, C>) -> Option<Duration> {
+    match error {
+        AjarsError::Status { headers, .. } => headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("retry-after"))
+            .and_then(|(_, value)| value.trim().parse().ok())
+            .map(Duration::from_secs),
+        _ => None,
+    }
+}
+
+/// A random number within 0 and 1, seeded by the operating system or, in the browser, by its crypto API,
+/// so that every client retries after its own delays.
+fn random_fraction() -> f64 {
+    rand::random()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::cell::Cell;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    use super::*;
+    use crate::{CodecError, HttpStatus, NoError};
+
+    type Error = AjarsError<NoError, String>;
+
+    fn status(status: u16, headers: &[(&str, &str)]) -> Error {
+        AjarsError::Status {
+            status: HttpStatus::from(status),
+            headers: headers.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+            body: vec![],
+        }
+    }
+
+    /// Polls a future whose steps never wait.
+    fn ready_now<F: Future>(future: F) -> F::Output {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("The future should be ready"),
+        }
+    }
+
+    #[test]
+    fn should_retry_only_the_transient_errors() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.is_retryable(&Error::Transport("Connection reset".to_owned())));
+        assert!(policy.is_retryable(&status(503, &[])));
+        assert!(policy.is_retryable(&status(429, &[])));
+        assert!(!policy.is_retryable(&status(500, &[])));
+        assert!(!policy.is_retryable(&status(404, &[])));
+        assert!(!policy.is_retryable(&Error::Decode(CodecError::new("Not JSON"))));
+        assert!(!policy.is_retryable(&Error::UnexpectedStatus { status: 201.into(), expected: 200.into() }));
+
+        let policy = policy.with_retryable_statuses([500]).with_transport_errors(false);
+        assert!(policy.is_retryable(&status(500, &[])));
+        assert!(!policy.is_retryable(&status(503, &[])));
+        assert!(!policy.is_retryable(&Error::Transport("Connection reset".to_owned())));
+    }
+
+    #[test]
+    fn should_retry_only_the_idempotent_endpoints_until_the_attempts_are_over() {
+        let policy = RetryPolicy::default().with_max_attempts(3);
+        let error = status(503, &[]);
+
+        assert!(policy.retry_delay(true, 1, &error).is_some());
+        assert!(policy.retry_delay(true, 2, &error).is_some());
+        assert_eq!(None, policy.retry_delay(true, 3, &error));
+        assert_eq!(None, policy.retry_delay(false, 1, &error));
+        assert_eq!(None, RetryPolicy::none().retry_delay(true, 1, &error));
+    }
+
+    #[test]
+    fn should_double_the_delay_up_to_the_maximum() {
+        let policy = RetryPolicy::default()
+            .with_max_attempts(10)
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(500))
+            .with_jitter(false);
+        let error = status(503, &[]);
+
+        let delays = (1..6).map(|attempt| policy.retry_delay(true, attempt, &error).unwrap()).collect::<Vec<_>>();
+        assert_eq!([100, 200, 400, 500, 500].map(Duration::from_millis).to_vec(), delays);
+    }
+
+    #[test]
+    fn should_randomize_the_delay_within_half_and_the_whole_of_it() {
+        let policy = RetryPolicy::default().with_backoff(Duration::from_millis(400), Duration::from_secs(1));
+        let error = status(503, &[]);
+
+        for _ in 0..100 {
+            let delay = policy.retry_delay(true, 1, &error).unwrap();
+            assert!(Duration::from_millis(200) <= delay && delay <= Duration::from_millis(400), "{:?}", delay);
+        }
+    }
+
+    #[test]
+    fn should_honor_the_retry_after_header_up_to_the_maximum() {
+        let policy = RetryPolicy::default().with_backoff(Duration::from_millis(100), Duration::from_secs(5));
+
+        assert_eq!(Some(Duration::from_secs(2)), policy.retry_delay(true, 1, &status(503, &[("Retry-After", "2")])));
+        assert_eq!(Some(Duration::from_secs(5)), policy.retry_delay(true, 1, &status(429, &[("retry-after", "60")])));
+    }
+
+    #[test]
+    fn should_send_again_until_the_request_succeeds() {
+        let policy = RetryPolicy::default().with_max_attempts(5);
+        let attempts = Cell::new(0);
+        let sleeps = Cell::new(0);
+
+        let result = ready_now(policy.run(
+            true,
+            || {
+                attempts.set(attempts.get() + 1);
+                let result = if attempts.get() < 3 { Err(status(503, &[])) } else { Ok(attempts.get()) };
+                async move { result }
+            },
+            |_| {
+                sleeps.set(sleeps.get() + 1);
+                async {}
+            },
+        ));
+
+        assert_eq!(3, result.unwrap());
+        assert_eq!(2, sleeps.get());
+    }
+
+    #[test]
+    fn should_return_the_last_error_when_the_attempts_are_over() {
+        let policy = RetryPolicy::default().with_max_attempts(2);
+        let attempts = Cell::new(0);
+
+        let result: Result<(), Error> = ready_now(policy.run(
+            true,
+            || {
+                attempts.set(attempts.get() + 1);
+                async { Err(status(503, &[])) }
+            },
+            |_| async {},
+        ));
+
+        assert_eq!(Some(503), result.unwrap_err().status().map(u16::from));
+        assert_eq!(2, attempts.get());
+    }
+}
@@ -0,0 +1,255 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{CodecError, NoError, Rest};
+
+/// The `Content-Type` of the responses of the Server-Sent Events endpoints.
+pub const SSE_CONTENT_TYPE: &str = "text/event-stream";
+
+/// The header with which a client that reconnects sends the ID of the last event it received.
+pub const LAST_EVENT_ID_HEADER: &str = "Last-Event-ID";
+
+/// A Server-Sent Events endpoint, whose events carry a payload of type `O` encoded as JSON.
+///
+/// The endpoint is a `GET` call with the input in the query string, as it is the only request
+/// that the `EventSource` of the browsers can send. The handler of the endpoint returns a stream of
+/// `Result<SseEvent<O>, E>`: an error returned before the first event is sent as the typed error of the endpoint,
+/// while an error returned after it closes the connection.
+pub struct RestSse<I, O, P = (), E = NoError> {
+    rest: Rest<I, O, P, E>,
+}
+
+impl<I, O, P, E> Clone for RestSse<I, O, P, E> {
+    fn clone(&self) -> Self {
+        Self { rest: self.rest.clone() }
+    }
+}
+
+impl<I, O, P, E> RestSse<I, O, P, E> {
+    /// # Panics
+    ///
+    /// Panics if the path is not valid according to [`check_path`](crate::check_path);
+    /// for a `const` endpoint it fails the build.
+    pub const fn get(path: &'static str) -> Self {
+        Self { rest: Rest::get(path) }
+    }
+
+    /// Sets the HTTP status code of the responses that carry the typed error of the endpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the status is not a client or server error status (400-599).
+    pub const fn with_error_status(self, status: u16) -> Self {
+        Self { rest: self.rest.with_error_status(status) }
+    }
+
+    /// The endpoint that opens the stream of events; its output type is the type of the payload of the events.
+    pub fn rest(&self) -> &Rest<I, O, P, E> {
+        &self.rest
+    }
+}
+
+/// An event of a Server-Sent Events endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent<T> {
+    /// The ID of the event. A client that reconnects sends the last ID it received in the `Last-Event-ID` header,
+    /// so that the server can resume the stream after it.
+    /// As in the `EventSource` of the browsers, a received event without an ID carries the last ID received before it.
+    pub id: Option<String>,
+    pub data: T,
+}
+
+impl<T> SseEvent<T> {
+    pub fn new(data: T) -> Self {
+        Self { id: None, data }
+    }
+
+    pub fn with_id<S: Into<String>>(mut self, id: S) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+}
+
+/// Encodes an event as a message of the `text/event-stream` format, with its payload as JSON.
+pub fn encode_sse_event<T: Serialize>(event: &SseEvent<T>) -> Result<Vec<u8>, CodecError> {
+    let mut message = Vec::new();
+    if let Some(id) = &event.id {
+        if id.contains(['\n', '\r', '\0']) {
+            return Err(CodecError::new(format!("The ID of the event [{id:?}] contains a line break or a NUL")));
+        }
+        message.extend_from_slice(b"id: ");
+        message.extend_from_slice(id.as_bytes());
+        message.push(b'\n');
+    }
+    // JSON without pretty printing never contains a line break, so the payload always fits a single data line
+    message.extend_from_slice(b"data: ");
+    message.extend(serde_json::to_vec(&event.data).map_err(CodecError::new)?);
+    message.extend_from_slice(b"\n\n");
+    Ok(message)
+}
+
+/// Decodes the events of a `text/event-stream` response body from its chunks of bytes,
+/// that can split a line at any byte. The lines can end with LF or CRLF.
+///
+/// The ID of the last event and the reconnection time sent by the server are kept across reconnections.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buffer: Vec<u8>,
+    data: Option<String>,
+    last_event_id: Option<String>,
+    retry: Option<Duration>,
+}
+
+impl SseDecoder {
+    /// Appends a chunk of the body and returns the events that it completes. Comments are skipped.
+    pub fn decode<T: DeserializeOwned>(&mut self, chunk: &[u8]) -> Vec<Result<SseEvent<T>, CodecError>> {
+        self.buffer.extend_from_slice(chunk);
+        let Some(end) = self.buffer.iter().rposition(|byte| *byte == b'\n') else {
+            return vec![];
+        };
+        let lines = self.buffer.drain(..=end).collect::<Vec<_>>();
+        let mut events = vec![];
+        for line in lines[..lines.len() - 1].split(|byte| *byte == b'\n') {
+            let line = String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line));
+            if let Some(event) = self.decode_line(&line) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// The ID of the last event received, to be sent in the `Last-Event-ID` header when reconnecting.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// The reconnection time sent by the server, if any.
+    pub fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+
+    /// Discards the event partially received on a connection that was lost.
+    pub fn reconnect(&mut self) {
+        self.buffer.clear();
+        self.data = None;
+    }
+
+    fn decode_line<T: DeserializeOwned>(&mut self, line: &str) -> Option<Result<SseEvent<T>, CodecError>> {
+        // An empty line dispatches the event
+        if line.is_empty() {
+            let data = self.data.take()?;
+            let event = serde_json::from_str(&data)
+                .map(|data| SseEvent { id: self.last_event_id.clone(), data })
+                .map_err(CodecError::new);
+            return Some(event);
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "data" => match &mut self.data {
+                Some(data) => {
+                    data.push('\n');
+                    data.push_str(value);
+                }
+                None => self.data = Some(value.to_owned()),
+            },
+            "id" if !value.contains('\0') => {
+                self.last_event_id = Some(value.to_owned()).filter(|id| !id.is_empty());
+            }
+            "retry" => {
+                if let Ok(millis) = value.parse() {
+                    self.retry = Some(Duration::from_millis(millis));
+                }
+            }
+            // The comments, whose field is empty, the event types and the unknown fields are ignored
+            _ => {}
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{HttpMethod, InputLocation, RestType};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Tick {
+        count: u64,
+    }
+
+    #[test]
+    fn should_describe_the_endpoint_of_the_events() {
+        const TICKS: RestSse<(), Tick, (), String> = RestSse::get("/ticks").with_error_status(409);
+
+        let rest = TICKS.clone();
+        assert_eq!(&HttpMethod::GET, rest.rest().method());
+        assert_eq!("/ticks", rest.rest().path());
+        assert_eq!(409, rest.rest().error_status());
+        assert_eq!(InputLocation::Query, rest.rest().input_location());
+    }
+
+    #[test]
+    fn should_encode_an_event() {
+        assert_eq!(b"data: {\"count\":1}\n\n".to_vec(), encode_sse_event(&SseEvent::new(Tick { count: 1 })).unwrap());
+        assert_eq!(
+            b"id: 7\ndata: {\"count\":1}\n\n".to_vec(),
+            encode_sse_event(&SseEvent::new(Tick { count: 1 }).with_id("7")).unwrap()
+        );
+        assert!(encode_sse_event(&SseEvent::new(Tick { count: 1 }).with_id("7\n")).is_err());
+    }
+
+    #[test]
+    fn should_decode_the_events_split_across_chunks() {
+        let mut decoder = SseDecoder::default();
+
+        let first: Vec<Result<SseEvent<Tick>, _>> = decoder.decode(b": a comment\nid: 1\ndata: {\"count\"");
+        let second: Vec<Result<SseEvent<Tick>, _>> = decoder.decode(b":1}\r\n\r\nretry: 500\ndata: {\"count\":2}\n\n");
+
+        assert!(first.is_empty());
+        assert_eq!(
+            vec![SseEvent::new(Tick { count: 1 }).with_id("1"), SseEvent::new(Tick { count: 2 }).with_id("1")],
+            second.into_iter().map(Result::unwrap).collect::<Vec<_>>()
+        );
+        assert_eq!(Some("1"), decoder.last_event_id());
+        assert_eq!(Some(Duration::from_millis(500)), decoder.retry());
+    }
+
+    #[test]
+    fn should_join_the_data_lines_of_an_event() {
+        let mut decoder = SseDecoder::default();
+
+        let events: Vec<Result<SseEvent<Tick>, _>> = decoder.decode(b"data: {\"count\":\ndata: 3}\n\n");
+
+        assert_eq!(Tick { count: 3 }, events.into_iter().next().unwrap().unwrap().data);
+    }
+
+    #[test]
+    fn should_keep_the_last_event_id_when_reconnecting() {
+        let mut decoder = SseDecoder::default();
+
+        let events: Vec<Result<SseEvent<Tick>, _>> = decoder.decode(b"id: 1\ndata: {\"count\":1}\n\nid: 2\ndata: {");
+        decoder.reconnect();
+        let resumed: Vec<Result<SseEvent<Tick>, _>> = decoder.decode(b"data: {\"count\":2}\n\n");
+
+        assert_eq!(1, events.len());
+        assert_eq!(Some("2"), decoder.last_event_id());
+        assert_eq!(Some("2".to_owned()), resumed.into_iter().next().unwrap().unwrap().id);
+    }
+
+    #[test]
+    fn should_fail_to_decode_an_event_that_is_not_a_payload() {
+        let mut decoder = SseDecoder::default();
+
+        let events: Vec<Result<SseEvent<Tick>, _>> = decoder.decode(b"data: not json\n\n");
+
+        assert!(events[0].is_err());
+    }
+}
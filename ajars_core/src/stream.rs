@@ -0,0 +1,154 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{CodecError, HttpMethod, InputLocation, NoError, Rest};
+
+/// The `Content-Type` of the responses of the streaming endpoints.
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// An endpoint whose output is a stream of items of type `O`, sent as newline-delimited JSON:
+/// one JSON document per line, decoded by the clients as soon as it arrives.
+///
+/// The handler of the endpoint returns a stream of `Result<O, E>`. An error returned before the first item is sent
+/// as the typed error of the endpoint, while an error returned after it aborts the response,
+/// as its status has already been sent.
+pub struct RestStream<I, O, P = (), E = NoError> {
+    rest: Rest<I, O, P, E>,
+}
+
+impl<I, O, P, E> Clone for RestStream<I, O, P, E> {
+    fn clone(&self) -> Self {
+        Self { rest: self.rest.clone() }
+    }
+}
+
+impl<I, O, P, E> RestStream<I, O, P, E> {
+    /// # Panics
+    ///
+    /// Panics if the path is not valid according to [`check_path`](crate::check_path);
+    /// for a `const` endpoint it fails the build.
+    pub const fn new(method: HttpMethod, path: &'static str) -> Self {
+        Self { rest: Rest::new(method, path) }
+    }
+
+    /// Sets the HTTP status code of the responses that carry the typed error of the endpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the status is not a client or server error status (400-599).
+    pub const fn with_error_status(self, status: u16) -> Self {
+        Self { rest: self.rest.with_error_status(status) }
+    }
+
+    /// Sets where the input of the endpoint is sent, overriding the default location of the method.
+    pub const fn with_input_location(self, location: InputLocation) -> Self {
+        Self { rest: self.rest.with_input_location(location) }
+    }
+
+    pub const fn get(path: &'static str) -> Self {
+        Self::new(HttpMethod::GET, path)
+    }
+
+    pub const fn post(path: &'static str) -> Self {
+        Self::new(HttpMethod::POST, path)
+    }
+
+    /// The endpoint that sends the request; its output type is the type of the items of the stream.
+    pub fn rest(&self) -> &Rest<I, O, P, E> {
+        &self.rest
+    }
+}
+
+/// Encodes an item of a stream as a line of newline-delimited JSON.
+pub fn encode_ndjson_item<T: Serialize>(item: &T) -> Result<Vec<u8>, CodecError> {
+    let mut line = serde_json::to_vec(item).map_err(CodecError::new)?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+/// Decodes the items of a newline-delimited JSON stream from the chunks of bytes of a response body,
+/// that can split a line at any byte.
+#[derive(Debug, Default)]
+pub struct NdjsonDecoder {
+    buffer: Vec<u8>,
+}
+
+impl NdjsonDecoder {
+    /// Appends a chunk of the body and returns the items of the lines that it completes. Empty lines are skipped.
+    pub fn decode<T: DeserializeOwned>(&mut self, chunk: &[u8]) -> Vec<Result<T, CodecError>> {
+        self.buffer.extend_from_slice(chunk);
+        let Some(end) = self.buffer.iter().rposition(|byte| *byte == b'\n') else {
+            return vec![];
+        };
+        let lines = self.buffer.drain(..=end).collect::<Vec<_>>();
+        lines.split(|byte| *byte == b'\n').filter_map(decode_line).collect()
+    }
+
+    /// Returns the item of the last line, when the body does not end with a newline.
+    pub fn finish<T: DeserializeOwned>(&mut self) -> Option<Result<T, CodecError>> {
+        let line = std::mem::take(&mut self.buffer);
+        decode_line(&line)
+    }
+}
+
+fn decode_line<T: DeserializeOwned>(line: &[u8]) -> Option<Result<T, CodecError>> {
+    if line.trim_ascii().is_empty() {
+        return None;
+    }
+    Some(serde_json::from_slice(line).map_err(CodecError::new))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::RestType;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Row {
+        id: u64,
+    }
+
+    #[test]
+    fn should_describe_the_endpoint_of_the_stream() {
+        const EXPORT: RestStream<(), Row, (), String> =
+            RestStream::post("/export").with_error_status(409).with_input_location(InputLocation::Query);
+
+        let rest = EXPORT.clone();
+        assert_eq!(&HttpMethod::POST, rest.rest().method());
+        assert_eq!("/export", rest.rest().path());
+        assert_eq!(409, rest.rest().error_status());
+        assert_eq!(InputLocation::Query, rest.rest().input_location());
+    }
+
+    #[test]
+    fn should_encode_an_item_per_line() {
+        assert_eq!(b"{\"id\":1}\n".to_vec(), encode_ndjson_item(&Row { id: 1 }).unwrap());
+    }
+
+    #[test]
+    fn should_decode_the_lines_split_across_chunks() {
+        let mut decoder = NdjsonDecoder::default();
+
+        let first: Vec<Result<Row, _>> = decoder.decode(b"{\"id\":1}\n{\"id\"");
+        let second: Vec<Result<Row, _>> = decoder.decode(b":2}\n\n{\"id\":3}");
+        let last: Option<Result<Row, _>> = decoder.finish();
+
+        assert_eq!(vec![Row { id: 1 }], first.into_iter().map(Result::unwrap).collect::<Vec<_>>());
+        assert_eq!(vec![Row { id: 2 }], second.into_iter().map(Result::unwrap).collect::<Vec<_>>());
+        assert_eq!(Row { id: 3 }, last.unwrap().unwrap());
+        assert!(decoder.finish::<Row>().is_none());
+    }
+
+    #[test]
+    fn should_fail_to_decode_a_line_that_is_not_an_item() {
+        let mut decoder = NdjsonDecoder::default();
+
+        let items: Vec<Result<Row, _>> = decoder.decode(b"{\"id\":1}\nnot json\n");
+
+        assert!(items[0].is_ok());
+        assert!(items[1].is_err());
+    }
+}
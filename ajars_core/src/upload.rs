@@ -0,0 +1,344 @@
+use std::collections::hash_map::RandomState;
+use std::fs::{self, File, OpenOptions};
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use thiserror::Error as ThisError;
+
+use crate::{CodecError, HttpMethod, NoError, Rest};
+
+/// The name of the part that carries the metadata of an upload, encoded as JSON.
+pub const METADATA_PART: &str = "metadata";
+
+/// The name of the parts that carry the files of an upload.
+pub const FILE_PART: &str = "file";
+
+/// The content type of the files sent without one.
+pub const DEFAULT_FILE_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// An endpoint that receives files together with typed metadata of type `M`, sent as a `multipart/form-data` body:
+/// a `metadata` part with the metadata encoded as JSON and a `file` part for each file.
+///
+/// The handler of the endpoint receives the metadata and the files, already streamed to temporary files,
+/// and returns a `Result<O, E>` like the handler of a [`Rest`] endpoint.
+pub struct RestUpload<M, O, P = (), E = NoError> {
+    rest: Rest<M, O, P, E>,
+}
+
+impl<M, O, P, E> Clone for RestUpload<M, O, P, E> {
+    fn clone(&self) -> Self {
+        Self { rest: self.rest.clone() }
+    }
+}
+
+impl<M, O, P, E> RestUpload<M, O, P, E> {
+    /// # Panics
+    ///
+    /// Panics if the path is not valid according to [`check_path`](crate::check_path);
+    /// for a `const` endpoint it fails the build.
+    pub const fn new(method: HttpMethod, path: &'static str) -> Self {
+        Self { rest: Rest::new(method, path) }
+    }
+
+    /// Sets the HTTP status code of the responses that carry the typed error of the endpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the status is not a client or server error status (400-599).
+    pub const fn with_error_status(self, status: u16) -> Self {
+        Self { rest: self.rest.with_error_status(status) }
+    }
+
+    /// Sets the HTTP status code of the responses that carry the output of the endpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the status is not a success status (200-299).
+    pub const fn with_success_status(self, status: u16) -> Self {
+        Self { rest: self.rest.with_success_status(status) }
+    }
+
+    /// Validates the metadata of the upload with its [`Validate`](crate::Validate) implementation,
+    /// on the client before sending the request and on the server before calling the handler.
+    #[cfg(feature = "validator")]
+    pub const fn with_validation(self) -> Self
+    where
+        M: crate::Validate,
+    {
+        Self { rest: self.rest.with_validation() }
+    }
+
+    pub const fn post(path: &'static str) -> Self {
+        Self::new(HttpMethod::POST, path)
+    }
+
+    pub const fn put(path: &'static str) -> Self {
+        Self::new(HttpMethod::PUT, path)
+    }
+
+    /// The endpoint that sends the request; its input type is the type of the metadata.
+    pub fn rest(&self) -> &Rest<M, O, P, E> {
+        &self.rest
+    }
+}
+
+/// A file sent by a client to an upload endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePart {
+    file_name: String,
+    content_type: String,
+    content: Vec<u8>,
+}
+
+impl FilePart {
+    pub fn new<S: Into<String>, C: Into<Vec<u8>>>(file_name: S, content: C) -> Self {
+        Self {
+            file_name: file_name.into(),
+            content_type: DEFAULT_FILE_CONTENT_TYPE.to_owned(),
+            content: content.into(),
+        }
+    }
+
+    pub fn with_content_type<S: Into<String>>(mut self, content_type: S) -> Self {
+        self.content_type = content_type.into();
+        self
+    }
+
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+
+    pub fn into_content(self) -> Vec<u8> {
+        self.content
+    }
+}
+
+/// Returns a random boundary for a `multipart/form-data` body.
+pub fn multipart_boundary() -> String {
+    // Every `RandomState` is seeded with different random keys
+    let random = || RandomState::new().build_hasher().finish();
+    format!("ajars-{:016x}{:016x}", random(), random())
+}
+
+/// The `Content-Type` of a `multipart/form-data` body with the given boundary.
+pub fn multipart_content_type(boundary: &str) -> String {
+    format!("multipart/form-data; boundary={boundary}")
+}
+
+/// Encodes the metadata and the files of an upload as a `multipart/form-data` body,
+/// for the clients without built-in support for multipart requests.
+pub fn encode_multipart<M: Serialize>(boundary: &str, metadata: &M, files: &[FilePart]) -> Result<Vec<u8>, CodecError> {
+    let mut body = Vec::new();
+    body.extend_from_slice(
+        format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"{METADATA_PART}\"\r\nContent-Type: application/json\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend(serde_json::to_vec(metadata).map_err(CodecError::new)?);
+    for file in files {
+        body.extend_from_slice(
+            format!(
+                "\r\n--{boundary}\r\nContent-Disposition: form-data; name=\"{FILE_PART}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+                escape_quoted(&file.file_name),
+                file.content_type
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(&file.content);
+    }
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+    Ok(body)
+}
+
+/// Escapes a file name in a quoted parameter of a header, as the browsers do.
+fn escape_quoted(value: &str) -> String {
+    value.replace('"', "%22").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// A file received by an upload endpoint, streamed to a temporary file.
+/// The temporary file is deleted when the `UploadedFile` is dropped, unless it is persisted.
+#[derive(Debug)]
+pub struct UploadedFile {
+    file_name: Option<String>,
+    content_type: Option<String>,
+    path: PathBuf,
+}
+
+impl UploadedFile {
+    /// Creates a new, empty, temporary file for a received file, returning it opened for writing.
+    pub fn create(file_name: Option<String>, content_type: Option<String>) -> io::Result<(Self, File)> {
+        let directory = std::env::temp_dir();
+        loop {
+            let path = directory.join(format!("{}.upload", multipart_boundary()));
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((Self { file_name, content_type, path }, file)),
+                Err(error) if error.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// The name of the file, as sent by the client.
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+
+    /// The content type of the file, as sent by the client.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// The path of the temporary file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The size of the file in bytes.
+    pub fn size(&self) -> io::Result<u64> {
+        Ok(fs::metadata(&self.path)?.len())
+    }
+
+    /// Reads the whole content of the file.
+    pub fn read(&self) -> io::Result<Vec<u8>> {
+        fs::read(&self.path)
+    }
+
+    /// Moves the temporary file to the given path, so that it is not deleted.
+    /// On failure the file is returned with the error, so that it can be persisted elsewhere.
+    pub fn persist<Q: AsRef<Path>>(self, to: Q) -> Result<(), PersistError> {
+        // A rename fails across file systems, where the file is copied and then removed instead
+        if fs::rename(&self.path, &to).is_err() {
+            if let Err(error) = fs::copy(&self.path, &to) {
+                return Err(PersistError { error, file: self });
+            }
+            fs::remove_file(&self.path).ok();
+        }
+        Ok(())
+    }
+}
+
+/// The error returned when an `UploadedFile` cannot be persisted, holding the file that was not moved.
+#[derive(Debug, ThisError)]
+#[error("{error}")]
+pub struct PersistError {
+    /// The error of the failed move.
+    #[source]
+    pub error: io::Error,
+    /// The file that was not persisted; its temporary file is still deleted when it is dropped.
+    pub file: UploadedFile,
+}
+
+impl From<PersistError> for io::Error {
+    fn from(error: PersistError) -> Self {
+        error.error
+    }
+}
+
+impl Drop for UploadedFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Write;
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::RestType;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Document {
+        title: String,
+    }
+
+    #[test]
+    fn should_describe_the_endpoint_of_the_upload() {
+        const UPLOAD: RestUpload<Document, (), u64, String> =
+            RestUpload::put("/folders/{id}").with_error_status(409).with_success_status(201);
+
+        let rest = UPLOAD.clone();
+        assert_eq!(&HttpMethod::PUT, rest.rest().method());
+        assert_eq!("/folders/7", rest.rest().path_with(&7));
+        assert_eq!(409, rest.rest().error_status());
+        assert_eq!(201, rest.rest().success_status());
+    }
+
+    #[test]
+    fn should_encode_the_metadata_and_the_files_as_multipart() {
+        let files = [
+            FilePart::new("a \"b\".txt", "hello"),
+            FilePart::new("c.json", "{}").with_content_type("application/json"),
+        ];
+
+        let body = encode_multipart("XYZ", &Document { title: "doc".to_owned() }, &files).unwrap();
+
+        assert_eq!(
+            "--XYZ\r\nContent-Disposition: form-data; name=\"metadata\"\r\nContent-Type: application/json\r\n\r\n\
+             {\"title\":\"doc\"}\r\n\
+             --XYZ\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a %22b%22.txt\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\nhello\r\n\
+             --XYZ\r\nContent-Disposition: form-data; name=\"file\"; filename=\"c.json\"\r\n\
+             Content-Type: application/json\r\n\r\n{}\r\n\
+             --XYZ--\r\n",
+            String::from_utf8(body).unwrap()
+        );
+        assert_eq!("multipart/form-data; boundary=XYZ", multipart_content_type("XYZ"));
+    }
+
+    #[test]
+    fn should_generate_different_boundaries() {
+        assert_ne!(multipart_boundary(), multipart_boundary());
+    }
+
+    #[test]
+    fn should_delete_the_temporary_file_unless_persisted() {
+        let (uploaded, mut file) = UploadedFile::create(Some("a.txt".to_owned()), None).unwrap();
+        file.write_all(b"hello").unwrap();
+        let temporary = uploaded.path().to_owned();
+
+        assert_eq!(Some("a.txt"), uploaded.file_name());
+        assert_eq!(5, uploaded.size().unwrap());
+        assert_eq!(b"hello".to_vec(), uploaded.read().unwrap());
+        drop(uploaded);
+        assert!(!temporary.exists());
+
+        let (uploaded, _) = UploadedFile::create(None, None).unwrap();
+        let temporary = uploaded.path().to_owned();
+        let target = std::env::temp_dir().join(format!("{}.persisted", multipart_boundary()));
+        uploaded.persist(&target).unwrap();
+        assert!(!temporary.exists());
+        assert!(target.exists());
+        fs::remove_file(target).unwrap();
+    }
+
+    #[test]
+    fn should_return_the_file_when_it_cannot_be_persisted() {
+        let (uploaded, _) = UploadedFile::create(Some("a.txt".to_owned()), None).unwrap();
+        let temporary = uploaded.path().to_owned();
+        let target = std::env::temp_dir().join(multipart_boundary()).join("missing").join("a.txt");
+
+        let error = uploaded.persist(&target).unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, error.error.kind());
+        assert_eq!(temporary, error.file.path());
+        assert!(temporary.exists());
+        assert!(!target.exists());
+
+        drop(error);
+        assert!(!temporary.exists());
+    }
+}
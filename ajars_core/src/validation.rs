@@ -0,0 +1,67 @@
+pub use validator::{Validate, ValidationErrors};
+
+/// The HTTP status code of the responses to the requests whose input fails the validation.
+pub const VALIDATION_ERROR_STATUS: u16 = 422;
+
+/// The function that validates the input of an endpoint.
+/// It runs on the client before sending the request and on the server before calling the handler.
+pub type InputValidator<I> = fn(&I) -> Result<(), ValidationErrors>;
+
+/// The validator of the endpoints that do not validate their input: it accepts every input.
+pub(crate) fn no_validation<I>(_input: &I) -> Result<(), ValidationErrors> {
+    Ok(())
+}
+
+/// The validator of the endpoints whose input implements [`Validate`].
+pub(crate) fn validate<I: Validate>(input: &I) -> Result<(), ValidationErrors> {
+    input.validate()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{ApiGroup, Rest, RestFluent, RestType, ScopedRest};
+
+    #[derive(Serialize, Deserialize, Validate)]
+    struct Signup {
+        #[validate(length(min = 3))]
+        username: String,
+    }
+
+    #[test]
+    fn should_accept_every_input_if_the_endpoint_does_not_validate_it() {
+        const SIGNUP: Rest<Signup, ()> = Rest::post("/signup");
+
+        assert!(SIGNUP.validate(&Signup { username: "".to_owned() }).is_ok());
+    }
+
+    #[test]
+    fn should_validate_the_input() {
+        const SIGNUP: Rest<Signup, ()> = Rest::post("/signup").with_validation();
+        let fluent = RestFluent::<Signup, ()>::post("/signup").with_validation();
+
+        assert_validates(&SIGNUP);
+        assert_validates(&SIGNUP.clone());
+        assert_validates(&fluent);
+        assert_validates(&fluent.clone());
+    }
+
+    fn assert_validates<REST: RestType<Signup, ()>>(rest: &REST) {
+        assert!(rest.validate(&Signup { username: "ufo".to_owned() }).is_ok());
+        let errors = rest.validate(&Signup { username: "u".to_owned() }).unwrap_err();
+        assert!(errors.field_errors().contains_key("username"));
+    }
+
+    #[test]
+    fn should_keep_the_validation_of_a_scoped_endpoint() {
+        const SIGNUP: Rest<Signup, ()> = Rest::post("/signup").with_validation();
+
+        let group = ApiGroup::new("/api").endpoint(&SIGNUP);
+        let scoped: ScopedRest<'_, _> = group.scope(&SIGNUP);
+
+        assert!(scoped.validate(&Signup { username: "u".to_owned() }).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use thiserror::Error as ThisError;
+
+use crate::{CodecError, Rest};
+
+/// A WebSocket endpoint, whose client sends messages of type `I` and whose server sends messages of type `O`,
+/// both encoded as JSON in text frames.
+///
+/// The connection is opened by a `GET` request to the path of the endpoint, whose placeholders are filled
+/// with the path parameters `P`. The handler of the endpoint receives a typed sink of the messages sent to the client
+/// and a typed stream of the messages received from it, and the clients get the mirror image.
+pub struct RestWebSocket<I, O, P = ()> {
+    rest: Rest<I, O, P>,
+}
+
+impl<I, O, P> Clone for RestWebSocket<I, O, P> {
+    fn clone(&self) -> Self {
+        Self { rest: self.rest.clone() }
+    }
+}
+
+impl<I, O, P> RestWebSocket<I, O, P> {
+    /// # Panics
+    ///
+    /// Panics if the path is not valid according to [`check_path`](crate::check_path);
+    /// for a `const` endpoint it fails the build.
+    pub const fn new(path: &'static str) -> Self {
+        Self { rest: Rest::get(path) }
+    }
+
+    /// The endpoint that opens the connection; its input and output types are the types of the messages.
+    pub fn rest(&self) -> &Rest<I, O, P> {
+        &self.rest
+    }
+}
+
+/// The error of a WebSocket connection.
+#[derive(Debug, ThisError)]
+pub enum WebSocketError {
+    /// A message could not be encoded, or a received message could not be decoded
+    #[error("Codec error: {0}")]
+    Codec(#[from] CodecError),
+    /// The connection could not be opened or failed
+    #[error("Connection error: {0}")]
+    Connection(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl WebSocketError {
+    pub fn connection<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> Self {
+        Self::Connection(error.into())
+    }
+}
+
+/// Encodes a message as the JSON payload of a text frame.
+pub fn encode_ws_message<T: Serialize>(message: &T) -> Result<String, CodecError> {
+    serde_json::to_string(message).map_err(CodecError::new)
+}
+
+/// Decodes a message from the payload of a text or binary frame.
+pub fn decode_ws_message<T: DeserializeOwned>(payload: &[u8]) -> Result<T, CodecError> {
+    serde_json::from_slice(payload).map_err(CodecError::new)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{HttpMethod, RestType};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Chat {
+        text: String,
+    }
+
+    #[test]
+    fn should_describe_the_endpoint_of_the_connection() {
+        const CHAT: RestWebSocket<Chat, Chat, u64> = RestWebSocket::new("/rooms/{id}");
+
+        let rest = CHAT.clone();
+        assert_eq!(&HttpMethod::GET, rest.rest().method());
+        assert_eq!("/rooms/7", rest.rest().path_with(&7));
+    }
+
+    #[test]
+    fn should_encode_and_decode_a_message() {
+        let message = Chat { text: "hello".to_owned() };
+
+        let payload = encode_ws_message(&message).unwrap();
+
+        assert_eq!("{\"text\":\"hello\"}", payload);
+        assert_eq!(message, decode_ws_message(payload.as_bytes()).unwrap());
+        assert!(decode_ws_message::<Chat>(b"hello").is_err());
+    }
+}
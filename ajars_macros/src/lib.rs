@@ -0,0 +1,506 @@
+//! The procedural macros of AjaRS. They are re-exported by the `ajars` crate, that the generated code refers to.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::{
+    Error, FnArg, GenericArgument, Ident, ItemTrait, LitInt, LitStr, Pat, PathArguments, ReturnType, Token, TraitItem,
+    TraitItemFn, Type, Visibility, parse_macro_input, parse_quote,
+};
+
+const HTTP_METHODS: [&str; 7] = ["delete", "get", "head", "options", "patch", "post", "put"];
+
+/// Declares the endpoints of a service as the async methods of a trait.
+///
+/// Every method is annotated with its HTTP method and path, e.g. `#[post("/users")]`, optionally followed by
+/// `error_status = 409`, `success_status = 201`, `input = Form` and `codec = Cbor`. The method takes `&self`, at most one argument marked
+/// with `#[path]` for the path parameters and at most one argument for the input; a missing input is `()`.
+/// A return type written as `Result<O, E>` declares `E` as the typed error of the endpoint.
+///
+/// Next to the trait, whose methods become `Send` futures, the macro generates a module named after the trait
+/// in snake case with:
+/// - a `Rest` const per method, named after the method in upper snake case;
+/// - `group()`, the `ApiGroup` of all the endpoints;
+/// - `ReqwestClient`, `SurfClient` and `WebClient`, typed clients with a method per endpoint;
+/// - `axum_router` and `actix_web_services`, that mount all the endpoints on an implementation of the trait.
+///
+/// The clients and the routers are generated only for the backends enabled in the `ajars` crate.
+#[proc_macro_attribute]
+pub fn service(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return Error::new(TokenStream2::from(attr).span(), "The service attribute takes no arguments")
+            .to_compile_error()
+            .into();
+    }
+    let item = parse_macro_input!(item as ItemTrait);
+    expand(item).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+struct Endpoint {
+    name: Ident,
+    const_name: Ident,
+    method: Ident,
+    path: LitStr,
+    error_status: Option<LitInt>,
+    success_status: Option<LitInt>,
+    input_location: Option<Ident>,
+    codec: Option<Type>,
+    path_params: Option<(Ident, Type)>,
+    input: Option<(Ident, Type)>,
+    output: Type,
+    error: Option<Type>,
+}
+
+impl Endpoint {
+    fn input_type(&self) -> Type {
+        self.input.as_ref().map(|(_, ty)| ty.clone()).unwrap_or_else(|| parse_quote!(()))
+    }
+
+    fn path_params_type(&self) -> Type {
+        self.path_params.as_ref().map(|(_, ty)| ty.clone()).unwrap_or_else(|| parse_quote!(()))
+    }
+
+    fn error_type(&self) -> Type {
+        self.error.clone().unwrap_or_else(|| parse_quote!(::ajars::NoError))
+    }
+}
+
+fn expand(mut item: ItemTrait) -> syn::Result<TokenStream2> {
+    if !item.generics.params.is_empty() || item.generics.where_clause.is_some() {
+        return Err(Error::new_spanned(&item.generics, "A service trait cannot be generic"));
+    }
+
+    let mut endpoints = Vec::new();
+    for trait_item in &mut item.items {
+        match trait_item {
+            TraitItem::Fn(function) => endpoints.push(parse_endpoint(function)?),
+            other => return Err(Error::new_spanned(other, "A service trait can only contain endpoint methods")),
+        }
+    }
+
+    let module = module(&item.ident, &item.vis, &endpoints);
+    Ok(quote! {
+        #item
+        #module
+    })
+}
+
+/// Reads the endpoint declared by a method and rewrites the method into one that returns a `Send` future.
+fn parse_endpoint(function: &mut TraitItemFn) -> syn::Result<Endpoint> {
+    if function.default.is_some() {
+        return Err(Error::new_spanned(&function.sig, "An endpoint method cannot have a default implementation"));
+    }
+    if function.sig.asyncness.is_none() {
+        return Err(Error::new_spanned(&function.sig, "An endpoint method must be async"));
+    }
+    if !function.sig.generics.params.is_empty() {
+        return Err(Error::new_spanned(&function.sig.generics, "An endpoint method cannot be generic"));
+    }
+
+    let position = function
+        .attrs
+        .iter()
+        .position(|attr| HTTP_METHODS.iter().any(|method| attr.path().is_ident(method)))
+        .ok_or_else(|| {
+            Error::new_spanned(
+                &function.sig,
+                "An endpoint method must be annotated with its HTTP method and path, e.g. #[get(\"/users\")]",
+            )
+        })?;
+    let attr = function.attrs.remove(position);
+    let method = attr.path().get_ident().cloned().expect("The HTTP method should be an identifier");
+
+    let mut error_status = None;
+    let mut success_status = None;
+    let mut input_location = None;
+    let mut codec = None;
+    let path = attr.parse_args_with(|input: ParseStream| {
+        let path: LitStr = input.parse()?;
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "error_status" => error_status = Some(input.parse::<LitInt>()?),
+                "success_status" => success_status = Some(input.parse::<LitInt>()?),
+                "input" => {
+                    let location: Ident = input.parse()?;
+                    if !["Query", "Body", "Form"].contains(&location.to_string().as_str()) {
+                        return Err(Error::new_spanned(location, "The input must be one of Query, Body or Form"));
+                    }
+                    input_location = Some(location);
+                }
+                "codec" => codec = Some(input.parse::<Type>()?),
+                _ => {
+                    return Err(Error::new_spanned(
+                        key,
+                        "Expected one of error_status, success_status, input or codec",
+                    ));
+                }
+            }
+        }
+        Ok(path)
+    })?;
+
+    let mut inputs = function.sig.inputs.iter_mut();
+    match inputs.next() {
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+        _ => return Err(Error::new_spanned(&function.sig, "An endpoint method must take &self")),
+    }
+
+    let mut path_params = None;
+    let mut input = None;
+    for arg in inputs {
+        let FnArg::Typed(arg) = arg else { unreachable!("The receiver can only be the first argument") };
+        let Pat::Ident(name) = arg.pat.as_ref() else {
+            return Err(Error::new_spanned(&arg.pat, "The arguments of an endpoint method must be identifiers"));
+        };
+        let name = name.ident.clone();
+        let is_path = arg.attrs.iter().any(|attr| attr.path().is_ident("path"));
+        arg.attrs.retain(|attr| !attr.path().is_ident("path"));
+
+        let target = if is_path { &mut path_params } else { &mut input };
+        if target.is_some() {
+            let message = if is_path {
+                "An endpoint method can have only one #[path] argument"
+            } else {
+                "An endpoint method can have only one input argument"
+            };
+            return Err(Error::new_spanned(arg, message));
+        }
+        *target = Some((name, arg.ty.as_ref().clone()));
+    }
+
+    let return_type: Type = match &function.sig.output {
+        ReturnType::Default => parse_quote!(()),
+        ReturnType::Type(_, ty) => ty.as_ref().clone(),
+    };
+    let (output, error) = match split_result(&return_type) {
+        Some((output, error)) => (output, Some(error)),
+        None => (return_type.clone(), None),
+    };
+
+    function.sig.asyncness = None;
+    function.sig.output = parse_quote!(-> impl ::core::future::Future<Output = #return_type> + ::core::marker::Send);
+
+    let name = function.sig.ident.clone();
+    Ok(Endpoint {
+        const_name: format_ident!("{}", to_upper_snake_case(&name.to_string())),
+        name,
+        method,
+        path,
+        error_status,
+        success_status,
+        input_location,
+        codec,
+        path_params,
+        input,
+        output,
+        error,
+    })
+}
+
+/// Returns the output and the error types of a `Result<O, E>`.
+fn split_result(ty: &Type) -> Option<(Type, Type)> {
+    let Type::Path(path) = ty else { return None };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else { return None };
+    let mut types = arguments.args.iter().filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    });
+    match (types.next(), types.next(), types.next()) {
+        (Some(output), Some(error), None) => Some((output, error)),
+        _ => None,
+    }
+}
+
+fn module(trait_name: &Ident, vis: &Visibility, endpoints: &[Endpoint]) -> TokenStream2 {
+    let module_name = format_ident!("{}", to_snake_case(&trait_name.to_string()));
+    let doc = format!("The endpoints, clients and routers of the [`{}`] service.", trait_name);
+
+    let consts = endpoints.iter().map(|endpoint| {
+        let Endpoint { const_name, method, path, .. } = endpoint;
+        let input = endpoint.input_type();
+        let output = &endpoint.output;
+        let path_params = endpoint.path_params_type();
+        let error = endpoint.error_type();
+        let codec = endpoint.codec.clone().unwrap_or_else(|| parse_quote!(::ajars::Json));
+        let error_status = endpoint.error_status.iter();
+        let success_status = endpoint.success_status.iter();
+        let input_location = endpoint.input_location.iter();
+        let doc = format!("The `{} {}` endpoint of [`{}::{}`].", method, path.value(), trait_name, endpoint.name);
+        quote! {
+            #[doc = #doc]
+            pub const #const_name: ::ajars::Rest<#input, #output, #path_params, #error, #codec> =
+                ::ajars::Rest::#method(#path)
+                    #( .with_error_status(#error_status) )*
+                    #( .with_success_status(#success_status) )*
+                    #( .with_input_location(::ajars::InputLocation::#input_location) )*;
+        }
+    });
+
+    let const_names = endpoints.iter().map(|endpoint| &endpoint.const_name);
+
+    let mut backends = TokenStream2::new();
+    if cfg!(feature = "reqwest") {
+        let error: Type = parse_quote!(::ajars::reqwest::reqwest::Error);
+        backends.extend(client(
+            &format_ident!("ReqwestClient"),
+            &parse_quote!(::ajars::reqwest::AjarsClientReqwest),
+            &error,
+            true,
+            endpoints,
+        ));
+    }
+    if cfg!(feature = "surf") {
+        let error: Type = parse_quote!(::ajars::surf::surf::Error);
+        backends.extend(client(
+            &format_ident!("SurfClient"),
+            &parse_quote!(::ajars::surf::AjarsClientSurf),
+            &error,
+            true,
+            endpoints,
+        ));
+    }
+    if cfg!(feature = "web") {
+        let error: Type = parse_quote!(::ajars::web::error::Error);
+        backends.extend(client(
+            &format_ident!("WebClient"),
+            &parse_quote!(::ajars::web::AjarsClientWeb),
+            &error,
+            false,
+            endpoints,
+        ));
+    }
+    if cfg!(feature = "axum") {
+        backends.extend(axum_router(trait_name, endpoints));
+    }
+    if cfg!(feature = "actix_web") {
+        backends.extend(actix_web_services(trait_name, endpoints));
+    }
+
+    quote! {
+        #[doc = #doc]
+        #[allow(dead_code)]
+        #vis mod #module_name {
+            use super::*;
+
+            #( #consts )*
+
+            /// The group of all the endpoints of the service, without a prefix.
+            pub fn group() -> ::ajars::ApiGroup {
+                ::ajars::ApiGroup::new("") #( .endpoint(&#const_names) )*
+            }
+
+            #backends
+        }
+    }
+}
+
+/// Generates a typed client that wraps the client of a backend.
+fn client(name: &Ident, inner: &Type, client_error: &Type, clone: bool, endpoints: &[Endpoint]) -> TokenStream2 {
+    let methods = endpoints.iter().map(|endpoint| {
+        let Endpoint { name, const_name, output, .. } = endpoint;
+        let error = endpoint.error_type();
+        let path_arg = endpoint.path_params.iter().map(|(name, ty)| quote!(#name: &#ty));
+        let input_arg = endpoint.input.iter().map(|(name, ty)| quote!(#name: &#ty));
+        let request = match &endpoint.path_params {
+            Some((path, _)) => quote!(self.client.request_with_path(&#const_name, #path)),
+            None => quote!(self.client.request(&#const_name)),
+        };
+        let input = match &endpoint.input {
+            Some((input, _)) => quote!(#input),
+            None => quote!(&()),
+        };
+        quote! {
+            pub async fn #name(&self, #( #path_arg, )* #( #input_arg )*)
+                -> ::core::result::Result<#output, ::ajars::AjarsError<#error, #client_error>>
+            {
+                #request.send(#input).await
+            }
+        }
+    });
+    let derive = clone.then(|| quote!(#[derive(Clone)]));
+
+    quote! {
+        #derive
+        pub struct #name {
+            client: #inner,
+        }
+
+        impl #name {
+            pub fn new(client: #inner) -> Self {
+                Self { client }
+            }
+
+            #( #methods )*
+        }
+    }
+}
+
+/// Generates the handler closure of an endpoint, that calls the method of the service.
+fn handler(endpoint: &Endpoint, path_first: bool) -> TokenStream2 {
+    let name = &endpoint.name;
+    let input_type = endpoint.input_type();
+    let input = match endpoint.input {
+        Some(_) => quote!(input: #input_type),
+        None => quote!(_: #input_type),
+    };
+    let path_params = endpoint.path_params.as_ref().map(|(_, ty)| quote!(path: #ty));
+    let args = match (path_params, path_first) {
+        (Some(path), true) => quote!(#path, #input),
+        (Some(path), false) => quote!(#input, #path),
+        (None, _) => input,
+    };
+    let call_args = match (&endpoint.path_params, &endpoint.input) {
+        (Some(_), Some(_)) => quote!(path, input),
+        (Some(_), None) => quote!(path),
+        (None, Some(_)) => quote!(input),
+        (None, None) => quote!(),
+    };
+    let call = quote!(service.#name(#call_args).await);
+    let result = if endpoint.error.is_some() {
+        call
+    } else {
+        quote!(::core::result::Result::<_, ::core::convert::Infallible>::Ok(#call))
+    };
+    quote! {
+        {
+            let service = service.clone();
+            move |#args| {
+                let service = service.clone();
+                async move { #result }
+            }
+        }
+    }
+}
+
+fn axum_router(trait_name: &Ident, endpoints: &[Endpoint]) -> TokenStream2 {
+    let routes = endpoints.iter().map(|endpoint| {
+        let const_name = &endpoint.const_name;
+        let handler = handler(endpoint, true);
+        quote!(.route(&#const_name, #handler))
+    });
+    quote! {
+        /// Returns an axum router with all the endpoints of the service.
+        pub fn axum_router<T, S>(service: ::std::sync::Arc<T>) -> ::ajars::axum::axum::Router<S>
+        where
+            T: #trait_name + ::core::marker::Send + ::core::marker::Sync + 'static,
+            S: ::core::clone::Clone + ::core::marker::Send + ::core::marker::Sync + 'static,
+        {
+            let group = group();
+            ::ajars::axum::AjarsAxumGroupRouter::new(&group) #( #routes )* .build()
+        }
+    }
+}
+
+fn actix_web_services(trait_name: &Ident, endpoints: &[Endpoint]) -> TokenStream2 {
+    let routes = endpoints.iter().map(|endpoint| {
+        let const_name = &endpoint.const_name;
+        let handler = handler(endpoint, false);
+        quote!(.route(&#const_name, #handler))
+    });
+    quote! {
+        /// Returns the actix-web resources of all the endpoints of the service, to be registered with `App::service`.
+        pub fn actix_web_services<T>(service: ::std::sync::Arc<T>) -> ::std::vec::Vec<::ajars::actix_web::actix_web::Resource>
+        where
+            T: #trait_name + 'static,
+        {
+            let group = group();
+            ::ajars::actix_web::AjarsActixWebGroup::new(&group) #( #routes )* .build()
+        }
+    }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    let mut previous: Option<char> = None;
+    let mut chars = name.chars().peekable();
+    while let Some(current) = chars.next() {
+        if current.is_uppercase() {
+            let next_is_lowercase = chars.peek().is_some_and(|next| next.is_lowercase());
+            let boundary = match previous {
+                Some(previous) => {
+                    previous.is_lowercase()
+                        || previous.is_ascii_digit()
+                        || (previous.is_uppercase() && next_is_lowercase)
+                }
+                None => false,
+            };
+            if boundary {
+                snake.push('_');
+            }
+            snake.extend(current.to_lowercase());
+        } else {
+            snake.push(current);
+        }
+        previous = Some(current);
+    }
+    snake
+}
+
+fn to_upper_snake_case(name: &str) -> String {
+    to_snake_case(name).to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_convert_to_snake_case() {
+        assert_eq!("user_api", to_snake_case("UserApi"));
+        assert_eq!("http_api", to_snake_case("HTTPApi"));
+        assert_eq!("api2_users", to_snake_case("Api2Users"));
+        assert_eq!("users", to_snake_case("users"));
+        assert_eq!("GET_USER", to_upper_snake_case("get_user"));
+    }
+
+    #[test]
+    fn should_split_a_result_type() {
+        let (output, error) = split_result(&parse_quote!(Result<User, UserError>)).unwrap();
+        assert_eq!(quote!(User).to_string(), quote!(#output).to_string());
+        assert_eq!(quote!(UserError).to_string(), quote!(#error).to_string());
+        assert!(split_result(&parse_quote!(User)).is_none());
+        assert!(split_result(&parse_quote!(Option<User>)).is_none());
+    }
+
+    #[test]
+    fn should_rewrite_the_endpoint_methods() {
+        let mut function: TraitItemFn = parse_quote! {
+            #[get("/users/{id}", error_status = 404, success_status = 203, input = Query)]
+            async fn get_user(&self, #[path] id: u64, request: UserRequest) -> Result<User, NotFound>;
+        };
+
+        let endpoint = parse_endpoint(&mut function).unwrap();
+
+        assert_eq!("GET_USER", endpoint.const_name.to_string());
+        assert_eq!("get", endpoint.method.to_string());
+        assert_eq!("/users/{id}", endpoint.path.value());
+        assert_eq!("404", endpoint.error_status.unwrap().to_string());
+        assert_eq!("203", endpoint.success_status.unwrap().to_string());
+        assert_eq!("id", endpoint.path_params.unwrap().0.to_string());
+        assert_eq!("request", endpoint.input.unwrap().0.to_string());
+        assert!(function.attrs.is_empty());
+        assert!(function.sig.asyncness.is_none());
+    }
+
+    #[test]
+    fn should_reject_a_method_without_http_method() {
+        let mut function: TraitItemFn = parse_quote! {
+            async fn get_user(&self, request: UserRequest) -> User;
+        };
+
+        assert!(parse_endpoint(&mut function).is_err());
+    }
+}
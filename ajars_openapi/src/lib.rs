@@ -0,0 +1,390 @@
+use std::collections::BTreeMap;
+
+use ajars_core::{Codec, DEFAULT_SUCCESS_STATUS, HttpMethod, InputLocation, NO_CONTENT_STATUS, RestType};
+use schemars::generate::SchemaSettings;
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+
+pub mod schemars {
+    pub use ::schemars::*;
+}
+
+/// The OpenAPI version of the generated documents
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+const DEFINITIONS_PATH: &str = "/components/schemas";
+const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// Builds an OpenAPI 3.1 document from a set of endpoints.
+///
+/// The schemas of the input, output, path parameters and error types are generated with `schemars`
+/// and the shared ones are placed in `components/schemas`.
+/// The input is documented as query parameters or as request body according to the input location of the endpoint.
+pub struct OpenApi {
+    title: String,
+    version: String,
+    description: Option<String>,
+    servers: Vec<String>,
+    paths: BTreeMap<String, Map<String, Value>>,
+    generator: SchemaGenerator,
+}
+
+impl OpenApi {
+    pub fn new<T: Into<String>, V: Into<String>>(title: T, version: V) -> Self {
+        let settings = SchemaSettings::draft2020_12().with(|settings| {
+            settings.definitions_path = DEFINITIONS_PATH.into();
+            settings.meta_schema = None;
+        });
+
+        Self {
+            title: title.into(),
+            version: version.into(),
+            description: None,
+            servers: Vec::new(),
+            paths: BTreeMap::new(),
+            generator: settings.into_generator(),
+        }
+    }
+
+    /// Sets the description of the API
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Adds the URL of a server that exposes the API
+    pub fn with_server<S: Into<String>>(mut self, url: S) -> Self {
+        self.servers.push(url.into());
+        self
+    }
+
+    /// Adds an endpoint to the document.
+    /// An endpoint with the same method and path of a previous one replaces it.
+    pub fn endpoint<I, O, REST>(mut self, rest: &REST) -> Self
+    where
+        I: Serialize + DeserializeOwned + JsonSchema,
+        O: Serialize + DeserializeOwned + JsonSchema,
+        REST: RestType<I, O>,
+        REST::PathParams: JsonSchema,
+        REST::Error: JsonSchema,
+    {
+        let content_type = REST::Codec::CONTENT_TYPE;
+        let mut operation = Map::new();
+        operation.insert("operationId".to_owned(), json!(operation_id(rest.method(), rest.path())));
+
+        let mut parameters = self.path_parameters::<REST::PathParams>(rest.path());
+
+        let input = self.generator.subschema_for::<I>();
+        match rest.input_location() {
+            InputLocation::Query => parameters.extend(self.query_parameters(&input)),
+            InputLocation::Body => {
+                if !is_null(&input) {
+                    operation.insert("requestBody".to_owned(), request_body(content_type, input));
+                }
+            }
+            InputLocation::Form => {
+                if !is_null(&input) {
+                    operation.insert("requestBody".to_owned(), request_body(FORM_CONTENT_TYPE, input));
+                }
+            }
+        }
+
+        if !parameters.is_empty() {
+            operation.insert("parameters".to_owned(), Value::Array(parameters));
+        }
+
+        let mut responses = Map::new();
+        let output = self.generator.subschema_for::<O>();
+        if is_null(&output) && rest.success_status() == DEFAULT_SUCCESS_STATUS {
+            // An empty output, like `()`, of an endpoint with the default success status is sent as '204 No Content'
+            responses.insert(NO_CONTENT_STATUS.to_string(), json!({ "description": "No Content" }));
+        } else {
+            let mut success = json!({ "description": "Success" });
+            if !is_null(&output) && !matches!(rest.method(), HttpMethod::HEAD) {
+                success["content"] = json!({ content_type: { "schema": output } });
+            }
+            responses.insert(rest.success_status().to_string(), success);
+        }
+
+        let error = self.generator.subschema_for::<REST::Error>();
+        if error.as_bool() != Some(false) {
+            responses.insert(
+                rest.error_status().to_string(),
+                json!({ "description": "Error", "content": { content_type: { "schema": error } } }),
+            );
+        }
+        operation.insert("responses".to_owned(), Value::Object(responses));
+
+        self.paths
+            .entry(rest.path().to_owned())
+            .or_default()
+            .insert(method_name(rest.method()).to_owned(), Value::Object(operation));
+        self
+    }
+
+    /// Returns the document as a JSON value
+    pub fn to_value(&self) -> Value {
+        let mut info = json!({ "title": self.title, "version": self.version });
+        if let Some(description) = &self.description {
+            info["description"] = json!(description);
+        }
+
+        let mut document = json!({
+            "openapi": OPENAPI_VERSION,
+            "info": info,
+            "paths": self.paths,
+        });
+
+        if !self.servers.is_empty() {
+            document["servers"] = self.servers.iter().map(|url| json!({ "url": url })).collect();
+        }
+
+        let schemas = self.generator.definitions();
+        if !schemas.is_empty() {
+            document["components"] = json!({ "schemas": schemas });
+        }
+
+        document
+    }
+
+    /// Returns the document serialized as pretty printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_value()).expect("A JSON value should always be serializable")
+    }
+
+    /// Returns the document serialized as YAML
+    pub fn to_yaml(&self) -> String {
+        serde_yaml_ng::to_string(&self.to_value()).expect("A JSON value should always be serializable as YAML")
+    }
+
+    /// Documents the `{name}` placeholders of the path. The schema of each placeholder is taken from the field
+    /// with the same name, from the item at the same position or, for a single placeholder, from the whole type.
+    fn path_parameters<P: JsonSchema>(&mut self, path: &str) -> Vec<Value> {
+        let names = placeholders(path);
+        if names.is_empty() {
+            return Vec::new();
+        }
+
+        let schema = self.generator.subschema_for::<P>();
+        let schema = self.resolve(&schema);
+
+        names
+            .iter()
+            .enumerate()
+            .map(|(position, name)| {
+                let schema = schema
+                    .get("properties")
+                    .and_then(|properties| properties.get(*name))
+                    .or_else(|| schema.get("prefixItems").and_then(|items| items.get(position)))
+                    .cloned()
+                    .or_else(|| (names.len() == 1).then(|| schema.clone()))
+                    .unwrap_or_else(|| json!({ "type": "string" }));
+                json!({ "name": name, "in": "path", "required": true, "schema": schema })
+            })
+            .collect()
+    }
+
+    /// Documents every field of the input as a query parameter
+    fn query_parameters(&self, input: &Schema) -> Vec<Value> {
+        let input = self.resolve(input);
+        let required = input.get("required").and_then(Value::as_array);
+
+        input
+            .get("properties")
+            .and_then(Value::as_object)
+            .map(|properties| {
+                properties
+                    .iter()
+                    .map(|(name, schema)| {
+                        let required = required.is_some_and(|required| required.contains(&json!(name)));
+                        json!({ "name": name, "in": "query", "required": required, "schema": schema })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Replaces a reference to `components/schemas` with the referenced schema
+    fn resolve(&self, schema: &Schema) -> Value {
+        schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix(&format!("#{}/", DEFINITIONS_PATH)))
+            .and_then(|name| self.generator.definitions().get(name))
+            .cloned()
+            .unwrap_or_else(|| schema.as_value().clone())
+    }
+}
+
+fn request_body(content_type: &str, schema: Schema) -> Value {
+    json!({ "required": true, "content": { content_type: { "schema": schema } } })
+}
+
+fn is_null(schema: &Schema) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+fn method_name(method: &HttpMethod) -> &'static str {
+    match method {
+        HttpMethod::DELETE => "delete",
+        HttpMethod::GET => "get",
+        HttpMethod::HEAD => "head",
+        HttpMethod::OPTIONS => "options",
+        HttpMethod::PATCH => "patch",
+        HttpMethod::POST => "post",
+        HttpMethod::PUT => "put",
+    }
+}
+
+/// Builds an operation id from the method and the path, e.g. `get_api_users_id` for `GET /api/users/{id}`
+fn operation_id(method: &HttpMethod, path: &str) -> String {
+    let mut id = method_name(method).to_owned();
+    for word in path.split(|c: char| !c.is_ascii_alphanumeric()).filter(|word| !word.is_empty()) {
+        id.push('_');
+        id.push_str(word);
+    }
+    id
+}
+
+fn placeholders(path: &str) -> Vec<&str> {
+    path.split('{').skip(1).filter_map(|segment| segment.split_once('}').map(|(name, _)| name)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use ::schemars::JsonSchema;
+    use ajars_core::{InputLocation, NoError, Rest};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Value, json};
+
+    use super::OpenApi;
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct UserRequest {
+        pub verbose: bool,
+        pub fields: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct User {
+        pub id: u64,
+        pub name: String,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct UserPath {
+        pub id: u64,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct UserError {
+        pub reason: String,
+    }
+
+    const GET_USER: Rest<UserRequest, User, UserPath> = Rest::get("/api/users/{id}");
+    const CREATE_USER: Rest<User, User, (), UserError> =
+        Rest::post("/api/users").with_error_status(409).with_success_status(201);
+    const DELETE_USER: Rest<User, (), (u64,)> =
+        Rest::delete("/api/users/{id}").with_input_location(InputLocation::Form);
+
+    #[test]
+    fn should_generate_the_document_info() {
+        let document =
+            OpenApi::new("Users", "1.0.0").with_description("The users API").with_server("https://example.com");
+
+        let value = document.to_value();
+
+        assert_eq!("3.1.0", value["openapi"]);
+        assert_eq!("Users", value["info"]["title"]);
+        assert_eq!("1.0.0", value["info"]["version"]);
+        assert_eq!("The users API", value["info"]["description"]);
+        assert_eq!("https://example.com", value["servers"][0]["url"]);
+    }
+
+    #[test]
+    fn should_document_the_query_input_and_the_path_parameters() {
+        let value = OpenApi::new("Users", "1.0.0").endpoint(&GET_USER).to_value();
+
+        let operation = &value["paths"]["/api/users/{id}"]["get"];
+        assert_eq!("get_api_users_id", operation["operationId"]);
+        assert!(operation.get("requestBody").is_none());
+
+        let parameters = operation["parameters"].as_array().unwrap();
+        assert_eq!(3, parameters.len());
+        assert_eq!(
+            json!({ "name": "id", "in": "path", "required": true, "schema": { "type": "integer", "format": "uint64", "minimum": 0 } }),
+            parameters[0]
+        );
+        assert_eq!(
+            json!({ "name": "fields", "in": "query", "required": false, "schema": { "type": ["string", "null"] } }),
+            parameters[1]
+        );
+        assert_eq!(
+            json!({ "name": "verbose", "in": "query", "required": true, "schema": { "type": "boolean" } }),
+            parameters[2]
+        );
+
+        assert_eq!(
+            "#/components/schemas/User",
+            operation["responses"]["200"]["content"]["application/json"]["schema"]["$ref"]
+        );
+        assert_eq!("object", value["components"]["schemas"]["User"]["type"]);
+    }
+
+    #[test]
+    fn should_document_the_body_input_the_success_status_and_the_typed_error() {
+        let value = OpenApi::new("Users", "1.0.0").endpoint(&CREATE_USER).to_value();
+
+        let operation = &value["paths"]["/api/users"]["post"];
+        assert!(operation.get("parameters").is_none());
+        assert_eq!(
+            "#/components/schemas/User",
+            operation["requestBody"]["content"]["application/json"]["schema"]["$ref"]
+        );
+        assert_eq!(
+            "#/components/schemas/User",
+            operation["responses"]["201"]["content"]["application/json"]["schema"]["$ref"]
+        );
+        assert!(operation["responses"].get("200").is_none());
+        assert_eq!(
+            "#/components/schemas/UserError",
+            operation["responses"]["409"]["content"]["application/json"]["schema"]["$ref"]
+        );
+    }
+
+    #[test]
+    fn should_document_the_form_input_and_the_positional_path_parameters() {
+        let value = OpenApi::new("Users", "1.0.0").endpoint(&DELETE_USER).to_value();
+
+        let operation = &value["paths"]["/api/users/{id}"]["delete"];
+        assert_eq!("id", operation["parameters"][0]["name"]);
+        assert_eq!("integer", operation["parameters"][0]["schema"]["type"]);
+        assert!(operation["requestBody"]["content"].get("application/x-www-form-urlencoded").is_some());
+        assert_eq!("No Content", operation["responses"]["204"]["description"]);
+        assert_eq!(1, operation["responses"].as_object().unwrap().len());
+    }
+
+    #[test]
+    fn should_not_document_an_error_for_endpoints_without_a_typed_error() {
+        let rest = Rest::<User, User, (), NoError>::put("/api/users");
+
+        let value = OpenApi::new("Users", "1.0.0").endpoint(&rest).to_value();
+
+        assert_eq!(1, value["paths"]["/api/users"]["put"]["responses"].as_object().unwrap().len());
+    }
+
+    #[test]
+    fn should_serialize_as_json_and_yaml() {
+        let document = OpenApi::new("Users", "1.0.0").endpoint(&GET_USER).endpoint(&CREATE_USER);
+
+        let from_json: Value = serde_json::from_str(&document.to_json()).unwrap();
+        let from_yaml: Value = serde_yaml_ng::from_str(&document.to_yaml()).unwrap();
+
+        assert_eq!(document.to_value(), from_json);
+        assert_eq!(document.to_value(), from_yaml);
+        assert!(document.to_yaml().contains("openapi: 3.1.0\n"));
+    }
+}
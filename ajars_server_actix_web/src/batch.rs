@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use ::actix_web::http::StatusCode;
+use ::actix_web::{HttpRequest, HttpResponse, Resource, web};
+use ajars_core::{BatchCall, BatchContext, BatchHandlers, Json, RestBatch, RestType, TOO_MANY_CALLS_STATUS};
+
+use crate::{Encoded, encoded_response};
+
+pub trait AjarsServerActixWebBatchHandler {
+    /// Mounts the batch endpoint, that dispatches its calls to the given handlers
+    /// along with the headers of the batch request.
+    /// Only the middleware of the batch resource runs, not the one of the resources of the endpoints called.
+    fn to(&self, handlers: BatchHandlers) -> Resource;
+}
+
+impl AjarsServerActixWebBatchHandler for RestBatch {
+    fn to(&self, handlers: BatchHandlers) -> Resource {
+        let handlers = Arc::new(handlers);
+        web::resource::<&str>(self.rest().path()).route(web::post().to(
+            move |request: HttpRequest, calls: Encoded<Json, Vec<BatchCall>>| {
+                let handlers = handlers.clone();
+                async move {
+                    match handlers.dispatch(batch_context(&request), calls.into_inner()).await {
+                        Ok(results) => encoded_response::<Json, _>(StatusCode::OK, &results),
+                        Err(error) => {
+                            let status =
+                                StatusCode::from_u16(TOO_MANY_CALLS_STATUS).expect("The status should be valid");
+                            HttpResponse::build(status).body(error.to_string())
+                        }
+                    }
+                }
+            },
+        ))
+    }
+}
+
+/// The context of the calls of a batch: the headers of its request whose values are valid strings.
+fn batch_context(request: &HttpRequest) -> BatchContext {
+    BatchContext::new(
+        request
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| value.to_str().ok().map(|value| (name.to_string(), value.to_owned())))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use ::actix_web::dev::Service;
+    use ::actix_web::{App, test};
+    use ajars_core::{BatchResult, NoError, Rest};
+
+    use super::*;
+
+    const BATCH: RestBatch = RestBatch::post("/api/batch");
+    const DOUBLE: Rest<u64, u64> = Rest::get("/api/double");
+
+    #[actix_rt::test]
+    async fn should_answer_every_call_of_the_batch() {
+        // Arrange
+        let handlers = BatchHandlers::new().endpoint(&DOUBLE, |input: u64| async move { Ok::<_, NoError>(input * 2) });
+        let app = test::init_service(App::new().service(BATCH.to(handlers))).await;
+        let calls = vec![
+            BatchCall { endpoint: "GET /api/double".to_owned(), path_params: ().into(), input: 21.into() },
+            BatchCall { endpoint: "GET /api/unknown".to_owned(), path_params: ().into(), input: ().into() },
+        ];
+
+        // Act
+        let resp = app.call(test::TestRequest::post().uri("/api/batch").set_json(calls).to_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let results: Vec<BatchResult> = test::read_body_json(resp).await;
+        assert_eq!(2, results.len());
+        assert_eq!(BatchResult { status: 200, body: 42.into() }, results[0]);
+        assert_eq!(404, results[1].status);
+    }
+
+    #[actix_rt::test]
+    async fn should_pass_the_headers_of_the_batch_request_to_the_handlers() {
+        // Arrange
+        const WHOAMI: Rest<(), String> = Rest::get("/api/whoami");
+        let handlers =
+            BatchHandlers::new().endpoint_with_context(&WHOAMI, |context: BatchContext, _: (), _: ()| async move {
+                Ok::<_, NoError>(context.header("Authorization").unwrap_or_default().to_owned())
+            });
+        let app = test::init_service(App::new().service(BATCH.to(handlers))).await;
+        let calls =
+            vec![BatchCall { endpoint: "GET /api/whoami".to_owned(), path_params: ().into(), input: ().into() }];
+
+        // Act
+        let request = test::TestRequest::post()
+            .uri("/api/batch")
+            .insert_header(("Authorization", "Bearer token"))
+            .set_json(calls)
+            .to_request();
+        let resp = app.call(request).await.unwrap();
+
+        // Assert
+        let results: Vec<BatchResult> = test::read_body_json(resp).await;
+        assert_eq!(vec![BatchResult { status: 200, body: "Bearer token".into() }], results);
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_a_batch_with_too_many_calls() {
+        // Arrange
+        let handlers = BatchHandlers::new()
+            .endpoint(&DOUBLE, |input: u64| async move { Ok::<_, NoError>(input * 2) })
+            .with_max_calls(1);
+        let app = test::init_service(App::new().service(BATCH.to(handlers))).await;
+        let calls = vec![
+            BatchCall { endpoint: "GET /api/double".to_owned(), path_params: ().into(), input: 1.into() },
+            BatchCall { endpoint: "GET /api/double".to_owned(), path_params: ().into(), input: 2.into() },
+        ];
+
+        // Act
+        let resp = app.call(test::TestRequest::post().uri("/api/batch").set_json(calls).to_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, resp.status());
+    }
+}
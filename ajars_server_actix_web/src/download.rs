@@ -0,0 +1,342 @@
+use std::future::Future;
+use std::io::SeekFrom;
+
+use ::actix_web::http::StatusCode;
+use ::actix_web::http::header;
+use ::actix_web::web::{self, Bytes, Path, Query};
+use ::actix_web::{FromRequest, HttpRequest, HttpResponse, HttpResponseBuilder, Resource};
+use ajars_core::{ContentRange, Download, DownloadBody, InputLocation, Json, RestDownload, RestType, resolve_range};
+use futures_util::{Stream, TryStreamExt, stream};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+use crate::{
+    Encoded, InputValidation, UrlEncoded, WithPathParams, WithoutPathParams, encoded_response, error_status, method,
+};
+
+/// The size of the chunks read from the files of the downloads.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+pub trait AjarsServerActixWebDownloadHandler<I: Serialize + DeserializeOwned, T, H> {
+    fn to(&self, handler: H) -> Resource;
+}
+
+/// Sends the content of a download, or the part of it asked by the `Range` header of the request.
+async fn download_response(req: &HttpRequest, download: Download) -> HttpResponse {
+    let range = req.headers().get(header::RANGE).and_then(|range| range.to_str().ok());
+    let content_type = download.content_type().to_owned();
+    let disposition = download.content_disposition().map(str::to_owned);
+    let response = move |status: StatusCode| {
+        let mut builder = HttpResponse::build(status);
+        builder.content_type(content_type);
+        if let Some(disposition) = disposition {
+            builder.insert_header((header::CONTENT_DISPOSITION, disposition));
+        }
+        builder
+    };
+
+    match download.into_body() {
+        DownloadBody::Bytes(bytes) => match resolve_range(range, bytes.len() as u64) {
+            Ok(None) => response(StatusCode::OK).insert_header((header::ACCEPT_RANGES, "bytes")).body(bytes),
+            Ok(Some(range)) => {
+                let part = bytes[range.start as usize..=range.end as usize].to_vec();
+                partial_response(response(StatusCode::PARTIAL_CONTENT), range).body(part)
+            }
+            Err(not_satisfiable) => HttpResponse::RangeNotSatisfiable()
+                .insert_header((header::CONTENT_RANGE, not_satisfiable.to_string()))
+                .finish(),
+        },
+        DownloadBody::File(path) => {
+            let result = async {
+                let mut file = tokio::fs::File::open(&path).await?;
+                let size = file.metadata().await?.len();
+                let range = match resolve_range(range, size) {
+                    Ok(range) => range,
+                    Err(not_satisfiable) => {
+                        return Ok(HttpResponse::RangeNotSatisfiable()
+                            .insert_header((header::CONTENT_RANGE, not_satisfiable.to_string()))
+                            .finish());
+                    }
+                };
+                let (start, len) = range.map_or((0, size), |range| (range.start, range.len()));
+                file.seek(SeekFrom::Start(start)).await?;
+                let mut builder = match range {
+                    Some(range) => partial_response(response(StatusCode::PARTIAL_CONTENT), range),
+                    None => {
+                        let mut builder = response(StatusCode::OK);
+                        builder.insert_header((header::ACCEPT_RANGES, "bytes"));
+                        builder
+                    }
+                };
+                Ok::<_, std::io::Error>(builder.no_chunking(len).streaming(read_chunks(file, len)))
+            };
+            result.await.unwrap_or_else(|err| {
+                HttpResponse::InternalServerError().body(format!("Failed to read the file of the download: {}", err))
+            })
+        }
+        // The size of a stream is unknown, so it is always sent as a whole
+        DownloadBody::Stream(chunks) => response(StatusCode::OK).streaming(chunks.map_ok(Bytes::from)),
+    }
+}
+
+/// Streams `len` bytes of the file, from its current position.
+fn read_chunks(file: tokio::fs::File, len: u64) -> impl Stream<Item = std::io::Result<Bytes>> + 'static {
+    stream::try_unfold((file.take(len), vec![0; CHUNK_SIZE]), |(mut file, mut buffer)| async move {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        let chunk = Bytes::copy_from_slice(&buffer[..read]);
+        Ok(Some((chunk, (file, buffer))))
+    })
+}
+
+fn partial_response(mut builder: HttpResponseBuilder, range: ContentRange) -> HttpResponseBuilder {
+    builder.insert_header((header::CONTENT_RANGE, range.to_string()));
+    builder
+}
+
+macro_rules! download_factory_tuple ({ $($param:ident)* } => {
+    #[allow(non_snake_case)]
+    impl <I: Serialize + DeserializeOwned + 'static, E: Serialize + DeserializeOwned + 'static, H, R, $($param,)*> AjarsServerActixWebDownloadHandler<I, (WithoutPathParams, ($($param,)*)), H>
+    for RestDownload<I, (), E>
+where
+H: Clone + 'static + Fn(I, $($param,)*) -> R,
+R: Future<Output = Result<Download, E>> + 'static,
+$( $param: FromRequest + 'static, )*
+{
+    fn to(&self, handler: H) -> Resource {
+        let rest = self.rest();
+        let resource = web::resource::<&str>(rest.path());
+        let route = web::route().method(method(rest.method()));
+        let validation = InputValidation::<Json, I>::of(rest);
+        let status = error_status(rest);
+
+        match rest.input_location() {
+            InputLocation::Query => resource.route(route.to(
+                move |json: Query<I>, req: HttpRequest, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let download = validation.check(&input).map(|()| (handler)(input, $($param,)*));
+                async move {
+                    Ok::<_, ::actix_web::Error>(match download?.await {
+                        Ok(download) => download_response(&req, download).await,
+                        Err(error) => encoded_response::<Json, _>(status, &error),
+                    })
+                }
+            })),
+            InputLocation::Body => resource.route(route.to(
+                move |json: Encoded<Json, I>, req: HttpRequest, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let download = validation.check(&input).map(|()| (handler)(input, $($param,)*));
+                async move {
+                    Ok::<_, ::actix_web::Error>(match download?.await {
+                        Ok(download) => download_response(&req, download).await,
+                        Err(error) => encoded_response::<Json, _>(status, &error),
+                    })
+                }
+            })),
+            InputLocation::Form => resource.route(route.to(
+                move |json: Encoded<UrlEncoded, I>, req: HttpRequest, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let download = validation.check(&input).map(|()| (handler)(input, $($param,)*));
+                async move {
+                    Ok::<_, ::actix_web::Error>(match download?.await {
+                        Ok(download) => download_response(&req, download).await,
+                        Err(error) => encoded_response::<Json, _>(status, &error),
+                    })
+                }
+            })),
+        }
+    }
+}
+
+    #[allow(non_snake_case)]
+    impl <I: Serialize + DeserializeOwned + 'static, P: Serialize + DeserializeOwned + 'static, E: Serialize + DeserializeOwned + 'static, H, R, $($param,)*> AjarsServerActixWebDownloadHandler<I, (WithPathParams, ($($param,)*)), H>
+    for RestDownload<I, P, E>
+where
+H: Clone + 'static + Fn(I, P, $($param,)*) -> R,
+R: Future<Output = Result<Download, E>> + 'static,
+$( $param: FromRequest + 'static, )*
+{
+    fn to(&self, handler: H) -> Resource {
+        let rest = self.rest();
+        let resource = web::resource::<&str>(rest.path());
+        let route = web::route().method(method(rest.method()));
+        let validation = InputValidation::<Json, I>::of(rest);
+        let status = error_status(rest);
+
+        match rest.input_location() {
+            InputLocation::Query => resource.route(route.to(
+                move |json: Query<I>, path: Path<P>, req: HttpRequest, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let download = validation.check(&input).map(|()| (handler)(input, path.into_inner(), $($param,)*));
+                async move {
+                    Ok::<_, ::actix_web::Error>(match download?.await {
+                        Ok(download) => download_response(&req, download).await,
+                        Err(error) => encoded_response::<Json, _>(status, &error),
+                    })
+                }
+            })),
+            InputLocation::Body => resource.route(route.to(
+                move |json: Encoded<Json, I>, path: Path<P>, req: HttpRequest, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let download = validation.check(&input).map(|()| (handler)(input, path.into_inner(), $($param,)*));
+                async move {
+                    Ok::<_, ::actix_web::Error>(match download?.await {
+                        Ok(download) => download_response(&req, download).await,
+                        Err(error) => encoded_response::<Json, _>(status, &error),
+                    })
+                }
+            })),
+            InputLocation::Form => resource.route(route.to(
+                move |json: Encoded<UrlEncoded, I>, path: Path<P>, req: HttpRequest, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let download = validation.check(&input).map(|()| (handler)(input, path.into_inner(), $($param,)*));
+                async move {
+                    Ok::<_, ::actix_web::Error>(match download?.await {
+                        Ok(download) => download_response(&req, download).await,
+                        Err(error) => encoded_response::<Json, _>(status, &error),
+                    })
+                }
+            })),
+        }
+    }
+}
+});
+
+download_factory_tuple! {}
+download_factory_tuple! { P0 }
+download_factory_tuple! { P0 P1 }
+download_factory_tuple! { P0 P1 P2 }
+download_factory_tuple! { P0 P1 P2 P3 }
+download_factory_tuple! { P0 P1 P2 P3 P4 }
+download_factory_tuple! { P0 P1 P2 P3 P4 P5 }
+download_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 }
+download_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 }
+download_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 }
+download_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 P9 }
+
+#[cfg(test)]
+mod tests {
+
+    use ::actix_web::dev::Service;
+    use ::actix_web::{App, test};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct ReportRequest {
+        pub source: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct ReportError {
+        pub reason: String,
+    }
+
+    const REPORT: RestDownload<ReportRequest, (), ReportError> =
+        RestDownload::get("/api/report").with_error_status(404);
+
+    async fn report(request: ReportRequest) -> Result<Download, ReportError> {
+        match request.source.as_str() {
+            "bytes" => Ok(Download::bytes("text/plain", "0123456789").attachment("report.txt")),
+            "file" => {
+                let path = std::env::temp_dir().join(format!("ajars-actix-report-{}.txt", rand::random::<u64>()));
+                std::fs::write(&path, "0123456789").unwrap();
+                Ok(Download::file("text/plain", path))
+            }
+            "stream" => {
+                Ok(Download::stream("text/plain", stream::iter(vec![Ok(b"01234".to_vec()), Ok(b"56789".to_vec())])))
+            }
+            _ => Err(ReportError { reason: "Unknown source".to_owned() }),
+        }
+    }
+
+    fn request(uri: &str) -> test::TestRequest {
+        test::TestRequest::get().uri(uri)
+    }
+
+    #[actix_rt::test]
+    async fn should_send_the_whole_content_with_its_headers() {
+        // Arrange
+        let app = test::init_service(App::new().service(REPORT.to(report))).await;
+
+        // Act
+        let resp = app.call(request("/api/report?source=bytes").to_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!("text/plain", resp.headers().get(header::CONTENT_TYPE).unwrap());
+        assert_eq!("bytes", resp.headers().get(header::ACCEPT_RANGES).unwrap());
+        assert_eq!(
+            "attachment; filename=\"report.txt\"; filename*=UTF-8''report.txt",
+            resp.headers().get(header::CONTENT_DISPOSITION).unwrap()
+        );
+        assert_eq!("0123456789", test::read_body(resp).await);
+    }
+
+    #[actix_rt::test]
+    async fn should_send_the_range_of_the_content() {
+        // Arrange
+        let app = test::init_service(App::new().service(REPORT.to(report))).await;
+
+        for source in ["bytes", "file"] {
+            // Act
+            let uri = format!("/api/report?source={}", source);
+            let resp = app.call(request(&uri).insert_header((header::RANGE, "bytes=4-")).to_request()).await.unwrap();
+
+            // Assert
+            assert_eq!(resp.status(), StatusCode::PARTIAL_CONTENT);
+            assert_eq!("bytes 4-9/10", resp.headers().get(header::CONTENT_RANGE).unwrap());
+            assert_eq!("456789", test::read_body(resp).await);
+        }
+    }
+
+    #[actix_rt::test]
+    async fn should_not_satisfy_a_range_after_the_end_of_the_content() {
+        // Arrange
+        let app = test::init_service(App::new().service(REPORT.to(report))).await;
+
+        // Act
+        let resp = app
+            .call(request("/api/report?source=file").insert_header((header::RANGE, "bytes=10-")).to_request())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!("bytes */10", resp.headers().get(header::CONTENT_RANGE).unwrap());
+    }
+
+    #[actix_rt::test]
+    async fn should_send_a_stream_as_a_whole() {
+        // Arrange
+        let app = test::init_service(App::new().service(REPORT.to(report))).await;
+
+        // Act
+        let resp = app
+            .call(request("/api/report?source=stream").insert_header((header::RANGE, "bytes=4-")).to_request())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!("0123456789", test::read_body(resp).await);
+    }
+
+    #[actix_rt::test]
+    async fn should_send_the_typed_error() {
+        // Arrange
+        let app = test::init_service(App::new().service(REPORT.to(report))).await;
+
+        // Act
+        let resp = app.call(request("/api/report?source=none").to_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let body: ReportError = test::read_body_json(resp).await;
+        assert_eq!("Unknown source", body.reason);
+    }
+}
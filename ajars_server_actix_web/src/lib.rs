@@ -1,12 +1,40 @@
+use std::fmt::{Debug, Display};
 use std::future::Future;
-
-use ::actix_web::web::{self, Json, Query};
-use ::actix_web::{FromRequest, Resource, ResponseError};
-use ajars_core::{HttpMethod, RestType};
-use futures_util::future::FutureExt;
+use std::marker::PhantomData;
+
+use ::actix_web::body::BoxBody;
+use ::actix_web::dev::Payload;
+use ::actix_web::error::{ErrorBadRequest, ErrorUnsupportedMediaType};
+use ::actix_web::guard;
+use ::actix_web::http::{Method, StatusCode, header};
+use ::actix_web::web::{self, Bytes, Path, Query};
+use ::actix_web::{FromRequest, HttpRequest, HttpResponse, Resource, Responder, ResponseError};
+use ajars_core::{
+    ApiGroup, Codec, CodecError, EndpointDescriptor, HttpMethod, InputLocation, NoError, RestType, ScopedRest,
+    empty_body_output, is_empty_body,
+};
+use futures_util::future::{FutureExt, LocalBoxFuture};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 
+mod batch;
+mod download;
+mod sse;
+mod stream;
+#[cfg(feature = "upload")]
+mod upload;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+pub use batch::AjarsServerActixWebBatchHandler;
+pub use download::AjarsServerActixWebDownloadHandler;
+pub use sse::{AjarsServerActixWebSseHandler, LastEventId};
+pub use stream::AjarsServerActixWebStreamHandler;
+#[cfg(feature = "upload")]
+pub use upload::AjarsServerActixWebUploadHandler;
+#[cfg(feature = "websocket")]
+pub use websocket::{AjarsServerActixWebWebSocketHandler, WebSocketReceiver, WebSocketSender};
+
 pub mod actix_web {
     pub use ::actix_web::*;
 }
@@ -15,9 +43,280 @@ pub trait AjarsServerActixWebHandler<I: Serialize + DeserializeOwned, O: Seriali
     fn to(&self, handler: H) -> Resource;
 }
 
+/// Mounts all the endpoints of an [`ApiGroup`] at once, at their full paths.
+///
+/// Each resource is guarded by the method of its endpoint, so endpoints that share a path do not shadow each other.
+pub struct AjarsActixWebGroup<'g> {
+    group: &'g ApiGroup,
+    resources: Vec<Resource>,
+    mounted: Vec<EndpointDescriptor>,
+}
+
+impl<'g> AjarsActixWebGroup<'g> {
+    /// # Panics
+    ///
+    /// Panics if the endpoints of the group conflict with each other, see [`ApiGroup::validate`].
+    pub fn new(group: &'g ApiGroup) -> Self {
+        if let Err(error) = group.validate() {
+            panic!("{}", error);
+        }
+        Self { group, resources: vec![], mounted: vec![] }
+    }
+
+    /// Mounts the handler of an endpoint of the group,
+    /// at every path of the endpoint when it was added to several sub-groups.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the endpoint is not part of the group.
+    pub fn route<'r, I, O, T, H, REST>(mut self, rest: &'r REST, handler: H) -> Self
+    where
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        H: Clone,
+        REST: RestType<I, O>,
+        ScopedRest<'r, REST>: AjarsServerActixWebHandler<I, O, T, H> + RestType<I, O>,
+    {
+        let scopes = self.group.scopes(rest);
+        assert!(
+            !scopes.is_empty(),
+            "The endpoint [{:?} {}] is not part of the group [{}]",
+            rest.method(),
+            rest.path(),
+            self.group.prefix()
+        );
+        for scoped in scopes {
+            self.mounted.push(EndpointDescriptor::of(&scoped));
+            self.resources.push(scoped.to(handler.clone()).guard(guard::Method(method(rest.method()))));
+        }
+        self
+    }
+
+    /// Returns the resources of all the endpoints of the group, to be registered with `App::service`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an endpoint of the group, or of one of its sub-groups, has no handler.
+    pub fn build(self) -> Vec<Resource> {
+        let missing = self
+            .group
+            .endpoints()
+            .into_iter()
+            .filter(|endpoint| !self.mounted.contains(endpoint))
+            .map(|endpoint| format!("{:?} {}", endpoint.method(), endpoint.path()))
+            .collect::<Vec<_>>();
+        assert!(
+            missing.is_empty(),
+            "The endpoints {:?} of the group [{}] have no handler",
+            missing,
+            self.group.prefix()
+        );
+        self.resources
+    }
+}
+
+/// Marker that selects the handler signature of the endpoints without path parameters.
+pub struct WithoutPathParams;
+
+/// Marker that selects the handler signature of the endpoints with path parameters.
+/// Their handlers receive the path parameters right after the input.
+pub struct WithPathParams;
+
+/// Marker that selects the handlers of the endpoints without a typed error.
+/// Their handlers can fail with any error that implements `ResponseError`.
+pub struct UntypedError;
+
+/// Marker that selects the handlers of the endpoints with a typed error.
+/// Their handlers fail with the error type of the endpoint, that is encoded with the codec of the endpoint
+/// and sent with the endpoint error status.
+pub struct TypedError;
+
+/// A request or response body encoded with the codec `C`.
+/// The size of the request bodies is limited by the `PayloadConfig` of the application.
+struct Encoded<C, T>(T, PhantomData<C>);
+
+impl<C, T> Encoded<C, T> {
+    fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+
+    fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<C: Codec, T: DeserializeOwned + 'static> FromRequest for Encoded<C, T> {
+    type Error = ::actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let content_type = req.headers().get(header::CONTENT_TYPE).and_then(|value| value.to_str().ok());
+        let accepted = content_type.is_some_and(C::matches_content_type);
+        let body = Bytes::from_request(req, payload);
+
+        async move {
+            let body = body.await?;
+
+            // An empty input, like `()`, is sent without a body
+            if body.is_empty()
+                && let Some(value) = empty_body_output()
+            {
+                return Ok(Self::new(value));
+            }
+
+            if !accepted {
+                return Err(ErrorUnsupportedMediaType(format!(
+                    "Expected request with `Content-Type: {}`",
+                    C::CONTENT_TYPE
+                )));
+            }
+            C::decode(&body)
+                .map(Self::new)
+                .map_err(|err| ErrorBadRequest(format!("Failed to decode the request body: {}", err)))
+        }
+        .boxed_local()
+    }
+}
+
+/// The `application/x-www-form-urlencoded` encoding of the inputs sent as forms
+struct UrlEncoded;
+
+impl Codec for UrlEncoded {
+    const CONTENT_TYPE: &'static str = "application/x-www-form-urlencoded";
+
+    fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, CodecError> {
+        serde_urlencoded::to_string(value).map(String::into_bytes).map_err(CodecError::new)
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CodecError> {
+        serde_urlencoded::from_bytes(bytes).map_err(CodecError::new)
+    }
+}
+
+fn encoded_response<C: Codec, T: Serialize>(status: StatusCode, value: &T) -> HttpResponse {
+    match C::encode(value) {
+        Ok(body) => HttpResponse::build(status).content_type(C::CONTENT_TYPE).body(body),
+        Err(err) => HttpResponse::InternalServerError().body(format!("Failed to encode the response body: {}", err)),
+    }
+}
+
+/// The response that carries the output of an endpoint, sent with the success status of the endpoint
+struct SuccessResponse<C, O>(StatusCode, Encoded<C, O>);
+
+impl<C, O> SuccessResponse<C, O> {
+    fn new(status: StatusCode, output: O) -> Self {
+        Self(status, Encoded::new(output))
+    }
+}
+
+impl<C: Codec, O: Serialize> Responder for SuccessResponse<C, O> {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        // An empty output, like `()`, is sent without a body,
+        // as '204 No Content' when the endpoint has the default success status
+        if is_empty_body(&self.1.0) {
+            let status = if self.0 == StatusCode::OK { StatusCode::NO_CONTENT } else { self.0 };
+            return HttpResponse::build(status).finish();
+        }
+        encoded_response::<C, _>(self.0, &self.1.0)
+    }
+}
+
+/// The response that carries the typed error of an endpoint
+struct ErrorResponse<C, E>(StatusCode, Encoded<C, E>);
+
+impl<C, E> Debug for ErrorResponse<C, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ErrorResponse({})", self.0)
+    }
+}
+
+impl<C, E> Display for ErrorResponse<C, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Endpoint error. HTTP status: {}", self.0)
+    }
+}
+
+impl<C: Codec, E: Serialize> ResponseError for ErrorResponse<C, E> {
+    fn status_code(&self) -> StatusCode {
+        self.0
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        encoded_response::<C, _>(self.0, &self.1.0)
+    }
+}
+
+/// Validates the input of an endpoint before calling its handler.
+/// An input that fails the validation is answered with '422 Unprocessable Entity' and the validation errors,
+/// encoded with the codec `C`.
+struct InputValidation<C, I> {
+    #[cfg(feature = "validator")]
+    validator: ajars_core::InputValidator<I>,
+    phantom: PhantomData<fn(&I) -> C>,
+}
+
+impl<C, I> Clone for InputValidation<C, I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, I> Copy for InputValidation<C, I> {}
+
+impl<C: Codec, I: Serialize + DeserializeOwned> InputValidation<C, I> {
+    #[cfg_attr(not(feature = "validator"), allow(unused_variables))]
+    fn of<O: Serialize + DeserializeOwned, REST: RestType<I, O>>(rest: &REST) -> Self {
+        Self {
+            #[cfg(feature = "validator")]
+            validator: rest.input_validator(),
+            phantom: PhantomData,
+        }
+    }
+
+    #[cfg_attr(not(feature = "validator"), allow(unused_variables))]
+    fn check(&self, input: &I) -> Result<(), ::actix_web::Error> {
+        #[cfg(feature = "validator")]
+        if let Err(errors) = (self.validator)(input) {
+            let response = encoded_response::<C, _>(StatusCode::UNPROCESSABLE_ENTITY, &errors);
+            return Err(::actix_web::error::InternalError::from_response(errors, response).into());
+        }
+        Ok(())
+    }
+}
+
+fn method(method: &HttpMethod) -> Method {
+    match method {
+        HttpMethod::DELETE => Method::DELETE,
+        HttpMethod::GET => Method::GET,
+        HttpMethod::HEAD => Method::HEAD,
+        HttpMethod::OPTIONS => Method::OPTIONS,
+        HttpMethod::PATCH => Method::PATCH,
+        HttpMethod::POST => Method::POST,
+        HttpMethod::PUT => Method::PUT,
+    }
+}
+
+fn error_status<I, O, REST: RestType<I, O>>(rest: &REST) -> StatusCode
+where
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+{
+    StatusCode::from_u16(rest.error_status()).expect("The error status of the endpoint should be valid")
+}
+
+fn success_status<I, O, REST: RestType<I, O>>(rest: &REST) -> StatusCode
+where
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+{
+    StatusCode::from_u16(rest.success_status()).expect("The success status of the endpoint should be valid")
+}
+
 macro_rules! factory_tuple ({ $($param:ident)* } => {
     #[allow(non_snake_case)]
-    impl <I: Serialize + DeserializeOwned + 'static, O: Serialize + DeserializeOwned + 'static, H, R, E, REST: RestType<I, O>, $($param,)*> AjarsServerActixWebHandler<I, O, ($($param,)*), H>
+    impl <I: Serialize + DeserializeOwned + 'static, O: Serialize + DeserializeOwned + 'static, H, R, E, REST: RestType<I, O, PathParams = (), Error = NoError>, $($param,)*> AjarsServerActixWebHandler<I, O, (WithoutPathParams, UntypedError, ($($param,)*)), H>
     for REST
 where
 H: Clone + 'static + Fn(I, $($param,)*) -> R,
@@ -27,23 +326,146 @@ $( $param: FromRequest + 'static, )*
 {
     fn to(&self, handler: H) -> Resource {
         let resource = web::resource::<&str>(self.path());
+        let route = web::route().method(method(self.method()));
+        let validation = InputValidation::<REST::Codec, I>::of(self);
+        let success = success_status(self);
 
-        match self.method() {
-            HttpMethod::DELETE => resource.route(web::delete().to(
+        match self.input_location() {
+            InputLocation::Query => resource.route(route.to(
                 move |json: Query<I>, $( $param: $param,)*| {
-                (handler)(json.into_inner(), $($param,)*).map(|res| res.map(Json))
+                let input = json.into_inner();
+                let future = validation.check(&input).map(|()| (handler)(input, $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(future?.await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output))) }
+            })),
+            InputLocation::Body => resource.route(route.to(
+                move |json: Encoded<REST::Codec, I>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let future = validation.check(&input).map(|()| (handler)(input, $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(future?.await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output))) }
+            })),
+            InputLocation::Form => resource.route(route.to(
+                move |json: Encoded<UrlEncoded, I>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let future = validation.check(&input).map(|()| (handler)(input, $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(future?.await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output))) }
+            })),
+        }
+    }
+}
+
+    #[allow(non_snake_case)]
+    impl <I: Serialize + DeserializeOwned + 'static, O: Serialize + DeserializeOwned + 'static, H, R, E, REST: RestType<I, O, Error = NoError>, $($param,)*> AjarsServerActixWebHandler<I, O, (WithPathParams, UntypedError, ($($param,)*)), H>
+    for REST
+where
+REST::PathParams: 'static,
+H: Clone + 'static + Fn(I, REST::PathParams, $($param,)*) -> R,
+R: Future<Output = Result<O, E>> + 'static,
+E: ResponseError + 'static,
+$( $param: FromRequest + 'static, )*
+{
+    fn to(&self, handler: H) -> Resource {
+        let resource = web::resource::<&str>(self.path());
+        let route = web::route().method(method(self.method()));
+        let validation = InputValidation::<REST::Codec, I>::of(self);
+        let success = success_status(self);
+
+        match self.input_location() {
+            InputLocation::Query => resource.route(route.to(
+                move |json: Query<I>, path: Path<REST::PathParams>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let future = validation.check(&input).map(|()| (handler)(input, path.into_inner(), $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(future?.await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output))) }
+            })),
+            InputLocation::Body => resource.route(route.to(
+                move |json: Encoded<REST::Codec, I>, path: Path<REST::PathParams>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let future = validation.check(&input).map(|()| (handler)(input, path.into_inner(), $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(future?.await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output))) }
             })),
-            HttpMethod::GET => resource.route(web::get().to(
+            InputLocation::Form => resource.route(route.to(
+                move |json: Encoded<UrlEncoded, I>, path: Path<REST::PathParams>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let future = validation.check(&input).map(|()| (handler)(input, path.into_inner(), $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(future?.await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output))) }
+            })),
+        }
+    }
+}
+
+    #[allow(non_snake_case)]
+    impl <I: Serialize + DeserializeOwned + 'static, O: Serialize + DeserializeOwned + 'static, H, R, REST: RestType<I, O, PathParams = ()>, $($param,)*> AjarsServerActixWebHandler<I, O, (WithoutPathParams, TypedError, ($($param,)*)), H>
+    for REST
+where
+REST::Error: 'static,
+H: Clone + 'static + Fn(I, $($param,)*) -> R,
+R: Future<Output = Result<O, REST::Error>> + 'static,
+$( $param: FromRequest + 'static, )*
+{
+    fn to(&self, handler: H) -> Resource {
+        let resource = web::resource::<&str>(self.path());
+        let route = web::route().method(method(self.method()));
+        let validation = InputValidation::<REST::Codec, I>::of(self);
+        let success = success_status(self);
+        let status = error_status(self);
+
+        match self.input_location() {
+            InputLocation::Query => resource.route(route.to(
                 move |json: Query<I>, $( $param: $param,)*| {
-                (handler)(json.into_inner(), $($param,)*).map(|res| res.map(Json))
+                let input = json.into_inner();
+                let future = validation.check(&input).map(|()| (handler)(input, $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(future?.await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<REST::Codec, _>::new(error)))) }
+            })),
+            InputLocation::Body => resource.route(route.to(
+                move |json: Encoded<REST::Codec, I>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let future = validation.check(&input).map(|()| (handler)(input, $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(future?.await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<REST::Codec, _>::new(error)))) }
+            })),
+            InputLocation::Form => resource.route(route.to(
+                move |json: Encoded<UrlEncoded, I>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let future = validation.check(&input).map(|()| (handler)(input, $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(future?.await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<REST::Codec, _>::new(error)))) }
+            })),
+        }
+    }
+}
+
+    #[allow(non_snake_case)]
+    impl <I: Serialize + DeserializeOwned + 'static, O: Serialize + DeserializeOwned + 'static, H, R, REST: RestType<I, O>, $($param,)*> AjarsServerActixWebHandler<I, O, (WithPathParams, TypedError, ($($param,)*)), H>
+    for REST
+where
+REST::PathParams: 'static,
+REST::Error: 'static,
+H: Clone + 'static + Fn(I, REST::PathParams, $($param,)*) -> R,
+R: Future<Output = Result<O, REST::Error>> + 'static,
+$( $param: FromRequest + 'static, )*
+{
+    fn to(&self, handler: H) -> Resource {
+        let resource = web::resource::<&str>(self.path());
+        let route = web::route().method(method(self.method()));
+        let validation = InputValidation::<REST::Codec, I>::of(self);
+        let success = success_status(self);
+        let status = error_status(self);
+
+        match self.input_location() {
+            InputLocation::Query => resource.route(route.to(
+                move |json: Query<I>, path: Path<REST::PathParams>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let future = validation.check(&input).map(|()| (handler)(input, path.into_inner(), $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(future?.await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<REST::Codec, _>::new(error)))) }
             })),
-            HttpMethod::POST => resource.route(web::post().to(
-                move |json: Json<I>, $( $param: $param,)*| {
-                (handler)(json.into_inner(), $($param,)*).map(|res| res.map(Json))
+            InputLocation::Body => resource.route(route.to(
+                move |json: Encoded<REST::Codec, I>, path: Path<REST::PathParams>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let future = validation.check(&input).map(|()| (handler)(input, path.into_inner(), $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(future?.await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<REST::Codec, _>::new(error)))) }
             })),
-            HttpMethod::PUT => resource.route(web::put().to(
-                move |json: Json<I>, $( $param: $param,)*| {
-                (handler)(json.into_inner(), $($param,)*).map(|res| res.map(Json))
+            InputLocation::Form => resource.route(route.to(
+                move |json: Encoded<UrlEncoded, I>, path: Path<REST::PathParams>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let future = validation.check(&input).map(|()| (handler)(input, path.into_inner(), $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(future?.await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<REST::Codec, _>::new(error)))) }
             })),
         }
     }
@@ -69,7 +491,7 @@ mod tests {
 
     use ::actix_web::http::{StatusCode, header};
     use ::actix_web::{App, HttpRequest};
-    use ajars_core::RestFluent;
+    use ajars_core::{ApiGroup, InputLocation, MessagePack, Rest, RestFluent};
     use serde::{Deserialize, Serialize};
 
     use super::*;
@@ -86,6 +508,17 @@ mod tests {
         pub message: String,
     }
 
+    #[derive(Serialize, Deserialize, Debug)]
+    pub struct ItemPath {
+        pub id: u64,
+        pub name: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug)]
+    pub struct PingError {
+        pub reason: String,
+    }
+
     async fn ping(body: PingRequest, _request: HttpRequest) -> Result<PingResponse, ServerError> {
         Ok(PingResponse { message: body.message })
     }
@@ -145,6 +578,79 @@ mod tests {
         assert_eq!(resp.message, payload.message);
     }
 
+    #[actix_rt::test]
+    async fn should_create_a_head_endpoint() {
+        // Arrange
+        let rest = RestFluent::<PingRequest, ()>::head(format!("/api/something/{}", rand::random::<u64>()));
+
+        let app = test::init_service(App::new().service(rest.to(|body: PingRequest| async move {
+            assert!(body.message.starts_with("message"));
+            Result::<_, ServerError>::Ok(())
+        })))
+        .await;
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        let req = test::TestRequest::default()
+            .method(Method::HEAD)
+            .uri(&format!("{}?message={}", rest.path(), payload.message))
+            .to_request();
+
+        // Act
+        let resp = app.call(req).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[actix_rt::test]
+    async fn should_create_an_options_endpoint() {
+        // Arrange
+        let rest =
+            RestFluent::<PingRequest, PingResponse>::options(format!("/api/something/{}", rand::random::<u64>()));
+
+        let app = test::init_service(App::new().service(rest.to(ping))).await;
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        let req = test::TestRequest::default()
+            .method(Method::OPTIONS)
+            .uri(&format!("{}?message={}", rest.path(), payload.message))
+            .to_request();
+
+        // Act
+        let resp = app.call(req).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!("application/json", resp.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap());
+
+        let resp: PingResponse = test::read_body_json(resp).await;
+        assert_eq!(resp.message, payload.message);
+    }
+
+    #[actix_rt::test]
+    async fn should_create_a_patch_endpoint() {
+        // Arrange
+        let rest = RestFluent::<PingRequest, PingResponse>::patch(format!("/api/something/{}", rand::random::<u64>()));
+
+        let app = test::init_service(App::new().service(rest.to(ping))).await;
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        let req = test::TestRequest::patch().uri(rest.path()).set_json(&payload).to_request();
+
+        // Act
+        let resp = app.call(req).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!("application/json", resp.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap());
+
+        let resp: PingResponse = test::read_body_json(resp).await;
+        assert_eq!(resp.message, payload.message);
+    }
+
     #[actix_rt::test]
     async fn should_create_a_post_endpoint() {
         // Arrange
@@ -188,4 +694,306 @@ mod tests {
         let resp: PingResponse = test::read_body_json(resp).await;
         assert_eq!(resp.message, payload.message);
     }
+
+    #[actix_rt::test]
+    async fn should_create_an_endpoint_with_path_params() {
+        // Arrange
+        let rest = Rest::<PingRequest, PingResponse, ItemPath>::get("/api/items/{id}/{name}");
+
+        let app = test::init_service(App::new().service(rest.to(
+            |body: PingRequest, path: ItemPath, _request: HttpRequest| async move {
+                Result::<_, ServerError>::Ok(PingResponse {
+                    message: format!("{}-{}-{}", path.id, path.name, body.message),
+                })
+            },
+        )))
+        .await;
+
+        let path = rest.path_with(&ItemPath { id: 12, name: "John Doe".to_owned() });
+
+        let req = test::TestRequest::get().uri(&format!("{}?message=hello", path)).to_request();
+
+        // Act
+        let resp = app.call(req).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp: PingResponse = test::read_body_json(resp).await;
+        assert_eq!(resp.message, "12-John Doe-hello");
+    }
+
+    #[actix_rt::test]
+    async fn should_send_the_typed_error_with_the_error_status() {
+        // Arrange
+        let rest = Rest::<PingRequest, PingResponse, (), PingError>::post("/api/failing").with_error_status(409);
+
+        let app = test::init_service(App::new().service(rest.to(|body: PingRequest| async move {
+            Result::<PingResponse, _>::Err(PingError { reason: body.message })
+        })))
+        .await;
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        let req = test::TestRequest::post().uri(rest.path()).set_json(&payload).to_request();
+
+        // Act
+        let resp = app.call(req).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        assert_eq!("application/json", resp.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap());
+
+        let resp: PingError = test::read_body_json(resp).await;
+        assert_eq!(resp.reason, payload.message);
+    }
+
+    #[actix_rt::test]
+    async fn should_send_the_output_with_the_success_status() {
+        // Arrange
+        let created = Rest::<PingRequest, PingResponse>::post("/api/created").with_success_status(201);
+        let accepted = Rest::<PingRequest, ()>::post("/api/accepted").with_success_status(202);
+
+        let app = test::init_service(
+            App::new()
+                .service(created.to(|body: PingRequest| async move {
+                    Result::<_, ServerError>::Ok(PingResponse { message: body.message })
+                }))
+                .service(accepted.to(|_body: PingRequest| async move { Result::<_, ServerError>::Ok(()) })),
+        )
+        .await;
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        // Act
+        let created_resp =
+            app.call(test::TestRequest::post().uri(created.path()).set_json(&payload).to_request()).await.unwrap();
+        let accepted_resp =
+            app.call(test::TestRequest::post().uri(accepted.path()).set_json(&payload).to_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(created_resp.status(), StatusCode::CREATED);
+        let created_resp: PingResponse = test::read_body_json(created_resp).await;
+        assert_eq!(created_resp.message, payload.message);
+
+        assert_eq!(accepted_resp.status(), StatusCode::ACCEPTED);
+        assert!(test::read_body(accepted_resp).await.is_empty());
+    }
+
+    #[cfg(feature = "validator")]
+    #[actix_rt::test]
+    async fn should_answer_unprocessable_entity_if_the_input_is_not_valid() {
+        // Arrange
+        #[derive(Serialize, Deserialize, Debug, validator::Validate)]
+        pub struct SignupRequest {
+            #[validate(length(min = 3))]
+            pub username: String,
+        }
+
+        let rest = Rest::<SignupRequest, (), (), NoError>::post("/api/signup").with_validation();
+
+        let app = test::init_service(
+            App::new().service(rest.to(|_body: SignupRequest| async move { Result::<(), NoError>::Ok(()) })),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(rest.path())
+            .set_json(SignupRequest { username: "u".to_owned() })
+            .to_request();
+
+        // Act
+        let resp = app.call(req).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!("application/json", resp.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap());
+
+        let resp: ajars_core::ValidationErrors = test::read_body_json(resp).await;
+        assert!(resp.field_errors().contains_key("username"));
+    }
+
+    #[actix_rt::test]
+    async fn should_encode_the_bodies_with_the_codec_of_the_endpoint() {
+        // Arrange
+        let rest = Rest::<PingRequest, PingResponse, (), NoError, MessagePack>::post("/api/msgpack");
+
+        let app = test::init_service(App::new().service(rest.to(|body: PingRequest| async move {
+            Result::<_, ServerError>::Ok(PingResponse { message: body.message })
+        })))
+        .await;
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        let req = test::TestRequest::post()
+            .uri(rest.path())
+            .insert_header((header::CONTENT_TYPE, "application/msgpack"))
+            .set_payload(MessagePack::encode(&payload).unwrap())
+            .to_request();
+
+        // Act
+        let resp = app.call(req).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!("application/msgpack", resp.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap());
+
+        let body = test::read_body(resp).await;
+        let resp: PingResponse = MessagePack::decode(&body).unwrap();
+        assert_eq!(resp.message, payload.message);
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_a_body_with_a_different_content_type() {
+        // Arrange
+        let rest = Rest::<PingRequest, PingResponse, (), NoError, MessagePack>::post("/api/msgpack");
+
+        let app = test::init_service(App::new().service(rest.to(|body: PingRequest| async move {
+            Result::<_, ServerError>::Ok(PingResponse { message: body.message })
+        })))
+        .await;
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        let req = test::TestRequest::post().uri(rest.path()).set_json(&payload).to_request();
+
+        // Act
+        let resp = app.call(req).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[actix_rt::test]
+    async fn should_read_the_input_from_the_body_of_a_delete_endpoint() {
+        // Arrange
+        let rest =
+            Rest::<PingRequest, PingResponse>::delete("/api/delete_with_body").with_input_location(InputLocation::Body);
+
+        let app = test::init_service(App::new().service(rest.to(|body: PingRequest| async move {
+            Result::<_, ServerError>::Ok(PingResponse { message: body.message })
+        })))
+        .await;
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        let req = test::TestRequest::delete().uri(rest.path()).set_json(&payload).to_request();
+
+        // Act
+        let resp: PingResponse = test::call_and_read_body_json(&app, req).await;
+
+        // Assert
+        assert_eq!(resp.message, payload.message);
+    }
+
+    #[actix_rt::test]
+    async fn should_read_the_input_from_a_form_body() {
+        // Arrange
+        let rest = Rest::<PingRequest, PingResponse>::put("/api/form").with_input_location(InputLocation::Form);
+
+        let app = test::init_service(App::new().service(rest.to(|body: PingRequest| async move {
+            Result::<_, ServerError>::Ok(PingResponse { message: body.message })
+        })))
+        .await;
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        let req = test::TestRequest::put().uri(rest.path()).set_form(&payload).to_request();
+
+        // Act
+        let resp: PingResponse = test::call_and_read_body_json(&app, req).await;
+
+        // Assert
+        assert_eq!(resp.message, payload.message);
+    }
+
+    #[actix_rt::test]
+    async fn should_accept_a_unit_input_without_body_and_answer_no_content() {
+        for location in [InputLocation::Query, InputLocation::Body, InputLocation::Form] {
+            // Arrange
+            let rest = Rest::<(), ()>::post("/api/unit").with_input_location(location);
+
+            let app = test::init_service(
+                App::new().service(rest.to(|_: ()| async move { Result::<_, ServerError>::Ok(()) })),
+            )
+            .await;
+
+            let req = test::TestRequest::post().uri(rest.path()).to_request();
+
+            // Act
+            let resp = app.call(req).await.unwrap();
+
+            // Assert
+            assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+            assert!(resp.headers().get(header::CONTENT_TYPE).is_none());
+            assert!(test::read_body(resp).await.is_empty());
+        }
+    }
+
+    #[actix_rt::test]
+    async fn should_mount_all_the_endpoints_of_a_group() {
+        // Arrange
+        const GET_PING: Rest<PingRequest, PingResponse> = Rest::get("/ping");
+        const POST_PING: Rest<PingRequest, PingResponse> = Rest::post("/ping");
+        const GET_ITEM: Rest<PingRequest, PingResponse, u64> = Rest::get("/items/{id}");
+
+        let group = ApiGroup::new("/api")
+            .endpoint(&GET_PING)
+            .endpoint(&POST_PING)
+            .group(ApiGroup::new("/v1").endpoint(&GET_ITEM))
+            .group(ApiGroup::new("/v2").endpoint(&GET_ITEM));
+
+        let app = test::init_service(
+            App::new().service(
+                AjarsActixWebGroup::new(&group)
+                    .route(&GET_PING, |body: PingRequest| async move {
+                        Result::<_, ServerError>::Ok(PingResponse { message: format!("get {}", body.message) })
+                    })
+                    .route(&POST_PING, |body: PingRequest| async move {
+                        Result::<_, ServerError>::Ok(PingResponse { message: format!("post {}", body.message) })
+                    })
+                    .route(&GET_ITEM, |body: PingRequest, id: u64| async move {
+                        Result::<_, ServerError>::Ok(PingResponse { message: format!("{} {}", id, body.message) })
+                    })
+                    .build(),
+            ),
+        )
+        .await;
+
+        let requests = vec![
+            (test::TestRequest::get().uri("/api/ping?message=a").to_request(), "get a"),
+            (
+                test::TestRequest::post()
+                    .uri("/api/ping")
+                    .set_json(PingRequest { message: "b".to_owned() })
+                    .to_request(),
+                "post b",
+            ),
+            (test::TestRequest::get().uri("/api/v1/items/7?message=c").to_request(), "7 c"),
+            (test::TestRequest::get().uri("/api/v2/items/8?message=c").to_request(), "8 c"),
+        ];
+
+        for (req, expected) in requests {
+            // Act
+            let resp: PingResponse = test::call_and_read_body_json(&app, req).await;
+
+            // Assert
+            assert_eq!(expected, resp.message);
+        }
+    }
+
+    #[actix_rt::test]
+    #[should_panic(expected = "have no handler")]
+    async fn should_panic_if_an_endpoint_of_a_group_has_no_handler() {
+        const GET_PING: Rest<PingRequest, PingResponse> = Rest::get("/ping");
+        const POST_PING: Rest<PingRequest, PingResponse> = Rest::post("/ping");
+
+        let group = ApiGroup::new("/api").endpoint(&GET_PING).endpoint(&POST_PING);
+
+        AjarsActixWebGroup::new(&group)
+            .route(&POST_PING, |body: PingRequest| async move {
+                Result::<_, ServerError>::Ok(PingResponse { message: body.message })
+            })
+            .build();
+    }
 }
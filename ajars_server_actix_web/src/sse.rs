@@ -0,0 +1,203 @@
+use std::convert::Infallible;
+
+use ::actix_web::dev::Payload;
+use ::actix_web::http::{StatusCode, header};
+use ::actix_web::web::{self, Path, Query};
+use ::actix_web::{FromRequest, HttpRequest, HttpResponse, Resource};
+use ajars_core::{Json, LAST_EVENT_ID_HEADER, RestSse, RestType, SSE_CONTENT_TYPE, SseEvent, encode_sse_event};
+use futures_util::Stream;
+use futures_util::future::{Ready, ready};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::stream::streaming_response;
+use crate::{InputValidation, WithPathParams, WithoutPathParams, error_status, method};
+
+pub trait AjarsServerActixWebSseHandler<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, T, H> {
+    fn to(&self, handler: H) -> Resource;
+}
+
+/// The ID of the last event received by a client that reconnects to a Server-Sent Events endpoint,
+/// taken from the `Last-Event-ID` header; the handler can use it to resume the stream after that event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LastEventId(pub Option<String>);
+
+impl FromRequest for LastEventId {
+    type Error = Infallible;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let id = req.headers().get(LAST_EVENT_ID_HEADER).and_then(|id| id.to_str().ok()).map(ToOwned::to_owned);
+        ready(Ok(Self(id)))
+    }
+}
+
+/// Sends the events of the stream in the `text/event-stream` format.
+async fn sse_response<O, E, St>(error_status: StatusCode, events: St) -> HttpResponse
+where
+    O: Serialize + 'static,
+    E: Serialize + 'static,
+    St: Stream<Item = Result<SseEvent<O>, E>> + 'static,
+{
+    let mut response = streaming_response(error_status, SSE_CONTENT_TYPE, encode_sse_event, events).await;
+    if response.status().is_success() {
+        response.headers_mut().insert(header::CACHE_CONTROL, header::HeaderValue::from_static("no-cache"));
+    }
+    response
+}
+
+macro_rules! sse_factory_tuple ({ $($param:ident)* } => {
+    #[allow(non_snake_case)]
+    impl <I: Serialize + DeserializeOwned + 'static, O: Serialize + DeserializeOwned + 'static, E: Serialize + DeserializeOwned + 'static, H, St, $($param,)*> AjarsServerActixWebSseHandler<I, O, (WithoutPathParams, ($($param,)*)), H>
+    for RestSse<I, O, (), E>
+where
+H: Clone + 'static + Fn(I, $($param,)*) -> St,
+St: Stream<Item = Result<SseEvent<O>, E>> + 'static,
+$( $param: FromRequest + 'static, )*
+{
+    fn to(&self, handler: H) -> Resource {
+        let rest = self.rest();
+        let validation = InputValidation::<Json, I>::of(rest);
+        let status = error_status(rest);
+
+        web::resource::<&str>(rest.path()).route(web::route().method(method(rest.method())).to(
+            move |json: Query<I>, $( $param: $param,)*| {
+            let input = json.into_inner();
+            let events = validation.check(&input).map(|()| (handler)(input, $($param,)*));
+            async move { Ok::<_, ::actix_web::Error>(sse_response(status, events?).await) }
+        }))
+    }
+}
+
+    #[allow(non_snake_case)]
+    impl <I: Serialize + DeserializeOwned + 'static, O: Serialize + DeserializeOwned + 'static, P: Serialize + DeserializeOwned + 'static, E: Serialize + DeserializeOwned + 'static, H, St, $($param,)*> AjarsServerActixWebSseHandler<I, O, (WithPathParams, ($($param,)*)), H>
+    for RestSse<I, O, P, E>
+where
+H: Clone + 'static + Fn(I, P, $($param,)*) -> St,
+St: Stream<Item = Result<SseEvent<O>, E>> + 'static,
+$( $param: FromRequest + 'static, )*
+{
+    fn to(&self, handler: H) -> Resource {
+        let rest = self.rest();
+        let validation = InputValidation::<Json, I>::of(rest);
+        let status = error_status(rest);
+
+        web::resource::<&str>(rest.path()).route(web::route().method(method(rest.method())).to(
+            move |json: Query<I>, path: Path<P>, $( $param: $param,)*| {
+            let input = json.into_inner();
+            let events = validation.check(&input).map(|()| (handler)(input, path.into_inner(), $($param,)*));
+            async move { Ok::<_, ::actix_web::Error>(sse_response(status, events?).await) }
+        }))
+    }
+}
+});
+
+sse_factory_tuple! {}
+sse_factory_tuple! { P0 }
+sse_factory_tuple! { P0 P1 }
+sse_factory_tuple! { P0 P1 P2 }
+sse_factory_tuple! { P0 P1 P2 P3 }
+sse_factory_tuple! { P0 P1 P2 P3 P4 }
+sse_factory_tuple! { P0 P1 P2 P3 P4 P5 }
+sse_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 }
+sse_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 }
+sse_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 }
+sse_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 P9 }
+
+#[cfg(test)]
+mod tests {
+
+    use ::actix_web::dev::Service;
+    use ::actix_web::{App, test};
+    use futures_util::stream;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TicksRequest {
+        pub count: u64,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Tick {
+        pub count: u64,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TicksError {
+        pub reason: String,
+    }
+
+    const TICKS: RestSse<TicksRequest, Tick, (), TicksError> = RestSse::get("/api/ticks").with_error_status(409);
+
+    fn ticks(
+        request: TicksRequest,
+        last_event_id: LastEventId,
+    ) -> impl Stream<Item = Result<SseEvent<Tick>, TicksError>> {
+        let first = last_event_id.0.map(|id| id.parse::<u64>().unwrap() + 1).unwrap_or(1);
+        let events = match request.count {
+            0 => vec![Err(TicksError { reason: "No ticks".to_owned() })],
+            count => (first..first + count)
+                .map(|count| Ok(SseEvent::new(Tick { count }).with_id(count.to_string())))
+                .collect(),
+        };
+        stream::iter(events)
+    }
+
+    #[actix_rt::test]
+    async fn should_send_the_events_as_an_event_stream() {
+        // Arrange
+        let app = test::init_service(App::new().service(TICKS.to(ticks))).await;
+
+        // Act
+        let resp = app.call(test::TestRequest::get().uri("/api/ticks?count=2").to_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(SSE_CONTENT_TYPE, resp.headers().get("content-type").unwrap().to_str().unwrap());
+        assert_eq!("no-cache", resp.headers().get("cache-control").unwrap().to_str().unwrap());
+
+        let body = test::read_body(resp).await;
+        assert_eq!(
+            "id: 1\ndata: {\"count\":1}\n\nid: 2\ndata: {\"count\":2}\n\n",
+            String::from_utf8(body.to_vec()).unwrap()
+        );
+    }
+
+    #[actix_rt::test]
+    async fn should_pass_the_last_event_id_to_the_handler() {
+        // Arrange
+        let app = test::init_service(App::new().service(TICKS.to(ticks))).await;
+
+        // Act
+        let resp = app
+            .call(
+                test::TestRequest::get()
+                    .uri("/api/ticks?count=1")
+                    .insert_header((LAST_EVENT_ID_HEADER, "41"))
+                    .to_request(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        let body = test::read_body(resp).await;
+        assert_eq!("id: 42\ndata: {\"count\":42}\n\n", String::from_utf8(body.to_vec()).unwrap());
+    }
+
+    #[actix_rt::test]
+    async fn should_send_an_error_before_the_first_event_as_the_typed_error() {
+        // Arrange
+        let app = test::init_service(App::new().service(TICKS.to(ticks))).await;
+
+        // Act
+        let resp = app.call(test::TestRequest::get().uri("/api/ticks?count=0").to_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let resp: TicksError = test::read_body_json(resp).await;
+        assert_eq!("No ticks", resp.reason);
+    }
+}
@@ -0,0 +1,211 @@
+use ::actix_web::http::StatusCode;
+use ::actix_web::web::{self, Bytes, Path, Query};
+use ::actix_web::{FromRequest, HttpResponse, Resource};
+use ajars_core::{CodecError, InputLocation, Json, NDJSON_CONTENT_TYPE, RestStream, RestType, encode_ndjson_item};
+use futures_util::{Stream, StreamExt, stream};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    Encoded, InputValidation, UrlEncoded, WithPathParams, WithoutPathParams, encoded_response, error_status, method,
+};
+
+pub trait AjarsServerActixWebStreamHandler<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, T, H> {
+    fn to(&self, handler: H) -> Resource;
+}
+
+/// Sends the items of the stream as newline-delimited JSON.
+async fn ndjson_response<O, E, St>(error_status: StatusCode, items: St) -> HttpResponse
+where
+    O: Serialize + 'static,
+    E: Serialize + 'static,
+    St: Stream<Item = Result<O, E>> + 'static,
+{
+    streaming_response(error_status, NDJSON_CONTENT_TYPE, encode_ndjson_item, items).await
+}
+
+/// Sends the items of the stream, each one encoded by `encode`, as the body of a response with the given content type.
+/// An error returned before the first item is sent as the typed error of the endpoint,
+/// while an error returned after it aborts the response, as its status has already been sent.
+pub(crate) async fn streaming_response<T, E, St>(
+    error_status: StatusCode,
+    content_type: &'static str,
+    encode: fn(&T) -> Result<Vec<u8>, CodecError>,
+    items: St,
+) -> HttpResponse
+where
+    T: 'static,
+    E: Serialize + 'static,
+    St: Stream<Item = Result<T, E>> + 'static,
+{
+    let mut items = Box::pin(items);
+    let first = match items.next().await {
+        Some(Err(error)) => return encoded_response::<Json, _>(error_status, &error),
+        first => first,
+    };
+
+    let chunks = stream::iter(first).chain(items).map(move |item| match item {
+        Ok(item) => encode(&item).map(Bytes::from).map_err(Box::<dyn std::error::Error>::from),
+        Err(_) => {
+            Err(Box::<dyn std::error::Error>::from("The stream of the endpoint failed after sending the first item"))
+        }
+    });
+    HttpResponse::Ok().content_type(content_type).streaming(chunks)
+}
+
+macro_rules! stream_factory_tuple ({ $($param:ident)* } => {
+    #[allow(non_snake_case)]
+    impl <I: Serialize + DeserializeOwned + 'static, O: Serialize + DeserializeOwned + 'static, E: Serialize + DeserializeOwned + 'static, H, St, $($param,)*> AjarsServerActixWebStreamHandler<I, O, (WithoutPathParams, ($($param,)*)), H>
+    for RestStream<I, O, (), E>
+where
+H: Clone + 'static + Fn(I, $($param,)*) -> St,
+St: Stream<Item = Result<O, E>> + 'static,
+$( $param: FromRequest + 'static, )*
+{
+    fn to(&self, handler: H) -> Resource {
+        let rest = self.rest();
+        let resource = web::resource::<&str>(rest.path());
+        let route = web::route().method(method(rest.method()));
+        let validation = InputValidation::<Json, I>::of(rest);
+        let status = error_status(rest);
+
+        match rest.input_location() {
+            InputLocation::Query => resource.route(route.to(
+                move |json: Query<I>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let items = validation.check(&input).map(|()| (handler)(input, $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(ndjson_response(status, items?).await) }
+            })),
+            InputLocation::Body => resource.route(route.to(
+                move |json: Encoded<Json, I>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let items = validation.check(&input).map(|()| (handler)(input, $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(ndjson_response(status, items?).await) }
+            })),
+            InputLocation::Form => resource.route(route.to(
+                move |json: Encoded<UrlEncoded, I>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let items = validation.check(&input).map(|()| (handler)(input, $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(ndjson_response(status, items?).await) }
+            })),
+        }
+    }
+}
+
+    #[allow(non_snake_case)]
+    impl <I: Serialize + DeserializeOwned + 'static, O: Serialize + DeserializeOwned + 'static, P: Serialize + DeserializeOwned + 'static, E: Serialize + DeserializeOwned + 'static, H, St, $($param,)*> AjarsServerActixWebStreamHandler<I, O, (WithPathParams, ($($param,)*)), H>
+    for RestStream<I, O, P, E>
+where
+H: Clone + 'static + Fn(I, P, $($param,)*) -> St,
+St: Stream<Item = Result<O, E>> + 'static,
+$( $param: FromRequest + 'static, )*
+{
+    fn to(&self, handler: H) -> Resource {
+        let rest = self.rest();
+        let resource = web::resource::<&str>(rest.path());
+        let route = web::route().method(method(rest.method()));
+        let validation = InputValidation::<Json, I>::of(rest);
+        let status = error_status(rest);
+
+        match rest.input_location() {
+            InputLocation::Query => resource.route(route.to(
+                move |json: Query<I>, path: Path<P>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let items = validation.check(&input).map(|()| (handler)(input, path.into_inner(), $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(ndjson_response(status, items?).await) }
+            })),
+            InputLocation::Body => resource.route(route.to(
+                move |json: Encoded<Json, I>, path: Path<P>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let items = validation.check(&input).map(|()| (handler)(input, path.into_inner(), $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(ndjson_response(status, items?).await) }
+            })),
+            InputLocation::Form => resource.route(route.to(
+                move |json: Encoded<UrlEncoded, I>, path: Path<P>, $( $param: $param,)*| {
+                let input = json.into_inner();
+                let items = validation.check(&input).map(|()| (handler)(input, path.into_inner(), $($param,)*));
+                async move { Ok::<_, ::actix_web::Error>(ndjson_response(status, items?).await) }
+            })),
+        }
+    }
+}
+});
+
+stream_factory_tuple! {}
+stream_factory_tuple! { P0 }
+stream_factory_tuple! { P0 P1 }
+stream_factory_tuple! { P0 P1 P2 }
+stream_factory_tuple! { P0 P1 P2 P3 }
+stream_factory_tuple! { P0 P1 P2 P3 P4 }
+stream_factory_tuple! { P0 P1 P2 P3 P4 P5 }
+stream_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 }
+stream_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 }
+stream_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 }
+stream_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 P9 }
+
+#[cfg(test)]
+mod tests {
+
+    use ::actix_web::dev::Service;
+    use ::actix_web::{App, test};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct ExportRequest {
+        pub count: u64,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Row {
+        pub id: u64,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct ExportError {
+        pub reason: String,
+    }
+
+    const EXPORT: RestStream<ExportRequest, Row, (), ExportError> =
+        RestStream::get("/api/export").with_error_status(409);
+
+    fn export(request: ExportRequest) -> impl Stream<Item = Result<Row, ExportError>> {
+        let rows = match request.count {
+            0 => vec![Err(ExportError { reason: "Nothing to export".to_owned() })],
+            count => (0..count).map(|id| Ok(Row { id })).collect(),
+        };
+        stream::iter(rows)
+    }
+
+    #[actix_rt::test]
+    async fn should_send_the_items_as_newline_delimited_json() {
+        // Arrange
+        let app = test::init_service(App::new().service(EXPORT.to(export))).await;
+
+        // Act
+        let resp = app.call(test::TestRequest::get().uri("/api/export?count=3").to_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(NDJSON_CONTENT_TYPE, resp.headers().get("content-type").unwrap().to_str().unwrap());
+
+        let body = test::read_body(resp).await;
+        assert_eq!("{\"id\":0}\n{\"id\":1}\n{\"id\":2}\n", String::from_utf8(body.to_vec()).unwrap());
+    }
+
+    #[actix_rt::test]
+    async fn should_send_an_error_before_the_first_item_as_the_typed_error() {
+        // Arrange
+        let app = test::init_service(App::new().service(EXPORT.to(export))).await;
+
+        // Act
+        let resp = app.call(test::TestRequest::get().uri("/api/export?count=0").to_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let resp: ExportError = test::read_body_json(resp).await;
+        assert_eq!("Nothing to export", resp.reason);
+    }
+}
@@ -0,0 +1,231 @@
+use std::future::Future;
+
+use ::actix_web::error::{ErrorBadRequest, ErrorInternalServerError};
+use ::actix_web::web::{self, Path};
+use ::actix_web::{FromRequest, Resource};
+use actix_multipart::{Field, Multipart};
+use ajars_core::{Codec, FILE_PART, Json, METADATA_PART, RestType, RestUpload, UploadedFile, empty_body_output};
+use futures_util::StreamExt;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    Encoded, ErrorResponse, InputValidation, SuccessResponse, WithPathParams, WithoutPathParams, error_status, method,
+    success_status,
+};
+
+pub trait AjarsServerActixWebUploadHandler<M: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, T, H> {
+    fn to(&self, handler: H) -> Resource;
+}
+
+/// Reads the metadata and the files of an upload, streaming every file to a temporary file.
+/// The parts with an unknown name are skipped.
+async fn read_upload<M: DeserializeOwned>(
+    mut multipart: Multipart,
+) -> Result<(M, Vec<UploadedFile>), ::actix_web::Error> {
+    let mut metadata = None;
+    let mut files = vec![];
+    while let Some(field) = multipart.next().await {
+        let mut field = field?;
+        match field.name() {
+            Some(METADATA_PART) => {
+                let mut bytes = vec![];
+                while let Some(chunk) = field.next().await {
+                    bytes.extend_from_slice(&chunk?);
+                }
+                let decoded = Json::decode(&bytes)
+                    .map_err(|err| ErrorBadRequest(format!("Failed to decode the metadata of the upload: {}", err)))?;
+                metadata = Some(decoded);
+            }
+            Some(FILE_PART) => files.push(write_file(field).await?),
+            _ => {}
+        }
+    }
+
+    // An empty metadata, like `()`, can be sent without its part
+    let metadata = metadata
+        .or_else(empty_body_output)
+        .ok_or_else(|| ErrorBadRequest(format!("The `{}` part of the upload is missing", METADATA_PART)))?;
+    Ok((metadata, files))
+}
+
+/// Streams a file part to a temporary file.
+async fn write_file(mut field: Field) -> Result<UploadedFile, ::actix_web::Error> {
+    let failure = |err: std::io::Error| ErrorInternalServerError(format!("Failed to store the file: {}", err));
+
+    let file_name = field.content_disposition().and_then(|disposition| disposition.get_filename()).map(str::to_owned);
+    let content_type = field.content_type().map(ToString::to_string);
+    let (uploaded, file) = UploadedFile::create(file_name, content_type).map_err(failure)?;
+    let mut file = tokio::fs::File::from_std(file);
+    while let Some(chunk) = field.next().await {
+        file.write_all(&chunk?).await.map_err(failure)?;
+    }
+    file.flush().await.map_err(failure)?;
+    Ok(uploaded)
+}
+
+macro_rules! upload_factory_tuple ({ $($param:ident)* } => {
+    #[allow(non_snake_case)]
+    impl <M: Serialize + DeserializeOwned + 'static, O: Serialize + DeserializeOwned + 'static, E: Serialize + DeserializeOwned + 'static, H, R, $($param,)*> AjarsServerActixWebUploadHandler<M, O, (WithoutPathParams, ($($param,)*)), H>
+    for RestUpload<M, O, (), E>
+where
+H: Clone + 'static + Fn(M, Vec<UploadedFile>, $($param,)*) -> R,
+R: Future<Output = Result<O, E>> + 'static,
+$( $param: FromRequest + 'static, )*
+{
+    fn to(&self, handler: H) -> Resource {
+        let rest = self.rest();
+        let validation = InputValidation::<Json, M>::of(rest);
+        let success = success_status(rest);
+        let status = error_status(rest);
+        web::resource::<&str>(rest.path()).route(web::route().method(method(rest.method())).to(
+            move |multipart: Multipart, $( $param: $param,)*| {
+            let handler = handler.clone();
+            async move {
+                let (metadata, files) = read_upload(multipart).await?;
+                validation.check(&metadata)?;
+                Ok::<_, ::actix_web::Error>((handler)(metadata, files, $($param,)*).await.map(|output| SuccessResponse::<Json, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<Json, _>::new(error))))
+            }
+        }))
+    }
+}
+
+    #[allow(non_snake_case)]
+    impl <M: Serialize + DeserializeOwned + 'static, O: Serialize + DeserializeOwned + 'static, P: Serialize + DeserializeOwned + 'static, E: Serialize + DeserializeOwned + 'static, H, R, $($param,)*> AjarsServerActixWebUploadHandler<M, O, (WithPathParams, ($($param,)*)), H>
+    for RestUpload<M, O, P, E>
+where
+H: Clone + 'static + Fn(M, Vec<UploadedFile>, P, $($param,)*) -> R,
+R: Future<Output = Result<O, E>> + 'static,
+$( $param: FromRequest + 'static, )*
+{
+    fn to(&self, handler: H) -> Resource {
+        let rest = self.rest();
+        let validation = InputValidation::<Json, M>::of(rest);
+        let success = success_status(rest);
+        let status = error_status(rest);
+        web::resource::<&str>(rest.path()).route(web::route().method(method(rest.method())).to(
+            move |multipart: Multipart, path: Path<P>, $( $param: $param,)*| {
+            let handler = handler.clone();
+            async move {
+                let (metadata, files) = read_upload(multipart).await?;
+                validation.check(&metadata)?;
+                Ok::<_, ::actix_web::Error>((handler)(metadata, files, path.into_inner(), $($param,)*).await.map(|output| SuccessResponse::<Json, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<Json, _>::new(error))))
+            }
+        }))
+    }
+}
+});
+
+upload_factory_tuple! {}
+upload_factory_tuple! { P0 }
+upload_factory_tuple! { P0 P1 }
+upload_factory_tuple! { P0 P1 P2 }
+upload_factory_tuple! { P0 P1 P2 P3 }
+upload_factory_tuple! { P0 P1 P2 P3 P4 }
+upload_factory_tuple! { P0 P1 P2 P3 P4 P5 }
+upload_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 }
+upload_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 }
+upload_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 }
+upload_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 P9 }
+
+#[cfg(test)]
+mod tests {
+
+    use ::actix_web::dev::Service;
+    use ::actix_web::http::StatusCode;
+    use ::actix_web::http::header;
+    use ::actix_web::{App, test};
+    use ajars_core::{FilePart, encode_multipart, multipart_content_type};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Document {
+        pub title: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Stored {
+        pub title: String,
+        pub files: Vec<(String, String)>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct UploadError {
+        pub reason: String,
+    }
+
+    const UPLOAD: RestUpload<Document, Stored, (), UploadError> =
+        RestUpload::post("/api/upload").with_error_status(409);
+
+    async fn upload(metadata: Document, files: Vec<UploadedFile>) -> Result<Stored, UploadError> {
+        if files.is_empty() {
+            return Err(UploadError { reason: "No files".to_owned() });
+        }
+        let files = files
+            .iter()
+            .map(|file| (file.file_name().unwrap().to_owned(), String::from_utf8(file.read().unwrap()).unwrap()))
+            .collect();
+        Ok(Stored { title: metadata.title, files })
+    }
+
+    fn request(body: Vec<u8>) -> test::TestRequest {
+        test::TestRequest::post()
+            .uri("/api/upload")
+            .insert_header((header::CONTENT_TYPE, multipart_content_type("XYZ")))
+            .set_payload(body)
+    }
+
+    #[actix_rt::test]
+    async fn should_receive_the_metadata_and_the_files() {
+        // Arrange
+        let app = test::init_service(App::new().service(UPLOAD.to(upload))).await;
+        let metadata = Document { title: "doc".to_owned() };
+        let files = [FilePart::new("a.txt", "hello"), FilePart::new("b.txt", "world")];
+
+        // Act
+        let resp = app.call(request(encode_multipart("XYZ", &metadata, &files).unwrap()).to_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: Stored = test::read_body_json(resp).await;
+        assert_eq!(
+            Stored {
+                title: "doc".to_owned(),
+                files: vec![("a.txt".to_owned(), "hello".to_owned()), ("b.txt".to_owned(), "world".to_owned())]
+            },
+            body
+        );
+    }
+
+    #[actix_rt::test]
+    async fn should_send_the_typed_error_with_the_error_status() {
+        // Arrange
+        let app = test::init_service(App::new().service(UPLOAD.to(upload))).await;
+
+        // Act
+        let body = encode_multipart("XYZ", &Document { title: "doc".to_owned() }, &[]).unwrap();
+        let resp = app.call(request(body).to_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let body: UploadError = test::read_body_json(resp).await;
+        assert_eq!("No files", body.reason);
+    }
+
+    #[actix_rt::test]
+    async fn should_answer_bad_request_if_the_metadata_is_missing() {
+        // Arrange
+        let app = test::init_service(App::new().service(UPLOAD.to(upload))).await;
+
+        // Act
+        let resp = app.call(request(b"--XYZ--\r\n".to_vec()).to_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
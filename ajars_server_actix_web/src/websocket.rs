@@ -0,0 +1,178 @@
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use ::actix_web::web::{self, Bytes, Path, Payload};
+use ::actix_web::{FromRequest, HttpRequest, Resource};
+use actix_ws::{AggregatedMessage, AggregatedMessageStream, Session};
+use ajars_core::{RestType, RestWebSocket, WebSocketError, decode_ws_message, encode_ws_message};
+use futures_channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures_util::{Sink, Stream, StreamExt};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{WithPathParams, WithoutPathParams, method};
+
+pub trait AjarsServerActixWebWebSocketHandler<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, T, H> {
+    fn to(&self, handler: H) -> Resource;
+}
+
+/// A frame sent to the client by the task that owns the session of the connection.
+enum Outgoing {
+    Text(String),
+    Pong(Bytes),
+    Close,
+}
+
+/// The typed sink of the messages sent to the client of a WebSocket endpoint.
+/// The connection is closed when both the sender and the receiver are dropped.
+pub struct WebSocketSender<O> {
+    outgoing: UnboundedSender<Outgoing>,
+    phantom: PhantomData<fn(O)>,
+}
+
+impl<O: Serialize> Sink<O> for WebSocketSender<O> {
+    type Error = WebSocketError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.outgoing.poll_ready(cx).map_err(WebSocketError::connection)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, message: O) -> Result<(), Self::Error> {
+        let text = encode_ws_message(&message)?;
+        self.outgoing.start_send(Outgoing::Text(text)).map_err(WebSocketError::connection)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.outgoing.close_channel();
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// The typed stream of the messages received from the client of a WebSocket endpoint.
+/// It ends when the client closes the connection.
+pub struct WebSocketReceiver<I> {
+    messages: AggregatedMessageStream,
+    // The pings and the closing handshake are answered through the session of the connection
+    outgoing: UnboundedSender<Outgoing>,
+    phantom: PhantomData<fn() -> I>,
+}
+
+impl<I: DeserializeOwned> Stream for WebSocketReceiver<I> {
+    type Item = Result<I, WebSocketError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let message = match ready!(self.messages.poll_next_unpin(cx)) {
+                Some(Ok(message)) => message,
+                Some(Err(error)) => return Poll::Ready(Some(Err(WebSocketError::connection(error)))),
+                None => return Poll::Ready(None),
+            };
+            let message = match message {
+                AggregatedMessage::Text(text) => decode_ws_message(text.as_bytes()),
+                AggregatedMessage::Binary(bytes) => decode_ws_message(&bytes),
+                AggregatedMessage::Close(_) => {
+                    let _ = self.outgoing.unbounded_send(Outgoing::Close);
+                    return Poll::Ready(None);
+                }
+                AggregatedMessage::Ping(payload) => {
+                    let _ = self.outgoing.unbounded_send(Outgoing::Pong(payload));
+                    continue;
+                }
+                AggregatedMessage::Pong(_) => continue,
+            };
+            return Poll::Ready(Some(message.map_err(WebSocketError::from)));
+        }
+    }
+}
+
+/// Sends the frames to the client until the connection is closed, by either side,
+/// or until the handler drops both the sender and the receiver.
+async fn send_frames(mut session: Session, mut outgoing: UnboundedReceiver<Outgoing>) {
+    while let Some(frame) = outgoing.next().await {
+        let sent = match frame {
+            Outgoing::Text(text) => session.text(text).await,
+            Outgoing::Pong(payload) => session.pong(&payload).await,
+            Outgoing::Close => break,
+        };
+        if sent.is_err() {
+            return;
+        }
+    }
+    let _ = session.close(None).await;
+}
+
+/// Upgrades the connection and returns the typed sender and receiver given to the handler.
+fn upgrade<I, O>(
+    req: &HttpRequest,
+    body: Payload,
+) -> Result<(::actix_web::HttpResponse, WebSocketSender<O>, WebSocketReceiver<I>), ::actix_web::Error> {
+    let (response, session, messages) = actix_ws::handle(req, body)?;
+    let (outgoing, frames) = mpsc::unbounded();
+    ::actix_web::rt::spawn(send_frames(session, frames));
+
+    let sender = WebSocketSender { outgoing: outgoing.clone(), phantom: PhantomData };
+    let receiver = WebSocketReceiver { messages: messages.aggregate_continuations(), outgoing, phantom: PhantomData };
+    Ok((response, sender, receiver))
+}
+
+macro_rules! websocket_factory_tuple ({ $($param:ident)* } => {
+    #[allow(non_snake_case)]
+    impl <I: Serialize + DeserializeOwned + 'static, O: Serialize + DeserializeOwned + 'static, H, Fut, $($param,)*> AjarsServerActixWebWebSocketHandler<I, O, (WithoutPathParams, ($($param,)*)), H>
+    for RestWebSocket<I, O, ()>
+where
+H: Clone + 'static + Fn(WebSocketSender<O>, WebSocketReceiver<I>, $($param,)*) -> Fut,
+Fut: Future<Output = ()> + 'static,
+$( $param: FromRequest + 'static, )*
+{
+    fn to(&self, handler: H) -> Resource {
+        let rest = self.rest();
+        web::resource::<&str>(rest.path()).route(web::route().method(method(rest.method())).to(
+            move |req: HttpRequest, body: Payload, $( $param: $param,)*| {
+            let response = upgrade(&req, body).map(|(response, sender, receiver)| {
+                ::actix_web::rt::spawn((handler)(sender, receiver, $($param,)*));
+                response
+            });
+            async move { response }
+        }))
+    }
+}
+
+    #[allow(non_snake_case)]
+    impl <I: Serialize + DeserializeOwned + 'static, O: Serialize + DeserializeOwned + 'static, P: Serialize + DeserializeOwned + 'static, H, Fut, $($param,)*> AjarsServerActixWebWebSocketHandler<I, O, (WithPathParams, ($($param,)*)), H>
+    for RestWebSocket<I, O, P>
+where
+H: Clone + 'static + Fn(WebSocketSender<O>, WebSocketReceiver<I>, P, $($param,)*) -> Fut,
+Fut: Future<Output = ()> + 'static,
+$( $param: FromRequest + 'static, )*
+{
+    fn to(&self, handler: H) -> Resource {
+        let rest = self.rest();
+        web::resource::<&str>(rest.path()).route(web::route().method(method(rest.method())).to(
+            move |req: HttpRequest, body: Payload, path: Path<P>, $( $param: $param,)*| {
+            let response = upgrade(&req, body).map(|(response, sender, receiver)| {
+                ::actix_web::rt::spawn((handler)(sender, receiver, path.into_inner(), $($param,)*));
+                response
+            });
+            async move { response }
+        }))
+    }
+}
+});
+
+websocket_factory_tuple! {}
+websocket_factory_tuple! { P0 }
+websocket_factory_tuple! { P0 P1 }
+websocket_factory_tuple! { P0 P1 P2 }
+websocket_factory_tuple! { P0 P1 P2 P3 }
+websocket_factory_tuple! { P0 P1 P2 P3 P4 }
+websocket_factory_tuple! { P0 P1 P2 P3 P4 P5 }
+websocket_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 }
+websocket_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 }
+websocket_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 }
+websocket_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 P9 }
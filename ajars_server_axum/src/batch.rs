@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use ::axum::Router;
+use ::axum::http::{HeaderMap, StatusCode};
+use ::axum::response::IntoResponse;
+use ::axum::routing::post;
+use ajars_core::{BatchCall, BatchContext, BatchHandlers, Json, RestBatch, RestType, TOO_MANY_CALLS_STATUS};
+
+use crate::Encoded;
+
+pub trait AjarsServerAxumBatchHandler<S> {
+    /// Mounts the batch endpoint, that dispatches its calls to the given handlers
+    /// along with the headers of the batch request.
+    /// Only the middleware of the batch route runs, not the one of the routes of the endpoints called.
+    fn to(&self, handlers: BatchHandlers) -> Router<S>;
+}
+
+impl<S: Clone + Send + Sync + 'static> AjarsServerAxumBatchHandler<S> for RestBatch {
+    fn to(&self, handlers: BatchHandlers) -> Router<S> {
+        let handlers = Arc::new(handlers);
+        let route = post(move |headers: HeaderMap, calls: Encoded<Json, Vec<BatchCall>>| {
+            let handlers = handlers.clone();
+            async move {
+                match handlers.dispatch(batch_context(&headers), calls.0).await {
+                    Ok(results) => Encoded::<Json, _>::new(results).into_response(),
+                    Err(error) => {
+                        let status = StatusCode::from_u16(TOO_MANY_CALLS_STATUS).expect("The status should be valid");
+                        (status, error.to_string()).into_response()
+                    }
+                }
+            }
+        });
+        Router::new().route(self.rest().path(), route)
+    }
+}
+
+/// The context of the calls of a batch: the headers of its request whose values are valid strings.
+fn batch_context(headers: &HeaderMap) -> BatchContext {
+    BatchContext::new(
+        headers
+            .iter()
+            .filter_map(|(name, value)| value.to_str().ok().map(|value| (name.to_string(), value.to_owned())))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use ::axum::body::Body;
+    use ::axum::http::{Request, StatusCode, header};
+    use ajars_core::{BatchResult, NoError, Rest};
+    use http_body_util::BodyExt; // for `collect`
+    use serde_json::json;
+    use tower::ServiceExt; // for `oneshot`
+
+    use super::*;
+
+    const BATCH: RestBatch = RestBatch::post("/api/batch");
+    const DOUBLE: Rest<u64, u64> = Rest::get("/api/double");
+
+    #[tokio::test]
+    async fn should_answer_every_call_of_the_batch() {
+        // Arrange
+        let handlers = BatchHandlers::new().endpoint(&DOUBLE, |input: u64| async move { Ok::<_, NoError>(input * 2) });
+        let app: Router = BATCH.to(handlers);
+        let calls = json!([{ "endpoint": "GET /api/double", "input": 21 }, { "endpoint": "GET /api/unknown" }]);
+
+        // Act
+        let request = Request::post("/api/batch")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(calls.to_string()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(StatusCode::OK, response.status());
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let results: Vec<BatchResult> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(2, results.len());
+        assert_eq!(BatchResult { status: 200, body: json!(42) }, results[0]);
+        assert_eq!(404, results[1].status);
+    }
+
+    #[tokio::test]
+    async fn should_pass_the_headers_of_the_batch_request_to_the_handlers() {
+        // Arrange
+        const WHOAMI: Rest<(), String> = Rest::get("/api/whoami");
+        let handlers =
+            BatchHandlers::new().endpoint_with_context(&WHOAMI, |context: BatchContext, _: (), _: ()| async move {
+                Ok::<_, NoError>(context.header("Authorization").unwrap_or_default().to_owned())
+            });
+        let app: Router = BATCH.to(handlers);
+        let calls = json!([{ "endpoint": "GET /api/whoami" }]);
+
+        // Act
+        let request = Request::post("/api/batch")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::AUTHORIZATION, "Bearer token")
+            .body(Body::from(calls.to_string()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let results: Vec<BatchResult> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(vec![BatchResult { status: 200, body: json!("Bearer token") }], results);
+    }
+
+    #[tokio::test]
+    async fn should_reject_a_batch_with_too_many_calls() {
+        // Arrange
+        let handlers = BatchHandlers::new()
+            .endpoint(&DOUBLE, |input: u64| async move { Ok::<_, NoError>(input * 2) })
+            .with_max_calls(1);
+        let app: Router = BATCH.to(handlers);
+        let calls =
+            json!([{ "endpoint": "GET /api/double", "input": 1 }, { "endpoint": "GET /api/double", "input": 2 }]);
+
+        // Act
+        let request = Request::post("/api/batch")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(calls.to_string()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, response.status());
+    }
+}
@@ -0,0 +1,326 @@
+use std::future::Future;
+use std::io::SeekFrom;
+
+use ::axum::body::{Body, Bytes};
+use ::axum::extract::{self, FromRequestParts};
+use ::axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use ::axum::response::{IntoResponse, Response};
+use ::axum::routing::on;
+use ::axum::{BoxError, Router};
+use ajars_core::{ContentRange, Download, DownloadBody, InputLocation, Json, RestDownload, RestType, resolve_range};
+use futures_util::{TryStreamExt, stream};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+use crate::{
+    Encoded, ErrorResponse, InputValidation, UrlEncoded, WithPathParams, WithoutPathParams, error_status, method_filter,
+};
+
+/// The size of the chunks read from the files of the downloads.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+pub trait AjarsServerAxumDownloadHandler<I: Serialize + DeserializeOwned, T, H, S> {
+    fn to(&self, handler: H) -> Router<S>;
+}
+
+/// Sends the content of a download, or the part of it asked by the `Range` header of the request.
+async fn download_response(headers: &HeaderMap, download: Download) -> Response {
+    let range = headers.get(header::RANGE).and_then(|range| range.to_str().ok());
+    let mut response_headers = HeaderMap::new();
+    if let Ok(content_type) = HeaderValue::from_str(download.content_type()) {
+        response_headers.insert(header::CONTENT_TYPE, content_type);
+    }
+    if let Some(disposition) = download.content_disposition().and_then(|value| HeaderValue::from_str(value).ok()) {
+        response_headers.insert(header::CONTENT_DISPOSITION, disposition);
+    }
+
+    match download.into_body() {
+        DownloadBody::Bytes(bytes) => {
+            response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+            match resolve_range(range, bytes.len() as u64) {
+                Ok(None) => (response_headers, bytes).into_response(),
+                Ok(Some(range)) => {
+                    let part = bytes[range.start as usize..=range.end as usize].to_vec();
+                    partial_response(response_headers, range, Body::from(part))
+                }
+                Err(not_satisfiable) => not_satisfiable_response(not_satisfiable.to_string()),
+            }
+        }
+        DownloadBody::File(path) => {
+            response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+            let failure = |err: std::io::Error| {
+                (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to read the file of the download: {}", err))
+                    .into_response()
+            };
+            let result = async {
+                let mut file = tokio::fs::File::open(&path).await?;
+                let size = file.metadata().await?.len();
+                let range = match resolve_range(range, size) {
+                    Ok(range) => range,
+                    Err(not_satisfiable) => return Ok(not_satisfiable_response(not_satisfiable.to_string())),
+                };
+                let (start, len) = range.map_or((0, size), |range| (range.start, range.len()));
+                file.seek(SeekFrom::Start(start)).await?;
+                response_headers.insert(header::CONTENT_LENGTH, HeaderValue::from(len));
+                let body = Body::from_stream(read_chunks(file, len));
+                Ok(match range {
+                    Some(range) => partial_response(response_headers, range, body),
+                    None => (response_headers, body).into_response(),
+                })
+            };
+            result.await.unwrap_or_else(failure)
+        }
+        DownloadBody::Stream(chunks) => {
+            // The size of a stream is unknown, so it is always sent as a whole
+            (response_headers, Body::from_stream(chunks.map_ok(Bytes::from).map_err(BoxError::from))).into_response()
+        }
+    }
+}
+
+/// Streams `len` bytes of the file, from its current position.
+fn read_chunks(
+    file: tokio::fs::File,
+    len: u64,
+) -> impl futures_util::Stream<Item = std::io::Result<Bytes>> + Send + 'static {
+    stream::try_unfold((file.take(len), vec![0; CHUNK_SIZE]), |(mut file, mut buffer)| async move {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        let chunk = Bytes::copy_from_slice(&buffer[..read]);
+        Ok(Some((chunk, (file, buffer))))
+    })
+}
+
+fn partial_response(mut headers: HeaderMap, range: ContentRange, body: Body) -> Response {
+    if let Ok(content_range) = HeaderValue::from_str(&range.to_string()) {
+        headers.insert(header::CONTENT_RANGE, content_range);
+    }
+    (StatusCode::PARTIAL_CONTENT, headers, body).into_response()
+}
+
+fn not_satisfiable_response(content_range: String) -> Response {
+    (StatusCode::RANGE_NOT_SATISFIABLE, [(header::CONTENT_RANGE, content_range)]).into_response()
+}
+
+macro_rules! download_factory_tuple ({ $($param:ident)* } => {
+    #[allow(non_snake_case)]
+    impl <I, E, H, R, S, $($param,)*> AjarsServerAxumDownloadHandler<I, (WithoutPathParams, ($($param,)*)), H, S>
+    for RestDownload<I, (), E>
+    where
+    I: Serialize + DeserializeOwned + Send + 'static,
+    E: Serialize + DeserializeOwned + Send + 'static,
+    R: Future<Output = Result<Download, E>> + Send,
+    S: Clone + Send + Sync + 'static,
+    H: 'static + Send + Sync + Clone + Fn($($param,)* I) -> R,
+    $( $param: FromRequestParts<S> + Send + 'static, )*
+    {
+        fn to(&self, handler: H) -> Router<S> {
+            let rest = self.rest();
+            let filter = method_filter(rest.method());
+            let validation = InputValidation::<Json, I>::of(rest);
+            let status = error_status(rest);
+            let route = match rest.input_location() {
+                InputLocation::Query => on(filter,
+                    move |$( $param: $param,)* headers: HeaderMap, payload: extract::Query<I>| async move {
+                        validation.check(&payload.0)?;
+                        match (handler)($( $param,)* payload.0).await {
+                            Ok(download) => Ok::<_, Response>(download_response(&headers, download).await),
+                            Err(error) => Ok(ErrorResponse(status, Encoded::<Json, _>::new(error)).into_response()),
+                        }
+                    }),
+                InputLocation::Body => on(filter,
+                    move |$( $param: $param,)* headers: HeaderMap, payload: Encoded<Json, I>| async move {
+                        validation.check(&payload.0)?;
+                        match (handler)($( $param,)* payload.0).await {
+                            Ok(download) => Ok::<_, Response>(download_response(&headers, download).await),
+                            Err(error) => Ok(ErrorResponse(status, Encoded::<Json, _>::new(error)).into_response()),
+                        }
+                    }),
+                InputLocation::Form => on(filter,
+                    move |$( $param: $param,)* headers: HeaderMap, payload: Encoded<UrlEncoded, I>| async move {
+                        validation.check(&payload.0)?;
+                        match (handler)($( $param,)* payload.0).await {
+                            Ok(download) => Ok::<_, Response>(download_response(&headers, download).await),
+                            Err(error) => Ok(ErrorResponse(status, Encoded::<Json, _>::new(error)).into_response()),
+                        }
+                    }),
+            };
+
+            Router::new().route(rest.path(), route)
+        }
+    }
+
+    #[allow(non_snake_case)]
+    impl <I, P, E, H, R, S, $($param,)*> AjarsServerAxumDownloadHandler<I, (WithPathParams, ($($param,)*)), H, S>
+    for RestDownload<I, P, E>
+    where
+    I: Serialize + DeserializeOwned + Send + 'static,
+    P: Serialize + DeserializeOwned + Send + 'static,
+    E: Serialize + DeserializeOwned + Send + 'static,
+    R: Future<Output = Result<Download, E>> + Send,
+    S: Clone + Send + Sync + 'static,
+    H: 'static + Send + Sync + Clone + Fn($($param,)* P, I) -> R,
+    $( $param: FromRequestParts<S> + Send + 'static, )*
+    {
+        fn to(&self, handler: H) -> Router<S> {
+            let rest = self.rest();
+            let filter = method_filter(rest.method());
+            let validation = InputValidation::<Json, I>::of(rest);
+            let status = error_status(rest);
+            let route = match rest.input_location() {
+                InputLocation::Query => on(filter,
+                    move |$( $param: $param,)* headers: HeaderMap, path: extract::Path<P>, payload: extract::Query<I>| async move {
+                        validation.check(&payload.0)?;
+                        match (handler)($( $param,)* path.0, payload.0).await {
+                            Ok(download) => Ok::<_, Response>(download_response(&headers, download).await),
+                            Err(error) => Ok(ErrorResponse(status, Encoded::<Json, _>::new(error)).into_response()),
+                        }
+                    }),
+                InputLocation::Body => on(filter,
+                    move |$( $param: $param,)* headers: HeaderMap, path: extract::Path<P>, payload: Encoded<Json, I>| async move {
+                        validation.check(&payload.0)?;
+                        match (handler)($( $param,)* path.0, payload.0).await {
+                            Ok(download) => Ok::<_, Response>(download_response(&headers, download).await),
+                            Err(error) => Ok(ErrorResponse(status, Encoded::<Json, _>::new(error)).into_response()),
+                        }
+                    }),
+                InputLocation::Form => on(filter,
+                    move |$( $param: $param,)* headers: HeaderMap, path: extract::Path<P>, payload: Encoded<UrlEncoded, I>| async move {
+                        validation.check(&payload.0)?;
+                        match (handler)($( $param,)* path.0, payload.0).await {
+                            Ok(download) => Ok::<_, Response>(download_response(&headers, download).await),
+                            Err(error) => Ok(ErrorResponse(status, Encoded::<Json, _>::new(error)).into_response()),
+                        }
+                    }),
+            };
+
+            Router::new().route(rest.path(), route)
+        }
+    }
+});
+
+download_factory_tuple! {}
+download_factory_tuple! { P0 }
+download_factory_tuple! { P0 P1 }
+download_factory_tuple! { P0 P1 P2 }
+download_factory_tuple! { P0 P1 P2 P3 }
+download_factory_tuple! { P0 P1 P2 P3 P4 }
+download_factory_tuple! { P0 P1 P2 P3 P4 P5 }
+download_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 }
+download_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 }
+download_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 }
+download_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 P9 }
+
+#[cfg(test)]
+mod tests {
+
+    use ::axum::http::{Method, Request};
+    use http_body_util::BodyExt; // for `collect`
+    use serde::{Deserialize, Serialize};
+    use tower::ServiceExt; // for `oneshot`
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct ReportRequest {
+        pub source: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct ReportError {
+        pub reason: String,
+    }
+
+    const REPORT: RestDownload<ReportRequest, (), ReportError> =
+        RestDownload::get("/api/report").with_error_status(404);
+
+    async fn report(request: ReportRequest) -> Result<Download, ReportError> {
+        match request.source.as_str() {
+            "bytes" => Ok(Download::bytes("text/plain", "0123456789").attachment("report.txt")),
+            "file" => {
+                let path = std::env::temp_dir().join(format!("ajars-axum-report-{}.txt", rand::random::<u64>()));
+                std::fs::write(&path, "0123456789").unwrap();
+                Ok(Download::file("text/plain", path))
+            }
+            "stream" => {
+                Ok(Download::stream("text/plain", stream::iter(vec![Ok(b"01234".to_vec()), Ok(b"56789".to_vec())])))
+            }
+            _ => Err(ReportError { reason: "Unknown source".to_owned() }),
+        }
+    }
+
+    async fn call(uri: &str, range: Option<&str>) -> Response {
+        let app: Router = REPORT.to(report);
+        let mut request = Request::builder().method(Method::GET).uri(uri);
+        if let Some(range) = range {
+            request = request.header(header::RANGE, range);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    async fn body(response: Response) -> String {
+        String::from_utf8(response.into_body().collect().await.unwrap().to_bytes().to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_send_the_whole_content_with_its_headers() {
+        // Act
+        let response = call("/api/report?source=bytes", None).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!("text/plain", response.headers().get(header::CONTENT_TYPE).unwrap());
+        assert_eq!("bytes", response.headers().get(header::ACCEPT_RANGES).unwrap());
+        assert_eq!(
+            "attachment; filename=\"report.txt\"; filename*=UTF-8''report.txt",
+            response.headers().get(header::CONTENT_DISPOSITION).unwrap()
+        );
+        assert_eq!("0123456789", body(response).await);
+    }
+
+    #[tokio::test]
+    async fn should_send_the_range_of_the_content() {
+        for source in ["bytes", "file"] {
+            // Act
+            let response = call(&format!("/api/report?source={}", source), Some("bytes=4-")).await;
+
+            // Assert
+            assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+            assert_eq!("bytes 4-9/10", response.headers().get(header::CONTENT_RANGE).unwrap());
+            assert_eq!("456789", body(response).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn should_not_satisfy_a_range_after_the_end_of_the_content() {
+        // Act
+        let response = call("/api/report?source=file", Some("bytes=10-")).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!("bytes */10", response.headers().get(header::CONTENT_RANGE).unwrap());
+    }
+
+    #[tokio::test]
+    async fn should_send_a_stream_as_a_whole() {
+        // Act
+        let response = call("/api/report?source=stream", Some("bytes=4-")).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!("0123456789", body(response).await);
+    }
+
+    #[tokio::test]
+    async fn should_send_the_typed_error() {
+        // Act
+        let response = call("/api/report?source=none", None).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let error: ReportError = serde_json::from_str(&body(response).await).unwrap();
+        assert_eq!("Unknown source", error.reason);
+    }
+}
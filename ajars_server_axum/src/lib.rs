@@ -1,13 +1,37 @@
 use std::future::Future;
+use std::marker::PhantomData;
 
-use ::axum::extract::{self, FromRequestParts};
-use ::axum::response::IntoResponse;
-use ::axum::routing::{delete, get, post, put};
-use ::axum::{Json, Router};
-use ajars_core::{HttpMethod, RestType};
+use ::axum::Router;
+use ::axum::body::Bytes;
+use ::axum::extract::{self, FromRequest, FromRequestParts, Request};
+use ::axum::http::{StatusCode, header};
+use ::axum::response::{IntoResponse, Response};
+use ::axum::routing::{MethodFilter, on};
+use ajars_core::{
+    ApiGroup, Codec, CodecError, EndpointDescriptor, HttpMethod, InputLocation, NoError, RestType, ScopedRest,
+    empty_body_output, is_empty_body,
+};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 
+mod batch;
+mod download;
+mod sse;
+mod stream;
+#[cfg(feature = "upload")]
+mod upload;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+pub use batch::AjarsServerAxumBatchHandler;
+pub use download::AjarsServerAxumDownloadHandler;
+pub use sse::{AjarsServerAxumSseHandler, LastEventId};
+pub use stream::AjarsServerAxumStreamHandler;
+#[cfg(feature = "upload")]
+pub use upload::AjarsServerAxumUploadHandler;
+#[cfg(feature = "websocket")]
+pub use websocket::{AjarsServerAxumWebSocketHandler, WebSocketReceiver, WebSocketSender};
+
 pub mod axum {
     pub use ::axum::*;
 }
@@ -16,9 +40,257 @@ pub trait AjarsServerAxumHandler<I: Serialize + DeserializeOwned, O: Serialize +
     fn to(&self, handler: H) -> Router<S>;
 }
 
+/// Mounts all the endpoints of an [`ApiGroup`] on a single router, at their full paths.
+pub struct AjarsAxumGroupRouter<'g, S> {
+    group: &'g ApiGroup,
+    router: Router<S>,
+    mounted: Vec<EndpointDescriptor>,
+}
+
+impl<'g, S: Clone + Send + Sync + 'static> AjarsAxumGroupRouter<'g, S> {
+    /// # Panics
+    ///
+    /// Panics if the endpoints of the group conflict with each other, see [`ApiGroup::validate`].
+    pub fn new(group: &'g ApiGroup) -> Self {
+        if let Err(error) = group.validate() {
+            panic!("{}", error);
+        }
+        Self { group, router: Router::new(), mounted: vec![] }
+    }
+
+    /// Mounts the handler of an endpoint of the group,
+    /// at every path of the endpoint when it was added to several sub-groups.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the endpoint is not part of the group.
+    pub fn route<'r, I, O, T, H, REST>(mut self, rest: &'r REST, handler: H) -> Self
+    where
+        I: Serialize + DeserializeOwned,
+        O: Serialize + DeserializeOwned,
+        H: Clone,
+        REST: RestType<I, O>,
+        ScopedRest<'r, REST>: AjarsServerAxumHandler<I, O, T, H, S> + RestType<I, O>,
+    {
+        let scopes = self.group.scopes(rest);
+        assert!(
+            !scopes.is_empty(),
+            "The endpoint [{:?} {}] is not part of the group [{}]",
+            rest.method(),
+            rest.path(),
+            self.group.prefix()
+        );
+        for scoped in scopes {
+            self.mounted.push(EndpointDescriptor::of(&scoped));
+            self.router = self.router.merge(scoped.to(handler.clone()));
+        }
+        self
+    }
+
+    /// Returns the router with all the endpoints of the group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an endpoint of the group, or of one of its sub-groups, has no handler.
+    pub fn build(self) -> Router<S> {
+        let missing = self
+            .group
+            .endpoints()
+            .into_iter()
+            .filter(|endpoint| !self.mounted.contains(endpoint))
+            .map(|endpoint| format!("{:?} {}", endpoint.method(), endpoint.path()))
+            .collect::<Vec<_>>();
+        assert!(
+            missing.is_empty(),
+            "The endpoints {:?} of the group [{}] have no handler",
+            missing,
+            self.group.prefix()
+        );
+        self.router
+    }
+}
+
+/// Marker that selects the handler signature of the endpoints without path parameters.
+pub struct WithoutPathParams;
+
+/// Marker that selects the handler signature of the endpoints with path parameters.
+/// Their handlers receive the path parameters right before the input.
+pub struct WithPathParams;
+
+/// Marker that selects the handlers of the endpoints without a typed error.
+/// Their handlers can fail with any error that implements `IntoResponse`.
+pub struct UntypedError;
+
+/// Marker that selects the handlers of the endpoints with a typed error.
+/// Their handlers fail with the error type of the endpoint, that is encoded with the codec of the endpoint
+/// and sent with the endpoint error status.
+pub struct TypedError;
+
+/// A request or response body encoded with the codec `C`
+struct Encoded<C, T>(T, PhantomData<C>);
+
+impl<C, T> Encoded<C, T> {
+    fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+}
+
+impl<C: Codec, T: DeserializeOwned, S: Send + Sync> FromRequest<S> for Encoded<C, T> {
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let content_type = req.headers().get(header::CONTENT_TYPE).and_then(|value| value.to_str().ok());
+        let matches_content_type = content_type.is_some_and(C::matches_content_type);
+
+        let body = Bytes::from_request(req, state).await.map_err(IntoResponse::into_response)?;
+
+        // An empty input, like `()`, is sent without a body
+        if body.is_empty()
+            && let Some(value) = empty_body_output()
+        {
+            return Ok(Self::new(value));
+        }
+
+        if !matches_content_type {
+            return Err((
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!("Expected request with `Content-Type: {}`", C::CONTENT_TYPE),
+            )
+                .into_response());
+        }
+
+        C::decode(&body).map(Self::new).map_err(|err| {
+            (StatusCode::BAD_REQUEST, format!("Failed to decode the request body: {}", err)).into_response()
+        })
+    }
+}
+
+impl<C: Codec, T: Serialize> IntoResponse for Encoded<C, T> {
+    fn into_response(self) -> Response {
+        // An empty value, like `()`, is sent without a body
+        if is_empty_body(&self.0) {
+            return ().into_response();
+        }
+        match C::encode(&self.0) {
+            Ok(body) => ([(header::CONTENT_TYPE, C::CONTENT_TYPE)], body).into_response(),
+            Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to encode the response body: {}", err))
+                .into_response(),
+        }
+    }
+}
+
+/// The `application/x-www-form-urlencoded` encoding of the inputs sent as forms
+struct UrlEncoded;
+
+impl Codec for UrlEncoded {
+    const CONTENT_TYPE: &'static str = "application/x-www-form-urlencoded";
+
+    fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, CodecError> {
+        serde_urlencoded::to_string(value).map(String::into_bytes).map_err(CodecError::new)
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CodecError> {
+        serde_urlencoded::from_bytes(bytes).map_err(CodecError::new)
+    }
+}
+
+/// The response that carries the output of an endpoint, sent with the success status of the endpoint
+struct SuccessResponse<C, O>(StatusCode, Encoded<C, O>);
+
+impl<C, O> SuccessResponse<C, O> {
+    fn new(status: StatusCode, output: O) -> Self {
+        Self(status, Encoded::new(output))
+    }
+}
+
+impl<C: Codec, O: Serialize> IntoResponse for SuccessResponse<C, O> {
+    fn into_response(self) -> Response {
+        // An empty output, like `()`, of an endpoint with the default success status is sent as '204 No Content'
+        if self.0 == StatusCode::OK && is_empty_body(&self.1.0) {
+            return StatusCode::NO_CONTENT.into_response();
+        }
+        (self.0, self.1).into_response()
+    }
+}
+
+/// The response that carries the typed error of an endpoint
+struct ErrorResponse<C, E>(StatusCode, Encoded<C, E>);
+
+impl<C: Codec, E: Serialize> IntoResponse for ErrorResponse<C, E> {
+    fn into_response(self) -> Response {
+        (self.0, self.1).into_response()
+    }
+}
+
+/// Validates the input of an endpoint before calling its handler.
+/// An input that fails the validation is answered with '422 Unprocessable Entity' and the validation errors,
+/// encoded with the codec `C`.
+struct InputValidation<C, I> {
+    #[cfg(feature = "validator")]
+    validator: ajars_core::InputValidator<I>,
+    phantom: PhantomData<fn(&I) -> C>,
+}
+
+impl<C, I> Clone for InputValidation<C, I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, I> Copy for InputValidation<C, I> {}
+
+impl<C: Codec, I: Serialize + DeserializeOwned> InputValidation<C, I> {
+    #[cfg_attr(not(feature = "validator"), allow(unused_variables))]
+    fn of<O: Serialize + DeserializeOwned, REST: RestType<I, O>>(rest: &REST) -> Self {
+        Self {
+            #[cfg(feature = "validator")]
+            validator: rest.input_validator(),
+            phantom: PhantomData,
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    #[cfg_attr(not(feature = "validator"), allow(unused_variables))]
+    fn check(&self, input: &I) -> Result<(), Response> {
+        #[cfg(feature = "validator")]
+        if let Err(errors) = (self.validator)(input) {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Encoded::<C, _>::new(errors)).into_response());
+        }
+        Ok(())
+    }
+}
+
+fn method_filter(method: &HttpMethod) -> MethodFilter {
+    match method {
+        HttpMethod::DELETE => MethodFilter::DELETE,
+        HttpMethod::GET => MethodFilter::GET,
+        HttpMethod::HEAD => MethodFilter::HEAD,
+        HttpMethod::OPTIONS => MethodFilter::OPTIONS,
+        HttpMethod::PATCH => MethodFilter::PATCH,
+        HttpMethod::POST => MethodFilter::POST,
+        HttpMethod::PUT => MethodFilter::PUT,
+    }
+}
+
+fn error_status<I, O, REST: RestType<I, O>>(rest: &REST) -> StatusCode
+where
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+{
+    StatusCode::from_u16(rest.error_status()).expect("The error status of the endpoint should be valid")
+}
+
+fn success_status<I, O, REST: RestType<I, O>>(rest: &REST) -> StatusCode
+where
+    I: Serialize + DeserializeOwned,
+    O: Serialize + DeserializeOwned,
+{
+    StatusCode::from_u16(rest.success_status()).expect("The success status of the endpoint should be valid")
+}
+
 macro_rules! factory_tuple ({ $($param:ident)* } => {
     #[allow(non_snake_case)]
-    impl <I, O, H, R, E, S, REST: RestType<I, O>, $($param,)*> AjarsServerAxumHandler<I, O, ($($param,)*), H, S>
+    impl <I, O, H, R, E, S, REST: RestType<I, O, PathParams = (), Error = NoError>, $($param,)*> AjarsServerAxumHandler<I, O, (WithoutPathParams, UntypedError, ($($param,)*)), H, S>
     for REST
     where
     I: Serialize + DeserializeOwned + Send + 'static,
@@ -30,26 +302,146 @@ macro_rules! factory_tuple ({ $($param:ident)* } => {
     $( $param: FromRequestParts<S> + Send + 'static, )*
     {
         fn to(&self, handler: H) -> Router<S> {
-            let route = match self.method() {
-                HttpMethod::DELETE => Router::new().route(self.path(), delete(
-                    |$( $param: $param,)* payload: extract::Query<I>| async move {
-                        (handler)($( $param,)* payload.0).await.map(Json)
-                })),
-                HttpMethod::GET => Router::new().route(self.path(), get(
-                    |$( $param: $param,)* payload: extract::Query<I>| async move {
-                        (handler)($( $param,)* payload.0).await.map(Json)
-                    })),
-                HttpMethod::POST => Router::new().route(self.path(), post(
-                    |$( $param: $param,)* payload: Json<I>| async move {
-                        (handler)($( $param,)* payload.0).await.map(Json)
-                    })),
-                HttpMethod::PUT => Router::new().route(self.path(), put(
-                    |$( $param: $param,)* payload: Json<I>| async move {
-                        (handler)($( $param,)* payload.0).await.map(Json)
-                    })),
+            let filter = method_filter(self.method());
+            let validation = InputValidation::<REST::Codec, I>::of(self);
+            let success = success_status(self);
+            let route = match self.input_location() {
+                InputLocation::Query => on(filter,
+                    move |$( $param: $param,)* payload: extract::Query<I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>((handler)($( $param,)* payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)))
+                    }),
+                InputLocation::Body => on(filter,
+                    move |$( $param: $param,)* payload: Encoded<REST::Codec, I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>((handler)($( $param,)* payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)))
+                    }),
+                InputLocation::Form => on(filter,
+                    move |$( $param: $param,)* payload: Encoded<UrlEncoded, I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>((handler)($( $param,)* payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)))
+                    }),
+            };
+
+            Router::new().route(self.path(), route)
+        }
+    }
+
+    #[allow(non_snake_case)]
+    impl <I, O, H, R, E, S, REST: RestType<I, O, Error = NoError>, $($param,)*> AjarsServerAxumHandler<I, O, (WithPathParams, UntypedError, ($($param,)*)), H, S>
+    for REST
+    where
+    I: Serialize + DeserializeOwned + Send + 'static,
+    O: Serialize + DeserializeOwned + Send + 'static,
+    REST::PathParams: Send + 'static,
+    R: Future<Output = Result<O, E>> + Send,
+    E: IntoResponse + Send + 'static,
+    S: Clone + Send + Sync + 'static,
+    H: 'static + Send + Sync + Clone + Fn($($param,)* REST::PathParams, I) -> R,
+    $( $param: FromRequestParts<S> + Send + 'static, )*
+    {
+        fn to(&self, handler: H) -> Router<S> {
+            let filter = method_filter(self.method());
+            let validation = InputValidation::<REST::Codec, I>::of(self);
+            let success = success_status(self);
+            let route = match self.input_location() {
+                InputLocation::Query => on(filter,
+                    move |$( $param: $param,)* path: extract::Path<REST::PathParams>, payload: extract::Query<I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>((handler)($( $param,)* path.0, payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)))
+                    }),
+                InputLocation::Body => on(filter,
+                    move |$( $param: $param,)* path: extract::Path<REST::PathParams>, payload: Encoded<REST::Codec, I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>((handler)($( $param,)* path.0, payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)))
+                    }),
+                InputLocation::Form => on(filter,
+                    move |$( $param: $param,)* path: extract::Path<REST::PathParams>, payload: Encoded<UrlEncoded, I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>((handler)($( $param,)* path.0, payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)))
+                    }),
+            };
+
+            Router::new().route(self.path(), route)
+        }
+    }
+
+    #[allow(non_snake_case)]
+    impl <I, O, H, R, S, REST: RestType<I, O, PathParams = ()>, $($param,)*> AjarsServerAxumHandler<I, O, (WithoutPathParams, TypedError, ($($param,)*)), H, S>
+    for REST
+    where
+    I: Serialize + DeserializeOwned + Send + 'static,
+    O: Serialize + DeserializeOwned + Send + 'static,
+    REST::Error: Send + 'static,
+    R: Future<Output = Result<O, REST::Error>> + Send,
+    S: Clone + Send + Sync + 'static,
+    H: 'static + Send + Sync + Clone + Fn($($param,)* I) -> R,
+    $( $param: FromRequestParts<S> + Send + 'static, )*
+    {
+        fn to(&self, handler: H) -> Router<S> {
+            let filter = method_filter(self.method());
+            let validation = InputValidation::<REST::Codec, I>::of(self);
+            let success = success_status(self);
+            let status = error_status(self);
+            let route = match self.input_location() {
+                InputLocation::Query => on(filter,
+                    move |$( $param: $param,)* payload: extract::Query<I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>((handler)($( $param,)* payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<REST::Codec, _>::new(error))))
+                    }),
+                InputLocation::Body => on(filter,
+                    move |$( $param: $param,)* payload: Encoded<REST::Codec, I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>((handler)($( $param,)* payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<REST::Codec, _>::new(error))))
+                    }),
+                InputLocation::Form => on(filter,
+                    move |$( $param: $param,)* payload: Encoded<UrlEncoded, I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>((handler)($( $param,)* payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<REST::Codec, _>::new(error))))
+                    }),
+            };
+
+            Router::new().route(self.path(), route)
+        }
+    }
+
+    #[allow(non_snake_case)]
+    impl <I, O, H, R, S, REST: RestType<I, O>, $($param,)*> AjarsServerAxumHandler<I, O, (WithPathParams, TypedError, ($($param,)*)), H, S>
+    for REST
+    where
+    I: Serialize + DeserializeOwned + Send + 'static,
+    O: Serialize + DeserializeOwned + Send + 'static,
+    REST::PathParams: Send + 'static,
+    REST::Error: Send + 'static,
+    R: Future<Output = Result<O, REST::Error>> + Send,
+    S: Clone + Send + Sync + 'static,
+    H: 'static + Send + Sync + Clone + Fn($($param,)* REST::PathParams, I) -> R,
+    $( $param: FromRequestParts<S> + Send + 'static, )*
+    {
+        fn to(&self, handler: H) -> Router<S> {
+            let filter = method_filter(self.method());
+            let validation = InputValidation::<REST::Codec, I>::of(self);
+            let success = success_status(self);
+            let status = error_status(self);
+            let route = match self.input_location() {
+                InputLocation::Query => on(filter,
+                    move |$( $param: $param,)* path: extract::Path<REST::PathParams>, payload: extract::Query<I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>((handler)($( $param,)* path.0, payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<REST::Codec, _>::new(error))))
+                    }),
+                InputLocation::Body => on(filter,
+                    move |$( $param: $param,)* path: extract::Path<REST::PathParams>, payload: Encoded<REST::Codec, I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>((handler)($( $param,)* path.0, payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<REST::Codec, _>::new(error))))
+                    }),
+                InputLocation::Form => on(filter,
+                    move |$( $param: $param,)* path: extract::Path<REST::PathParams>, payload: Encoded<UrlEncoded, I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>((handler)($( $param,)* path.0, payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<REST::Codec, _>::new(error))))
+                    }),
             };
 
-            route
+            Router::new().route(self.path(), route)
         }
     }
 });
@@ -57,37 +449,44 @@ macro_rules! factory_tuple ({ $($param:ident)* } => {
 //
 // MODEL FN USED FOR CREATING THE MACRO
 //
-// impl <I, O, H, R, E, S, REST: RestType<I, O>, P> AxumHandler<I, O, P, H, S>
+// The macro implements `AjarsServerAxumHandler` in four variants: with or without path params,
+// and with an untyped error (`NoError`) or the typed error of the endpoint. This is the variant
+// without path params and with an untyped error, for a single extractor `P`:
+//
+// impl <I, O, H, R, E, S, REST: RestType<I, O, PathParams = (), Error = NoError>, P> AjarsServerAxumHandler<I, O, (WithoutPathParams, UntypedError, (P,)), H, S>
 // for REST
 // where
 // I: Serialize + DeserializeOwned + Send + 'static,
 // O: Serialize + DeserializeOwned + Send + 'static,
 // R: Future<Output = Result<O, E>> + Send,
 // E: IntoResponse + Send + 'static,
-// H: 'static + Send + Sync + Clone + Fn(P, I) -> R,
 // S: Clone + Send + Sync + 'static,
+// H: 'static + Send + Sync + Clone + Fn(P, I) -> R,
 // P: FromRequestParts<S> + Send + 'static,
 // {
 //     fn to(&self, handler: H) -> Router<S> {
-//         let route = match self.method() {
-//             HttpMethod::DELETE => Router::new().route(self.path(), delete(
-//                 |p: P, payload: extract::Query<I>| async move {
-//                     (handler)(p, payload.0).await.map(Json)
-//             })),
-//             HttpMethod::GET => Router::new().route(self.path(), get(
-//                 |p: P, payload: extract::Query<I>| async move {
-//                     (handler)(p, payload.0).await.map(Json)
-//                 })),
-//             HttpMethod::POST => Router::new().route(self.path(), post(
-//                 |p: P, payload: extract::Json<I>| async move {
-//                     (handler)(p, payload.0).await.map(Json)
-//                 })),
-//             HttpMethod::PUT => Router::new().route(self.path(), put(
-//                 |p: P, payload: extract::Json<I>| async move {
-//                     (handler)(p, payload.0).await.map(Json)
-//                 })),
+//         let filter = method_filter(self.method());
+//         let validation = InputValidation::<REST::Codec, I>::of(self);
+//         let success = success_status(self);
+//         let route = match self.input_location() {
+//             InputLocation::Query => on(filter,
+//                 move |p: P, payload: extract::Query<I>| async move {
+//                     validation.check(&payload.0)?;
+//                     Ok::<_, Response>((handler)(p, payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)))
+//                 }),
+//             InputLocation::Body => on(filter,
+//                 move |p: P, payload: Encoded<REST::Codec, I>| async move {
+//                     validation.check(&payload.0)?;
+//                     Ok::<_, Response>((handler)(p, payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)))
+//                 }),
+//             InputLocation::Form => on(filter,
+//                 move |p: P, payload: Encoded<UrlEncoded, I>| async move {
+//                     validation.check(&payload.0)?;
+//                     Ok::<_, Response>((handler)(p, payload.0).await.map(|output| SuccessResponse::<REST::Codec, _>::new(success, output)))
+//                 }),
 //         };
-//         route
+//
+//         Router::new().route(self.path(), route)
 //     }
 // }
 
@@ -111,7 +510,7 @@ mod tests {
     use ::axum::body::Body;
     use ::axum::extract::{Extension, Query, State};
     use ::axum::http::{Method, Request, Response, StatusCode, header};
-    use ajars_core::RestFluent;
+    use ajars_core::{ApiGroup, Cbor, InputLocation, NoError, Rest, RestFluent};
     use http_body_util::BodyExt; // for `collect`
     use serde::{Deserialize, Serialize};
     use tower::ServiceExt; // for `call`, `oneshot`, and `ready`
@@ -128,6 +527,17 @@ mod tests {
         pub message: String,
     }
 
+    #[derive(Serialize, Deserialize, Debug)]
+    pub struct ItemPath {
+        pub id: u64,
+        pub name: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug)]
+    pub struct PingError {
+        pub reason: String,
+    }
+
     async fn ping(_data: State<()>, body: PingRequest) -> Result<PingResponse, ServerError> {
         Ok(PingResponse { message: body.message })
     }
@@ -162,7 +572,7 @@ mod tests {
                 Request::builder()
                     .method(Method::DELETE)
                     .header(header::CONTENT_TYPE, "application/json")
-                    .uri(&format!("{}?message={}", rest.path(), payload.message))
+                    .uri(format!("{}?message={}", rest.path(), payload.message))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -195,7 +605,71 @@ mod tests {
                 Request::builder()
                     .method(Method::GET)
                     .header(header::CONTENT_TYPE, "application/json")
-                    .uri(&format!("{}?message={}", rest.path(), payload.message))
+                    .uri(format!("{}?message={}", rest.path(), payload.message))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!("application/json", response.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap());
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: PingResponse = serde_json::from_slice(&body).unwrap();
+
+        // Assert
+        assert_eq!(body.message, payload.message);
+    }
+
+    #[tokio::test]
+    async fn should_create_a_head_endpoint() {
+        // Arrange
+        let rest = RestFluent::<PingRequest, ()>::head(format!("/api/something/{}", rand::random::<u64>()));
+
+        let app = rest.to(|body: PingRequest| async move {
+            assert!(body.message.starts_with("message"));
+            Result::<_, ServerError>::Ok(())
+        });
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        // Act
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::HEAD)
+                    .uri(format!("{}?message={}", rest.path(), payload.message))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_create_an_options_endpoint() {
+        // Arrange
+        let rest =
+            RestFluent::<PingRequest, PingResponse>::options(format!("/api/something/{}", rand::random::<u64>()));
+
+        let app = rest.to(ping).layer(Extension(()));
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        // Act
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::OPTIONS)
+                    .uri(format!("{}?message={}", rest.path(), payload.message))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -213,6 +687,39 @@ mod tests {
         assert_eq!(body.message, payload.message);
     }
 
+    #[tokio::test]
+    async fn should_create_a_patch_endpoint() {
+        // Arrange
+        let rest = RestFluent::<PingRequest, PingResponse>::patch(format!("/api/something/{}", rand::random::<u64>()));
+
+        let app = rest.to(ping).layer(Extension(()));
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        // Act
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::PATCH)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .uri(rest.path())
+                    .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!("application/json", response.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap());
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: PingResponse = serde_json::from_slice(&body).unwrap();
+
+        // Assert
+        assert_eq!(body.message, payload.message);
+    }
+
     #[tokio::test]
     async fn should_create_a_post_endpoint() {
         // Arrange
@@ -279,6 +786,391 @@ mod tests {
         assert_eq!(body.message, payload.message);
     }
 
+    #[tokio::test]
+    async fn should_create_an_endpoint_with_path_params() {
+        // Arrange
+        let rest = Rest::<PingRequest, PingResponse, ItemPath>::get("/api/items/{id}/{name}");
+
+        let app = rest
+            .to(|_: State<()>, path: ItemPath, body: PingRequest| async move {
+                Result::<_, ServerError>::Ok(PingResponse {
+                    message: format!("{}-{}-{}", path.id, path.name, body.message),
+                })
+            })
+            .with_state(());
+
+        let path = rest.path_with(&ItemPath { id: 12, name: "John Doe".to_owned() });
+
+        // Act
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(format!("{}?message=hello", path))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: PingResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.message, "12-John Doe-hello");
+    }
+
+    #[tokio::test]
+    async fn should_send_the_typed_error_with_the_error_status() {
+        // Arrange
+        let rest = Rest::<PingRequest, PingResponse, (), PingError>::post("/api/failing").with_error_status(409);
+
+        let app = rest
+            .to(|body: PingRequest| async move { Result::<PingResponse, _>::Err(PingError { reason: body.message }) });
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        // Act
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .uri(rest.path())
+                    .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!("application/json", response.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap());
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: PingError = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.reason, payload.message);
+    }
+
+    #[tokio::test]
+    async fn should_send_the_output_with_the_success_status() {
+        // Arrange
+        let created = Rest::<PingRequest, PingResponse>::post("/api/created").with_success_status(201);
+        let accepted = Rest::<PingRequest, ()>::post("/api/accepted").with_success_status(202);
+
+        let app = Router::new()
+            .merge(created.to(ping))
+            .merge(accepted.to(|_body: PingRequest| async move { Result::<(), ServerError>::Ok(()) }))
+            .with_state(());
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+        let request = |path: &str| {
+            Request::builder()
+                .method(Method::POST)
+                .header(header::CONTENT_TYPE, "application/json")
+                .uri(path)
+                .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+                .unwrap()
+        };
+
+        // Act
+        let created_response = app.clone().oneshot(request(created.path())).await.unwrap();
+        let accepted_response = app.oneshot(request(accepted.path())).await.unwrap();
+
+        // Assert
+        assert_eq!(created_response.status(), StatusCode::CREATED);
+        let body = created_response.into_body().collect().await.unwrap().to_bytes();
+        let body: PingResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.message, payload.message);
+
+        assert_eq!(accepted_response.status(), StatusCode::ACCEPTED);
+        assert!(accepted_response.into_body().collect().await.unwrap().to_bytes().is_empty());
+    }
+
+    #[cfg(feature = "validator")]
+    #[tokio::test]
+    async fn should_answer_unprocessable_entity_if_the_input_is_not_valid() {
+        // Arrange
+        #[derive(Serialize, Deserialize, Debug, validator::Validate)]
+        pub struct SignupRequest {
+            #[validate(length(min = 3))]
+            pub username: String,
+        }
+
+        let rest = Rest::<SignupRequest, (), (), NoError>::post("/api/signup").with_validation();
+
+        let app = rest.to(|_body: SignupRequest| async move { Result::<(), NoError>::Ok(()) });
+
+        // Act
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .uri(rest.path())
+                    .body(Body::from(serde_json::to_vec(&SignupRequest { username: "u".to_owned() }).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!("application/json", response.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap());
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: ajars_core::ValidationErrors = serde_json::from_slice(&body).unwrap();
+        assert!(body.field_errors().contains_key("username"));
+    }
+
+    #[tokio::test]
+    async fn should_encode_the_bodies_with_the_codec_of_the_endpoint() {
+        // Arrange
+        let rest = Rest::<PingRequest, PingResponse, (), NoError, Cbor>::post("/api/cbor");
+
+        let app = rest.to(ping).with_state(());
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        // Act
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .header(header::CONTENT_TYPE, "application/cbor")
+                    .uri(rest.path())
+                    .body(Body::from(Cbor::encode(&payload).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!("application/cbor", response.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap());
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: PingResponse = Cbor::decode(&body).unwrap();
+        assert_eq!(body.message, payload.message);
+    }
+
+    #[tokio::test]
+    async fn should_reject_a_body_with_a_different_content_type() {
+        // Arrange
+        let rest = Rest::<PingRequest, PingResponse, (), NoError, Cbor>::post("/api/cbor");
+
+        let app = rest.to(ping).with_state(());
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        // Act
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .uri(rest.path())
+                    .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[tokio::test]
+    async fn should_read_the_input_from_the_body_of_a_delete_endpoint() {
+        // Arrange
+        let rest =
+            Rest::<PingRequest, PingResponse>::delete("/api/delete_with_body").with_input_location(InputLocation::Body);
+
+        let app = rest.to(ping).with_state(());
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        // Act
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .uri(rest.path())
+                    .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: PingResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.message, payload.message);
+    }
+
+    #[tokio::test]
+    async fn should_read_the_input_from_the_query_string_of_a_post_endpoint() {
+        // Arrange
+        let rest =
+            Rest::<PingRequest, PingResponse>::post("/api/post_with_query").with_input_location(InputLocation::Query);
+
+        let app = rest.to(ping).with_state(());
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        // Act
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(format!("{}?message={}", rest.path(), payload.message))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: PingResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.message, payload.message);
+    }
+
+    #[tokio::test]
+    async fn should_read_the_input_from_a_form_body() {
+        // Arrange
+        let rest = Rest::<PingRequest, PingResponse>::post("/api/form").with_input_location(InputLocation::Form);
+
+        let app = rest.to(ping).with_state(());
+
+        let payload = PingRequest { message: format!("message{}", rand::random::<u64>()) };
+
+        // Act
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                    .uri(rest.path())
+                    .body(Body::from(format!("message={}", payload.message)))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: PingResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.message, payload.message);
+    }
+
+    #[tokio::test]
+    async fn should_accept_a_unit_input_without_body_and_answer_no_content() {
+        for location in [InputLocation::Query, InputLocation::Body, InputLocation::Form] {
+            // Arrange
+            let rest = Rest::<(), ()>::post("/api/unit").with_input_location(location);
+
+            let app = rest.to(|_: ()| async move { Result::<_, ServerError>::Ok(()) });
+
+            // Act
+            let response = app
+                .oneshot(Request::builder().method(Method::POST).uri(rest.path()).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+
+            // Assert
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+            assert!(response.headers().get(header::CONTENT_TYPE).is_none());
+
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            assert!(body.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn should_mount_all_the_endpoints_of_a_group() {
+        // Arrange
+        const GET_PING: Rest<PingRequest, PingResponse> = Rest::get("/ping");
+        const POST_PING: Rest<PingRequest, PingResponse> = Rest::post("/ping");
+        const GET_ITEM: Rest<PingRequest, PingResponse, u64> = Rest::get("/items/{id}");
+
+        let group = ApiGroup::new("/api")
+            .endpoint(&GET_PING)
+            .endpoint(&POST_PING)
+            .group(ApiGroup::new("/v1").endpoint(&GET_ITEM))
+            .group(ApiGroup::new("/v2").endpoint(&GET_ITEM));
+
+        let app = AjarsAxumGroupRouter::new(&group)
+            .route(&GET_PING, |body: PingRequest| async move {
+                Result::<_, ServerError>::Ok(PingResponse { message: format!("get {}", body.message) })
+            })
+            .route(&POST_PING, |body: PingRequest| async move {
+                Result::<_, ServerError>::Ok(PingResponse { message: format!("post {}", body.message) })
+            })
+            .route(&GET_ITEM, |id: u64, body: PingRequest| async move {
+                Result::<_, ServerError>::Ok(PingResponse { message: format!("{} {}", id, body.message) })
+            })
+            .build();
+
+        let requests = vec![
+            (Request::get("/api/ping?message=a").body(Body::empty()).unwrap(), "get a"),
+            (
+                Request::post("/api/ping")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(r#"{"message":"b"}"#))
+                    .unwrap(),
+                "post b",
+            ),
+            (Request::get("/api/v1/items/7?message=c").body(Body::empty()).unwrap(), "7 c"),
+            (Request::get("/api/v2/items/8?message=c").body(Body::empty()).unwrap(), "8 c"),
+        ];
+
+        for (request, expected) in requests {
+            // Act
+            let response = app.clone().oneshot(request).await.unwrap();
+
+            // Assert
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let body: PingResponse = serde_json::from_slice(&body).unwrap();
+            assert_eq!(expected, body.message);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "The endpoints [GET /api/ping/{id}] and [POST /api/ping/{name}] have path templates")]
+    fn should_panic_if_the_endpoints_of_a_group_conflict() {
+        const GET_PING: Rest<PingRequest, PingResponse, u64> = Rest::get("/ping/{id}");
+        const POST_PING: Rest<PingRequest, PingResponse, String> = Rest::post("/ping/{name}");
+
+        let group = ApiGroup::new("/api").endpoint(&GET_PING).endpoint(&POST_PING);
+
+        AjarsAxumGroupRouter::<()>::new(&group);
+    }
+
+    #[test]
+    #[should_panic(expected = "have no handler")]
+    fn should_panic_if_an_endpoint_of_a_group_has_no_handler() {
+        const GET_PING: Rest<PingRequest, PingResponse> = Rest::get("/ping");
+        const POST_PING: Rest<PingRequest, PingResponse> = Rest::post("/ping");
+
+        let group = ApiGroup::new("/api").endpoint(&GET_PING).endpoint(&POST_PING);
+
+        let _: Router<()> = AjarsAxumGroupRouter::new(&group)
+            .route(&GET_PING, |body: PingRequest| async move {
+                Result::<_, ServerError>::Ok(PingResponse { message: body.message })
+            })
+            .build();
+    }
+
     #[tokio::test]
     async fn route_should_accept_variable_number_of_params() {
         // Arrange
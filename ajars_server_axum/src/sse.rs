@@ -0,0 +1,213 @@
+use std::convert::Infallible;
+
+use ::axum::Router;
+use ::axum::extract::{self, FromRequestParts};
+use ::axum::http::request::Parts;
+use ::axum::http::{HeaderValue, StatusCode, header};
+use ::axum::response::Response;
+use ::axum::routing::on;
+use ajars_core::{Json, LAST_EVENT_ID_HEADER, RestSse, RestType, SSE_CONTENT_TYPE, SseEvent, encode_sse_event};
+use futures_util::Stream;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::stream::streaming_response;
+use crate::{InputValidation, WithPathParams, WithoutPathParams, error_status, method_filter};
+
+pub trait AjarsServerAxumSseHandler<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, T, H, S> {
+    fn to(&self, handler: H) -> Router<S>;
+}
+
+/// The ID of the last event received by a client that reconnects to a Server-Sent Events endpoint,
+/// taken from the `Last-Event-ID` header; the handler can use it to resume the stream after that event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LastEventId(pub Option<String>);
+
+impl<S: Send + Sync> FromRequestParts<S> for LastEventId {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let id = parts.headers.get(LAST_EVENT_ID_HEADER).and_then(|id| id.to_str().ok()).map(ToOwned::to_owned);
+        Ok(Self(id))
+    }
+}
+
+/// Sends the events of the stream in the `text/event-stream` format.
+async fn sse_response<O, E, St>(error_status: StatusCode, events: St) -> Response
+where
+    O: Serialize + Send + 'static,
+    E: Serialize + Send + 'static,
+    St: Stream<Item = Result<SseEvent<O>, E>> + Send + 'static,
+{
+    let mut response = streaming_response(error_status, SSE_CONTENT_TYPE, encode_sse_event, events).await;
+    if response.status().is_success() {
+        response.headers_mut().insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    }
+    response
+}
+
+macro_rules! sse_factory_tuple ({ $($param:ident)* } => {
+    #[allow(non_snake_case)]
+    impl <I, O, E, H, St, S, $($param,)*> AjarsServerAxumSseHandler<I, O, (WithoutPathParams, ($($param,)*)), H, S>
+    for RestSse<I, O, (), E>
+    where
+    I: Serialize + DeserializeOwned + Send + 'static,
+    O: Serialize + DeserializeOwned + Send + 'static,
+    E: Serialize + DeserializeOwned + Send + 'static,
+    St: Stream<Item = Result<SseEvent<O>, E>> + Send + 'static,
+    S: Clone + Send + Sync + 'static,
+    H: 'static + Send + Sync + Clone + Fn($($param,)* I) -> St,
+    $( $param: FromRequestParts<S> + Send + 'static, )*
+    {
+        fn to(&self, handler: H) -> Router<S> {
+            let rest = self.rest();
+            let validation = InputValidation::<Json, I>::of(rest);
+            let status = error_status(rest);
+            let route = on(method_filter(rest.method()),
+                move |$( $param: $param,)* payload: extract::Query<I>| async move {
+                    validation.check(&payload.0)?;
+                    Ok::<_, Response>(sse_response(status, (handler)($( $param,)* payload.0)).await)
+                });
+
+            Router::new().route(rest.path(), route)
+        }
+    }
+
+    #[allow(non_snake_case)]
+    impl <I, O, P, E, H, St, S, $($param,)*> AjarsServerAxumSseHandler<I, O, (WithPathParams, ($($param,)*)), H, S>
+    for RestSse<I, O, P, E>
+    where
+    I: Serialize + DeserializeOwned + Send + 'static,
+    O: Serialize + DeserializeOwned + Send + 'static,
+    P: Serialize + DeserializeOwned + Send + 'static,
+    E: Serialize + DeserializeOwned + Send + 'static,
+    St: Stream<Item = Result<SseEvent<O>, E>> + Send + 'static,
+    S: Clone + Send + Sync + 'static,
+    H: 'static + Send + Sync + Clone + Fn($($param,)* P, I) -> St,
+    $( $param: FromRequestParts<S> + Send + 'static, )*
+    {
+        fn to(&self, handler: H) -> Router<S> {
+            let rest = self.rest();
+            let validation = InputValidation::<Json, I>::of(rest);
+            let status = error_status(rest);
+            let route = on(method_filter(rest.method()),
+                move |$( $param: $param,)* path: extract::Path<P>, payload: extract::Query<I>| async move {
+                    validation.check(&payload.0)?;
+                    Ok::<_, Response>(sse_response(status, (handler)($( $param,)* path.0, payload.0)).await)
+                });
+
+            Router::new().route(rest.path(), route)
+        }
+    }
+});
+
+sse_factory_tuple! {}
+sse_factory_tuple! { P0 }
+sse_factory_tuple! { P0 P1 }
+sse_factory_tuple! { P0 P1 P2 }
+sse_factory_tuple! { P0 P1 P2 P3 }
+sse_factory_tuple! { P0 P1 P2 P3 P4 }
+sse_factory_tuple! { P0 P1 P2 P3 P4 P5 }
+sse_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 }
+sse_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 }
+sse_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 }
+sse_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 P9 }
+
+#[cfg(test)]
+mod tests {
+
+    use ::axum::body::Body;
+    use ::axum::http::{Method, Request};
+    use futures_util::stream;
+    use http_body_util::BodyExt; // for `collect`
+    use serde::{Deserialize, Serialize};
+    use tower::ServiceExt; // for `oneshot`
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TicksRequest {
+        pub count: u64,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Tick {
+        pub count: u64,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TicksError {
+        pub reason: String,
+    }
+
+    const TICKS: RestSse<TicksRequest, Tick, (), TicksError> = RestSse::get("/api/ticks").with_error_status(409);
+
+    fn ticks(
+        last_event_id: LastEventId,
+        request: TicksRequest,
+    ) -> impl Stream<Item = Result<SseEvent<Tick>, TicksError>> {
+        let first = last_event_id.0.map(|id| id.parse::<u64>().unwrap() + 1).unwrap_or(1);
+        let events = match request.count {
+            0 => vec![Err(TicksError { reason: "No ticks".to_owned() })],
+            count => (first..first + count)
+                .map(|count| Ok(SseEvent::new(Tick { count }).with_id(count.to_string())))
+                .collect(),
+        };
+        stream::iter(events)
+    }
+
+    async fn call(request: Request<Body>) -> Response {
+        let app: Router = TICKS.to(ticks);
+        app.oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_send_the_events_as_an_event_stream() {
+        // Act
+        let response =
+            call(Request::builder().method(Method::GET).uri("/api/ticks?count=2").body(Body::empty()).unwrap()).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(SSE_CONTENT_TYPE, response.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap());
+        assert_eq!("no-cache", response.headers().get(header::CACHE_CONTROL).unwrap().to_str().unwrap());
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(
+            "id: 1\ndata: {\"count\":1}\n\nid: 2\ndata: {\"count\":2}\n\n",
+            String::from_utf8(body.to_vec()).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_pass_the_last_event_id_to_the_handler() {
+        // Act
+        let response = call(
+            Request::builder()
+                .method(Method::GET)
+                .uri("/api/ticks?count=1")
+                .header(LAST_EVENT_ID_HEADER, "41")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        // Assert
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!("id: 42\ndata: {\"count\":42}\n\n", String::from_utf8(body.to_vec()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn should_send_an_error_before_the_first_event_as_the_typed_error() {
+        // Act
+        let response =
+            call(Request::builder().method(Method::GET).uri("/api/ticks?count=0").body(Body::empty()).unwrap()).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: TicksError = serde_json::from_slice(&body).unwrap();
+        assert_eq!("No ticks", body.reason);
+    }
+}
@@ -0,0 +1,217 @@
+use ::axum::body::{Body, Bytes};
+use ::axum::extract::{self, FromRequestParts};
+use ::axum::http::{StatusCode, header};
+use ::axum::response::{IntoResponse, Response};
+use ::axum::routing::on;
+use ::axum::{BoxError, Router};
+use ajars_core::{CodecError, InputLocation, Json, NDJSON_CONTENT_TYPE, RestStream, RestType, encode_ndjson_item};
+use futures_util::{Stream, StreamExt, stream};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    Encoded, ErrorResponse, InputValidation, UrlEncoded, WithPathParams, WithoutPathParams, error_status, method_filter,
+};
+
+pub trait AjarsServerAxumStreamHandler<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, T, H, S> {
+    fn to(&self, handler: H) -> Router<S>;
+}
+
+/// Sends the items of the stream as newline-delimited JSON.
+async fn ndjson_response<O, E, St>(error_status: StatusCode, items: St) -> Response
+where
+    O: Serialize + Send + 'static,
+    E: Serialize + Send + 'static,
+    St: Stream<Item = Result<O, E>> + Send + 'static,
+{
+    streaming_response(error_status, NDJSON_CONTENT_TYPE, encode_ndjson_item, items).await
+}
+
+/// Sends the items of the stream, each one encoded by `encode`, as the body of a response with the given content type.
+/// An error returned before the first item is sent as the typed error of the endpoint,
+/// while an error returned after it aborts the response, as its status has already been sent.
+pub(crate) async fn streaming_response<T, E, St>(
+    error_status: StatusCode,
+    content_type: &'static str,
+    encode: fn(&T) -> Result<Vec<u8>, CodecError>,
+    items: St,
+) -> Response
+where
+    T: Send + 'static,
+    E: Serialize + Send + 'static,
+    St: Stream<Item = Result<T, E>> + Send + 'static,
+{
+    let mut items = Box::pin(items);
+    let first = match items.next().await {
+        Some(Err(error)) => return ErrorResponse(error_status, Encoded::<Json, _>::new(error)).into_response(),
+        first => first,
+    };
+
+    let chunks = stream::iter(first).chain(items).map(move |item| match item {
+        Ok(item) => encode(&item).map(Bytes::from).map_err(BoxError::from),
+        Err(_) => Err(BoxError::from("The stream of the endpoint failed after sending the first item")),
+    });
+    ([(header::CONTENT_TYPE, content_type)], Body::from_stream(chunks)).into_response()
+}
+
+macro_rules! stream_factory_tuple ({ $($param:ident)* } => {
+    #[allow(non_snake_case)]
+    impl <I, O, E, H, St, S, $($param,)*> AjarsServerAxumStreamHandler<I, O, (WithoutPathParams, ($($param,)*)), H, S>
+    for RestStream<I, O, (), E>
+    where
+    I: Serialize + DeserializeOwned + Send + 'static,
+    O: Serialize + DeserializeOwned + Send + 'static,
+    E: Serialize + DeserializeOwned + Send + 'static,
+    St: Stream<Item = Result<O, E>> + Send + 'static,
+    S: Clone + Send + Sync + 'static,
+    H: 'static + Send + Sync + Clone + Fn($($param,)* I) -> St,
+    $( $param: FromRequestParts<S> + Send + 'static, )*
+    {
+        fn to(&self, handler: H) -> Router<S> {
+            let rest = self.rest();
+            let filter = method_filter(rest.method());
+            let validation = InputValidation::<Json, I>::of(rest);
+            let status = error_status(rest);
+            let route = match rest.input_location() {
+                InputLocation::Query => on(filter,
+                    move |$( $param: $param,)* payload: extract::Query<I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>(ndjson_response(status, (handler)($( $param,)* payload.0)).await)
+                    }),
+                InputLocation::Body => on(filter,
+                    move |$( $param: $param,)* payload: Encoded<Json, I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>(ndjson_response(status, (handler)($( $param,)* payload.0)).await)
+                    }),
+                InputLocation::Form => on(filter,
+                    move |$( $param: $param,)* payload: Encoded<UrlEncoded, I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>(ndjson_response(status, (handler)($( $param,)* payload.0)).await)
+                    }),
+            };
+
+            Router::new().route(rest.path(), route)
+        }
+    }
+
+    #[allow(non_snake_case)]
+    impl <I, O, P, E, H, St, S, $($param,)*> AjarsServerAxumStreamHandler<I, O, (WithPathParams, ($($param,)*)), H, S>
+    for RestStream<I, O, P, E>
+    where
+    I: Serialize + DeserializeOwned + Send + 'static,
+    O: Serialize + DeserializeOwned + Send + 'static,
+    P: Serialize + DeserializeOwned + Send + 'static,
+    E: Serialize + DeserializeOwned + Send + 'static,
+    St: Stream<Item = Result<O, E>> + Send + 'static,
+    S: Clone + Send + Sync + 'static,
+    H: 'static + Send + Sync + Clone + Fn($($param,)* P, I) -> St,
+    $( $param: FromRequestParts<S> + Send + 'static, )*
+    {
+        fn to(&self, handler: H) -> Router<S> {
+            let rest = self.rest();
+            let filter = method_filter(rest.method());
+            let validation = InputValidation::<Json, I>::of(rest);
+            let status = error_status(rest);
+            let route = match rest.input_location() {
+                InputLocation::Query => on(filter,
+                    move |$( $param: $param,)* path: extract::Path<P>, payload: extract::Query<I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>(ndjson_response(status, (handler)($( $param,)* path.0, payload.0)).await)
+                    }),
+                InputLocation::Body => on(filter,
+                    move |$( $param: $param,)* path: extract::Path<P>, payload: Encoded<Json, I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>(ndjson_response(status, (handler)($( $param,)* path.0, payload.0)).await)
+                    }),
+                InputLocation::Form => on(filter,
+                    move |$( $param: $param,)* path: extract::Path<P>, payload: Encoded<UrlEncoded, I>| async move {
+                        validation.check(&payload.0)?;
+                        Ok::<_, Response>(ndjson_response(status, (handler)($( $param,)* path.0, payload.0)).await)
+                    }),
+            };
+
+            Router::new().route(rest.path(), route)
+        }
+    }
+});
+
+stream_factory_tuple! {}
+stream_factory_tuple! { P0 }
+stream_factory_tuple! { P0 P1 }
+stream_factory_tuple! { P0 P1 P2 }
+stream_factory_tuple! { P0 P1 P2 P3 }
+stream_factory_tuple! { P0 P1 P2 P3 P4 }
+stream_factory_tuple! { P0 P1 P2 P3 P4 P5 }
+stream_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 }
+stream_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 }
+stream_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 }
+stream_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 P9 }
+
+#[cfg(test)]
+mod tests {
+
+    use ::axum::body::Body;
+    use ::axum::http::{Method, Request};
+    use http_body_util::BodyExt; // for `collect`
+    use serde::{Deserialize, Serialize};
+    use tower::ServiceExt; // for `oneshot`
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct ExportRequest {
+        pub count: u64,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Row {
+        pub id: u64,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct ExportError {
+        pub reason: String,
+    }
+
+    const EXPORT: RestStream<ExportRequest, Row, (), ExportError> =
+        RestStream::get("/api/export").with_error_status(409);
+
+    fn export(request: ExportRequest) -> impl Stream<Item = Result<Row, ExportError>> {
+        let rows = match request.count {
+            0 => vec![Err(ExportError { reason: "Nothing to export".to_owned() })],
+            count => (0..count).map(|id| Ok(Row { id })).collect(),
+        };
+        stream::iter(rows)
+    }
+
+    async fn call(uri: &str) -> Response {
+        let app: Router = EXPORT.to(export);
+        app.oneshot(Request::builder().method(Method::GET).uri(uri).body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_send_the_items_as_newline_delimited_json() {
+        // Act
+        let response = call("/api/export?count=3").await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(NDJSON_CONTENT_TYPE, response.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap());
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!("{\"id\":0}\n{\"id\":1}\n{\"id\":2}\n", String::from_utf8(body.to_vec()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn should_send_an_error_before_the_first_item_as_the_typed_error() {
+        // Act
+        let response = call("/api/export?count=0").await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: ExportError = serde_json::from_slice(&body).unwrap();
+        assert_eq!("Nothing to export", body.reason);
+    }
+}
@@ -0,0 +1,235 @@
+use std::future::Future;
+
+use ::axum::Router;
+use ::axum::extract::multipart::{Field, Multipart};
+use ::axum::extract::{self, FromRequestParts};
+use ::axum::http::StatusCode;
+use ::axum::response::{IntoResponse, Response};
+use ::axum::routing::on;
+use ajars_core::{Codec, FILE_PART, Json, METADATA_PART, RestType, RestUpload, UploadedFile, empty_body_output};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    Encoded, ErrorResponse, InputValidation, SuccessResponse, WithPathParams, WithoutPathParams, error_status,
+    method_filter, success_status,
+};
+
+pub trait AjarsServerAxumUploadHandler<M: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, T, H, S> {
+    fn to(&self, handler: H) -> Router<S>;
+}
+
+/// Reads the metadata and the files of an upload, streaming every file to a temporary file.
+/// The parts with an unknown name are skipped.
+async fn read_upload<M: DeserializeOwned>(mut multipart: Multipart) -> Result<(M, Vec<UploadedFile>), Response> {
+    let mut metadata = None;
+    let mut files = vec![];
+    while let Some(field) = multipart.next_field().await.map_err(IntoResponse::into_response)? {
+        match field.name() {
+            Some(METADATA_PART) => {
+                let bytes = field.bytes().await.map_err(IntoResponse::into_response)?;
+                let decoded = Json::decode(&bytes).map_err(|err| {
+                    (StatusCode::BAD_REQUEST, format!("Failed to decode the metadata of the upload: {}", err))
+                        .into_response()
+                })?;
+                metadata = Some(decoded);
+            }
+            Some(FILE_PART) => files.push(write_file(field).await?),
+            _ => {}
+        }
+    }
+
+    // An empty metadata, like `()`, can be sent without its part
+    let metadata = metadata.or_else(empty_body_output).ok_or_else(|| {
+        (StatusCode::BAD_REQUEST, format!("The `{}` part of the upload is missing", METADATA_PART)).into_response()
+    })?;
+    Ok((metadata, files))
+}
+
+/// Streams a file part to a temporary file.
+async fn write_file(mut field: Field<'_>) -> Result<UploadedFile, Response> {
+    let failure = |err: std::io::Error| {
+        (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to store the file: {}", err)).into_response()
+    };
+
+    let (uploaded, file) =
+        UploadedFile::create(field.file_name().map(str::to_owned), field.content_type().map(str::to_owned))
+            .map_err(failure)?;
+    let mut file = tokio::fs::File::from_std(file);
+    while let Some(chunk) = field.chunk().await.map_err(IntoResponse::into_response)? {
+        file.write_all(&chunk).await.map_err(failure)?;
+    }
+    file.flush().await.map_err(failure)?;
+    Ok(uploaded)
+}
+
+macro_rules! upload_factory_tuple ({ $($param:ident)* } => {
+    #[allow(non_snake_case)]
+    impl <M, O, E, H, R, S, $($param,)*> AjarsServerAxumUploadHandler<M, O, (WithoutPathParams, ($($param,)*)), H, S>
+    for RestUpload<M, O, (), E>
+    where
+    M: Serialize + DeserializeOwned + Send + 'static,
+    O: Serialize + DeserializeOwned + Send + 'static,
+    E: Serialize + DeserializeOwned + Send + 'static,
+    R: Future<Output = Result<O, E>> + Send,
+    S: Clone + Send + Sync + 'static,
+    H: 'static + Send + Sync + Clone + Fn($($param,)* M, Vec<UploadedFile>) -> R,
+    $( $param: FromRequestParts<S> + Send + 'static, )*
+    {
+        fn to(&self, handler: H) -> Router<S> {
+            let rest = self.rest();
+            let validation = InputValidation::<Json, M>::of(rest);
+            let success = success_status(rest);
+            let status = error_status(rest);
+            let route = on(method_filter(rest.method()),
+                move |$( $param: $param,)* multipart: Multipart| async move {
+                    let (metadata, files) = read_upload(multipart).await?;
+                    validation.check(&metadata)?;
+                    Ok::<_, Response>((handler)($( $param,)* metadata, files).await.map(|output| SuccessResponse::<Json, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<Json, _>::new(error))))
+                });
+
+            Router::new().route(rest.path(), route)
+        }
+    }
+
+    #[allow(non_snake_case)]
+    impl <M, O, P, E, H, R, S, $($param,)*> AjarsServerAxumUploadHandler<M, O, (WithPathParams, ($($param,)*)), H, S>
+    for RestUpload<M, O, P, E>
+    where
+    M: Serialize + DeserializeOwned + Send + 'static,
+    O: Serialize + DeserializeOwned + Send + 'static,
+    P: Serialize + DeserializeOwned + Send + 'static,
+    E: Serialize + DeserializeOwned + Send + 'static,
+    R: Future<Output = Result<O, E>> + Send,
+    S: Clone + Send + Sync + 'static,
+    H: 'static + Send + Sync + Clone + Fn($($param,)* P, M, Vec<UploadedFile>) -> R,
+    $( $param: FromRequestParts<S> + Send + 'static, )*
+    {
+        fn to(&self, handler: H) -> Router<S> {
+            let rest = self.rest();
+            let validation = InputValidation::<Json, M>::of(rest);
+            let success = success_status(rest);
+            let status = error_status(rest);
+            let route = on(method_filter(rest.method()),
+                move |$( $param: $param,)* path: extract::Path<P>, multipart: Multipart| async move {
+                    let (metadata, files) = read_upload(multipart).await?;
+                    validation.check(&metadata)?;
+                    Ok::<_, Response>((handler)($( $param,)* path.0, metadata, files).await.map(|output| SuccessResponse::<Json, _>::new(success, output)).map_err(|error| ErrorResponse(status, Encoded::<Json, _>::new(error))))
+                });
+
+            Router::new().route(rest.path(), route)
+        }
+    }
+});
+
+upload_factory_tuple! {}
+upload_factory_tuple! { P0 }
+upload_factory_tuple! { P0 P1 }
+upload_factory_tuple! { P0 P1 P2 }
+upload_factory_tuple! { P0 P1 P2 P3 }
+upload_factory_tuple! { P0 P1 P2 P3 P4 }
+upload_factory_tuple! { P0 P1 P2 P3 P4 P5 }
+upload_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 }
+upload_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 }
+upload_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 }
+upload_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 P9 }
+
+#[cfg(test)]
+mod tests {
+
+    use ::axum::body::Body;
+    use ::axum::http::{Method, Request, header};
+    use ajars_core::{FilePart, encode_multipart, multipart_content_type};
+    use http_body_util::BodyExt; // for `collect`
+    use serde::{Deserialize, Serialize};
+    use tower::ServiceExt; // for `oneshot`
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Document {
+        pub title: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Stored {
+        pub title: String,
+        pub files: Vec<(String, String)>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct UploadError {
+        pub reason: String,
+    }
+
+    const UPLOAD: RestUpload<Document, Stored, (), UploadError> =
+        RestUpload::post("/api/upload").with_error_status(409);
+
+    async fn upload(metadata: Document, files: Vec<UploadedFile>) -> Result<Stored, UploadError> {
+        if files.is_empty() {
+            return Err(UploadError { reason: "No files".to_owned() });
+        }
+        let files = files
+            .iter()
+            .map(|file| (file.file_name().unwrap().to_owned(), String::from_utf8(file.read().unwrap()).unwrap()))
+            .collect();
+        Ok(Stored { title: metadata.title, files })
+    }
+
+    async fn call(body: Vec<u8>) -> Response {
+        let app: Router = UPLOAD.to(upload);
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/api/upload")
+            .header(header::CONTENT_TYPE, multipart_content_type("XYZ"))
+            .body(Body::from(body))
+            .unwrap();
+        app.oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_receive_the_metadata_and_the_files() {
+        // Arrange
+        let metadata = Document { title: "doc".to_owned() };
+        let files = [FilePart::new("a.txt", "hello"), FilePart::new("b.txt", "world")];
+
+        // Act
+        let response = call(encode_multipart("XYZ", &metadata, &files).unwrap()).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Stored = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            Stored {
+                title: "doc".to_owned(),
+                files: vec![("a.txt".to_owned(), "hello".to_owned()), ("b.txt".to_owned(), "world".to_owned())]
+            },
+            body
+        );
+    }
+
+    #[tokio::test]
+    async fn should_send_the_typed_error_with_the_error_status() {
+        // Act
+        let response = call(encode_multipart("XYZ", &Document { title: "doc".to_owned() }, &[]).unwrap()).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: UploadError = serde_json::from_slice(&body).unwrap();
+        assert_eq!("No files", body.reason);
+    }
+
+    #[tokio::test]
+    async fn should_answer_bad_request_if_the_metadata_is_missing() {
+        // Act
+        let response = call(b"--XYZ--\r\n".to_vec()).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
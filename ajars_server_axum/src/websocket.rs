@@ -0,0 +1,148 @@
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use ::axum::Router;
+use ::axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use ::axum::extract::{self, FromRequestParts};
+use ::axum::routing::on;
+use ajars_core::{RestType, RestWebSocket, WebSocketError, decode_ws_message, encode_ws_message};
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{WithPathParams, WithoutPathParams, method_filter};
+
+pub trait AjarsServerAxumWebSocketHandler<I: Serialize + DeserializeOwned, O: Serialize + DeserializeOwned, T, H, S> {
+    fn to(&self, handler: H) -> Router<S>;
+}
+
+/// The typed sink of the messages sent to the client of a WebSocket endpoint.
+/// The connection is closed when both the sender and the receiver are dropped.
+pub struct WebSocketSender<O> {
+    sink: SplitSink<WebSocket, Message>,
+    phantom: PhantomData<fn(O)>,
+}
+
+impl<O: Serialize> Sink<O> for WebSocketSender<O> {
+    type Error = WebSocketError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink.poll_ready_unpin(cx).map_err(WebSocketError::connection)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, message: O) -> Result<(), Self::Error> {
+        let text = encode_ws_message(&message)?;
+        self.sink.start_send_unpin(Message::Text(text.into())).map_err(WebSocketError::connection)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink.poll_flush_unpin(cx).map_err(WebSocketError::connection)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink.poll_close_unpin(cx).map_err(WebSocketError::connection)
+    }
+}
+
+/// The typed stream of the messages received from the client of a WebSocket endpoint.
+/// It ends when the client closes the connection.
+pub struct WebSocketReceiver<I> {
+    stream: SplitStream<WebSocket>,
+    phantom: PhantomData<fn() -> I>,
+}
+
+impl<I: DeserializeOwned> Stream for WebSocketReceiver<I> {
+    type Item = Result<I, WebSocketError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let message = match ready!(self.stream.poll_next_unpin(cx)) {
+                Some(Ok(message)) => message,
+                Some(Err(error)) => return Poll::Ready(Some(Err(WebSocketError::connection(error)))),
+                None => return Poll::Ready(None),
+            };
+            let message = match &message {
+                Message::Text(text) => decode_ws_message(text.as_bytes()),
+                Message::Binary(bytes) => decode_ws_message(bytes),
+                Message::Close(_) => return Poll::Ready(None),
+                // The pings are answered by the connection itself
+                Message::Ping(_) | Message::Pong(_) => continue,
+            };
+            return Poll::Ready(Some(message.map_err(WebSocketError::from)));
+        }
+    }
+}
+
+/// Splits the connection into the typed sender and receiver given to the handler.
+fn split<I, O>(socket: WebSocket) -> (WebSocketSender<O>, WebSocketReceiver<I>) {
+    let (sink, stream) = socket.split();
+    (WebSocketSender { sink, phantom: PhantomData }, WebSocketReceiver { stream, phantom: PhantomData })
+}
+
+macro_rules! websocket_factory_tuple ({ $($param:ident)* } => {
+    #[allow(non_snake_case)]
+    impl <I, O, H, Fut, S, $($param,)*> AjarsServerAxumWebSocketHandler<I, O, (WithoutPathParams, ($($param,)*)), H, S>
+    for RestWebSocket<I, O, ()>
+    where
+    I: Serialize + DeserializeOwned + Send + 'static,
+    O: Serialize + DeserializeOwned + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+    S: Clone + Send + Sync + 'static,
+    H: 'static + Send + Sync + Clone + Fn($($param,)* WebSocketSender<O>, WebSocketReceiver<I>) -> Fut,
+    $( $param: FromRequestParts<S> + Send + 'static, )*
+    {
+        fn to(&self, handler: H) -> Router<S> {
+            let rest = self.rest();
+            let route = on(method_filter(rest.method()),
+                move |$( $param: $param,)* upgrade: WebSocketUpgrade| async move {
+                    upgrade.on_upgrade(move |socket| {
+                        let (sender, receiver) = split(socket);
+                        (handler)($( $param,)* sender, receiver)
+                    })
+                });
+
+            Router::new().route(rest.path(), route)
+        }
+    }
+
+    #[allow(non_snake_case)]
+    impl <I, O, P, H, Fut, S, $($param,)*> AjarsServerAxumWebSocketHandler<I, O, (WithPathParams, ($($param,)*)), H, S>
+    for RestWebSocket<I, O, P>
+    where
+    I: Serialize + DeserializeOwned + Send + 'static,
+    O: Serialize + DeserializeOwned + Send + 'static,
+    P: Serialize + DeserializeOwned + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+    S: Clone + Send + Sync + 'static,
+    H: 'static + Send + Sync + Clone + Fn($($param,)* P, WebSocketSender<O>, WebSocketReceiver<I>) -> Fut,
+    $( $param: FromRequestParts<S> + Send + 'static, )*
+    {
+        fn to(&self, handler: H) -> Router<S> {
+            let rest = self.rest();
+            let route = on(method_filter(rest.method()),
+                move |$( $param: $param,)* path: extract::Path<P>, upgrade: WebSocketUpgrade| async move {
+                    upgrade.on_upgrade(move |socket| {
+                        let (sender, receiver) = split(socket);
+                        (handler)($( $param,)* path.0, sender, receiver)
+                    })
+                });
+
+            Router::new().route(rest.path(), route)
+        }
+    }
+});
+
+websocket_factory_tuple! {}
+websocket_factory_tuple! { P0 }
+websocket_factory_tuple! { P0 P1 }
+websocket_factory_tuple! { P0 P1 P2 }
+websocket_factory_tuple! { P0 P1 P2 P3 }
+websocket_factory_tuple! { P0 P1 P2 P3 P4 }
+websocket_factory_tuple! { P0 P1 P2 P3 P4 P5 }
+websocket_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 }
+websocket_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 }
+websocket_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 }
+websocket_factory_tuple! { P0 P1 P2 P3 P4 P5 P6 P7 P8 P9 }
@@ -1,16 +1,31 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use actix_rt::spawn;
-use ajars::RestType;
-use ajars::actix_web::AjarsServerActixWebHandler;
+use ajars::actix_web::actix_web::http::StatusCode;
 use ajars::actix_web::actix_web::web::Data;
 use ajars::actix_web::actix_web::{App, HttpRequest, HttpServer, ResponseError};
+use ajars::actix_web::{
+    AjarsActixWebGroup, AjarsServerActixWebBatchHandler, AjarsServerActixWebDownloadHandler,
+    AjarsServerActixWebHandler, AjarsServerActixWebSseHandler, AjarsServerActixWebStreamHandler,
+    AjarsServerActixWebUploadHandler, AjarsServerActixWebWebSocketHandler, LastEventId, WebSocketReceiver,
+    WebSocketSender,
+};
+use ajars::{Download, NoError, Page, PageRequest, RestType, SseEvent, UploadedFile};
+use futures_util::{Stream, StreamExt, stream};
 
 use crate::api::*;
-use crate::error::MyError;
+use crate::error::{MyError, Unavailable};
+use crate::service::{InMemoryItems, item_api};
 
 impl ResponseError for MyError {}
 
+impl ResponseError for Unavailable {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
 async fn echo(body: Simple<String>, request: HttpRequest, _data: Data<()>) -> Result<Simple<String>, MyError> {
     println!("echo - Request path: {:?}", request.path());
     println!("echo - Request method: {:?}", request.method());
@@ -19,6 +34,128 @@ async fn echo(body: Simple<String>, request: HttpRequest, _data: Data<()>) -> Re
     Ok(body)
 }
 
+async fn exists(body: Simple<String>, request: HttpRequest) -> Result<(), MyError> {
+    println!("exists - Request path: {:?}", request.path());
+    println!("exists - Request method: {:?}", request.method());
+    println!("exists - Request query_string: {:?}", request.query_string());
+    println!("exists - Request body: {:?}", body);
+    Ok(())
+}
+
+async fn item(body: Simple<String>, path: ItemPath, request: HttpRequest) -> Result<Item, MyError> {
+    println!("item - Request path: {:?}", request.path());
+    println!("item - Request method: {:?}", request.method());
+    println!("item - Request path params: {:?}", path);
+    println!("item - Request body: {:?}", body);
+    Ok(Item { path, inner: body.inner })
+}
+
+async fn failing(body: Simple<String>, request: HttpRequest) -> Result<Simple<String>, Failure> {
+    println!("failing - Request path: {:?}", request.path());
+    println!("failing - Request body: {:?}", body);
+    Err(Failure { reason: body.inner })
+}
+
+async fn unit(_body: (), request: HttpRequest) -> Result<(), MyError> {
+    println!("unit - Request path: {:?}", request.path());
+    Ok(())
+}
+
+async fn maybe(_body: (), request: HttpRequest) -> Result<Option<Simple<String>>, MyError> {
+    println!("maybe - Request path: {:?}", request.path());
+    Ok(None)
+}
+
+async fn flaky_call(body: Simple<String>, request: HttpRequest) -> Result<Simple<u64>, Unavailable> {
+    println!("flaky - Request path: {:?}", request.path());
+    flaky(body)
+}
+
+async fn signup(body: Signup, request: HttpRequest) -> Result<Simple<String>, MyError> {
+    println!("signup - Request path: {:?}", request.path());
+    Ok(Simple { inner: body.username })
+}
+
+fn export(request: ExportRequest) -> impl Stream<Item = Result<Simple<u64>, Failure>> {
+    println!("export - Request: {:?}", request);
+    let items = match request.count {
+        0 => vec![Err(Failure { reason: "Nothing to export".to_owned() })],
+        count => (0..count).map(|inner| Ok(Simple { inner })).collect(),
+    };
+    stream::iter(items)
+}
+
+fn ticks(
+    request: TicksRequest,
+    last_event_id: LastEventId,
+) -> impl Stream<Item = Result<SseEvent<Simple<u64>>, Failure>> {
+    println!("ticks - Last-Event-ID: {:?}", last_event_id.0);
+    let first = last_event_id.0.map(|id| id.parse::<u64>().expect("The ID should be a number") + 1).unwrap_or(1);
+    let events = match request.batch {
+        0 => vec![Err(Failure { reason: "Empty batch".to_owned() })],
+        batch => {
+            (first..first + batch).map(|inner| Ok(SseEvent::new(Simple { inner }).with_id(inner.to_string()))).collect()
+        }
+    };
+    stream::iter(events)
+}
+
+async fn upload(document: Document, files: Vec<UploadedFile>, folder: String) -> Result<Stored, Failure> {
+    println!("upload - Folder: {:?}", folder);
+    if files.is_empty() {
+        return Err(Failure { reason: "No files".to_owned() });
+    }
+    let files = files
+        .iter()
+        .map(|file| StoredFile {
+            file_name: file.file_name().unwrap_or_default().to_owned(),
+            content_type: file.content_type().unwrap_or_default().to_owned(),
+            content: String::from_utf8(file.read().expect("The file should be readable")).unwrap_or_default(),
+        })
+        .collect();
+    Ok(Stored { folder, title: document.title, files })
+}
+
+async fn numbers(page: PageRequest, request: HttpRequest) -> Result<Page<u64>, MyError> {
+    println!("numbers - Request path: {:?}", request.path());
+    let offset = page.offset_or_zero();
+    let items = (offset..NUMBERS_TOTAL).take(page.limit_or(3, 10) as usize).collect();
+    Ok(Page::with_offset(items, &page, NUMBERS_TOTAL))
+}
+
+async fn multiples(multiples: MultiplesRequest, request: HttpRequest) -> Result<Page<u64>, MyError> {
+    println!("multiples - Request path: {:?}", request.path());
+    let after =
+        multiples.cursor.as_ref().map(|cursor| cursor.parse::<u64>().expect("The cursor should be a number") + 1);
+    let items: Vec<u64> = (after.unwrap_or(0)..20)
+        .filter(|number| number % multiples.factor == 0)
+        .take(multiples.limit.unwrap_or(3) as usize)
+        .collect();
+    let next_cursor = items.last().filter(|last| *last + multiples.factor < 20).map(u64::to_string);
+    let page = PageRequest { limit: multiples.limit, ..PageRequest::default() };
+    Ok(Page::with_cursor(items, &page, next_cursor)
+        .with_next_link(MULTIPLES.path(), &multiples)
+        .expect("The request should be encoded"))
+}
+
+async fn report(request: ReportRequest, name: String) -> Result<Download, Failure> {
+    println!("report - Name: {:?}", name);
+    if request.lines == 0 {
+        return Err(Failure { reason: "Empty report".to_owned() });
+    }
+    let content = (0..request.lines).map(|line| format!("{} {}\n", name, line)).collect::<String>();
+    Ok(Download::bytes("text/plain", content).attachment(format!("{}.txt", name)))
+}
+
+async fn chat(sender: WebSocketSender<Simple<String>>, receiver: WebSocketReceiver<Simple<String>>, room: String) {
+    println!("chat - Room: {:?}", room);
+    let answers =
+        receiver.map(|message| message.map(|message| Simple { inner: format!("{}: {}", room, message.inner) }));
+    if let Err(error) = answers.forward(sender).await {
+        println!("chat - Error: {:?}", error);
+    }
+}
+
 async fn info(
     body: InfoRequest<String>,
     request: HttpRequest,
@@ -45,7 +182,9 @@ async fn info(
 }
 
 /// spanws an actix server and returns the server port
-pub fn spawn_actix_web<REST: 'static + Clone + Send + RestType<Simple<String>, Simple<String>>>(
+pub fn spawn_actix_web<
+    REST: 'static + Clone + Send + RestType<Simple<String>, Simple<String>, PathParams = (), Error = NoError>,
+>(
     echo_rest: REST,
 ) -> u16 {
     let free_port = port_check::free_local_port().unwrap();
@@ -58,10 +197,41 @@ pub fn spawn_actix_web<REST: 'static + Clone + Send + RestType<Simple<String>, S
             App::new()
                 .app_data(Data::new(()))
                 .service(echo_rest.to(echo))
+                .service(EXISTS.to(exists))
+                .service(ITEM.to(item))
+                .service(FAILING.to(failing))
+                .service(ECHO_CBOR.to(echo))
+                .service(ECHO_MSGPACK.to(echo))
+                .service(ECHO_DELETE_BODY.to(echo))
+                .service(ECHO_POST_QUERY.to(echo))
+                .service(ECHO_PUT_FORM.to(echo))
+                .service(UNIT.to(unit))
+                .service(MAYBE.to(maybe))
+                .service(SIGNUP.to(signup))
+                .service(CREATE.to(echo))
+                .service(FLAKY.to(flaky_call))
+                .service(FLAKY_POST.to(flaky_call))
+                .service(EXPORT.to(export))
+                .service(TICKS.to(ticks))
+                .service(UPLOAD.to(upload))
+                .service(DOWNLOAD.to(report))
+                .service(NUMBERS.to(numbers))
+                .service(MULTIPLES.to(multiples))
+                .service(BATCH.to(batch_handlers()))
+                .service(CHAT.to(chat))
                 .service(INFO_DELETE.to(info))
                 .service(INFO_GET.to(info))
+                .service(INFO_OPTIONS.to(info))
+                .service(INFO_PATCH.to(info))
                 .service(INFO_POST.to(info))
                 .service(INFO_PUT.to(info))
+                .service(
+                    AjarsActixWebGroup::new(&api_group())
+                        .route(&GROUP_ECHO_GET, echo)
+                        .route(&GROUP_ECHO_POST, echo)
+                        .route(&GROUP_ITEM, item)
+                        .build(),
+                )
         })
         .bind(&address)
         .unwrap()
@@ -71,3 +241,22 @@ pub fn spawn_actix_web<REST: 'static + Clone + Send + RestType<Simple<String>, S
     });
     free_port
 }
+
+/// spawns an actix server with the endpoints of the item service and returns the server port
+pub fn spawn_actix_web_service() -> u16 {
+    let free_port = port_check::free_local_port().unwrap();
+    let address = format!("127.0.0.1:{}", free_port);
+    let service = Arc::new(InMemoryItems::default());
+
+    // Start Server
+    spawn(async move {
+        println!("Start actix-web to {}", address);
+        HttpServer::new(move || App::new().service(item_api::actix_web_services(service.clone())))
+            .bind(&address)
+            .unwrap()
+            .run()
+            .await
+            .unwrap();
+    });
+    free_port
+}
@@ -1,7 +1,14 @@
 use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
 
-use ajars::Rest;
+use ajars::{
+    ApiGroup, BatchHandlers, Cbor, InputLocation, MessagePack, NoError, Page, PageRequest, Paginated, Rest, RestBatch,
+    RestDownload, RestSse, RestStream, RestUpload, RestWebSocket,
+};
 use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::error::Unavailable;
 
 // This defines a 'DELETE' call with request type 'InfoRequest' and response type 'InfoResponse'
 pub const INFO_DELETE: Rest<InfoRequest<String>, InfoResponse<String>> = Rest::delete("/api/info");
@@ -9,12 +16,159 @@ pub const INFO_DELETE: Rest<InfoRequest<String>, InfoResponse<String>> = Rest::d
 // This defines a 'GET' call with request type 'InfoRequest' and response type 'InfoResponse'
 pub const INFO_GET: Rest<InfoRequest<String>, InfoResponse<String>> = Rest::get("/api/info");
 
+// This defines a 'OPTIONS' call with request type 'InfoRequest' and response type 'InfoResponse'
+pub const INFO_OPTIONS: Rest<InfoRequest<String>, InfoResponse<String>> = Rest::options("/api/info");
+
+// This defines a 'PATCH' call with request type 'InfoRequest' and response type 'InfoResponse'
+pub const INFO_PATCH: Rest<InfoRequest<String>, InfoResponse<String>> = Rest::patch("/api/info");
+
 // This defines a 'POST' call with request type 'InfoRequest' and response type 'InfoResponse'
 pub const INFO_POST: Rest<InfoRequest<String>, InfoResponse<String>> = Rest::post("/api/info");
 
 // This defines a 'PUT' call with request type 'InfoRequest' and response type 'InfoResponse'
 pub const INFO_PUT: Rest<InfoRequest<String>, InfoResponse<String>> = Rest::put("/api/info");
 
+// This defines a 'HEAD' call with request type 'Simple' and no response body
+pub const EXISTS: Rest<Simple<String>, ()> = Rest::head("/api/exists");
+
+// This defines a 'GET' call with path parameters 'ItemPath', request type 'Simple' and response type 'Item'
+pub const ITEM: Rest<Simple<String>, Item, ItemPath> = Rest::get("/api/items/{id}/{name}");
+
+// This defines a 'POST' call with request type 'Simple', response type 'Simple' and error type 'Failure' sent with status 409
+pub const FAILING: Rest<Simple<String>, Simple<String>, (), Failure> =
+    Rest::post("/api/failing").with_error_status(409);
+
+// This defines a 'POST' call whose bodies are encoded as CBOR
+pub const ECHO_CBOR: Rest<Simple<String>, Simple<String>, (), NoError, Cbor> = Rest::post("/api/cbor");
+
+// This defines a 'PUT' call whose bodies are encoded as MessagePack
+pub const ECHO_MSGPACK: Rest<Simple<String>, Simple<String>, (), NoError, MessagePack> = Rest::put("/api/msgpack");
+
+// This defines a 'DELETE' call that sends its input in the body
+pub const ECHO_DELETE_BODY: Rest<Simple<String>, Simple<String>> =
+    Rest::delete("/api/delete_body").with_input_location(InputLocation::Body);
+
+// This defines a 'POST' call that sends its input in the query string
+pub const ECHO_POST_QUERY: Rest<Simple<String>, Simple<String>> =
+    Rest::post("/api/post_query").with_input_location(InputLocation::Query);
+
+// This defines a 'PUT' call that sends its input as a form
+pub const ECHO_PUT_FORM: Rest<Simple<String>, Simple<String>> =
+    Rest::put("/api/put_form").with_input_location(InputLocation::Form);
+
+// This defines a 'POST' call without input and output, that is sent without body and answered with '204 No Content'
+pub const UNIT: Rest<(), ()> = Rest::post("/api/unit");
+
+// This defines a 'GET' call without input, whose output is empty when there is no value
+pub const MAYBE: Rest<(), Option<Simple<String>>> = Rest::get("/api/maybe");
+
+// This defines a 'POST' call whose output is sent with the status '201 Created'
+pub const CREATE: Rest<Simple<String>, Simple<String>> = Rest::post("/api/create").with_success_status(201);
+
+// This defines the same call with the default success status, so that the client receives an unexpected status
+pub const CREATE_UNDECLARED: Rest<Simple<String>, Simple<String>> = Rest::post("/api/create");
+
+// This defines the same call with an output whose field has another type, so that the client fails to decode it
+pub const CREATE_NUMBER: Rest<Simple<String>, Simple<u64>> = Rest::post("/api/create").with_success_status(201);
+
+// This defines the failing call without its error type, so that the client receives an error status with an untyped body
+pub const FAILING_UNDECLARED: Rest<Simple<String>, Simple<String>> = Rest::post("/api/failing").with_error_status(409);
+
+// This defines a 'GET' call whose server fails 'FLAKY_FAILURES' times for each input before answering with the attempts
+pub const FLAKY: Rest<Simple<String>, Simple<u64>> = Rest::get("/api/flaky");
+
+// This defines a 'POST' call answered in the same way, that is not idempotent and so is not retried
+pub const FLAKY_POST: Rest<Simple<String>, Simple<u64>> = Rest::post("/api/flaky_post");
+
+// This defines the same 'POST' call declared idempotent, so that it is retried
+pub const FLAKY_POST_IDEMPOTENT: Rest<Simple<String>, Simple<u64>> = Rest::post("/api/flaky_post").idempotent();
+
+pub const FLAKY_FAILURES: u64 = 2;
+
+// This counts the attempts of the flaky calls for each input
+static FLAKY_ATTEMPTS: LazyLock<Mutex<HashMap<String, u64>>> = LazyLock::new(Mutex::default);
+
+// This answers a flaky call, failing with '503 Service Unavailable' until the input has been sent 'FLAKY_FAILURES' times
+pub fn flaky(body: Simple<String>) -> Result<Simple<u64>, Unavailable> {
+    let mut attempts = FLAKY_ATTEMPTS.lock().unwrap();
+    let attempt = attempts.entry(body.inner).or_default();
+    *attempt += 1;
+    match *attempt > FLAKY_FAILURES {
+        true => Ok(Simple { inner: *attempt }),
+        false => Err(Unavailable {}),
+    }
+}
+
+// This defines a 'POST' call whose input is validated by the client before sending it and by the server
+pub const SIGNUP: Rest<Signup, Simple<String>> = Rest::post("/api/signup").with_validation();
+
+// This defines the same call without validation, so that the client sends inputs that the server rejects
+pub const SIGNUP_UNCHECKED: Rest<Signup, Simple<String>> = Rest::post("/api/signup");
+
+// This defines a 'GET' call whose output is a stream of items, failing with 'Failure' when there is nothing to export
+pub const EXPORT: RestStream<ExportRequest, Simple<u64>, (), Failure> =
+    RestStream::get("/api/export").with_error_status(409);
+
+// This defines a Server-Sent Events call whose connections send a batch of events, resumed after the last event received
+pub const TICKS: RestSse<TicksRequest, Simple<u64>, (), Failure> = RestSse::get("/api/ticks").with_error_status(409);
+
+// This defines an upload call of files described by 'Document', failing with 'Failure' when no file is sent
+pub const UPLOAD: RestUpload<Document, Stored, String, Failure> =
+    RestUpload::post("/api/folders/{folder}/upload").with_error_status(409).with_success_status(201);
+
+// This defines a listing of the numbers below 'NUMBERS_TOTAL', paginated in the offset style
+pub const NUMBERS: Rest<PageRequest, Page<u64>> = Rest::get("/api/numbers");
+
+pub const NUMBERS_TOTAL: u64 = 7;
+
+// This defines a listing of the multiples of a factor below 20, paginated in the cursor style
+pub const MULTIPLES: Rest<MultiplesRequest, Page<u64>> = Rest::get("/api/multiples");
+
+// This defines a download call of the text report named by the path, failing with 'Failure' when it has no lines
+pub const DOWNLOAD: RestDownload<ReportRequest, String, Failure> =
+    RestDownload::get("/api/reports/{name}").with_error_status(404);
+
+// This defines the endpoint that carries several calls in a single request, dispatched to 'batch_handlers'
+pub const BATCH: RestBatch = RestBatch::post("/api/batch");
+
+// This defines a WebSocket call whose server answers every message with the message prefixed by the room
+pub const CHAT: RestWebSocket<Simple<String>, Simple<String>, String> = RestWebSocket::new("/api/rooms/{room}/chat");
+
+// These define the endpoints of the group below, with paths relative to the group
+pub const GROUP_ECHO_GET: Rest<Simple<String>, Simple<String>> = Rest::get("/echo");
+pub const GROUP_ECHO_POST: Rest<Simple<String>, Simple<String>> = Rest::post("/echo");
+pub const GROUP_ITEM: Rest<Simple<String>, Item, ItemPath> = Rest::get("/items/{id}/{name}");
+
+// This defines a group served under '/api/group', with the item endpoint nested under '/api/group/v1'
+pub fn api_group() -> ApiGroup {
+    ApiGroup::new("/api/group")
+        .endpoint(&GROUP_ECHO_GET)
+        .endpoint(&GROUP_ECHO_POST)
+        .group(ApiGroup::new("/v1").endpoint(&GROUP_ITEM))
+}
+
+// This defines the handlers of the endpoints that can be called through 'BATCH'
+pub fn batch_handlers() -> BatchHandlers {
+    BatchHandlers::new()
+        .endpoint_with_path(&ITEM, |path: ItemPath, body: Simple<String>| async move {
+            Ok::<_, NoError>(Item { path, inner: body.inner })
+        })
+        .endpoint(&FAILING, |body: Simple<String>| async move { Err(Failure { reason: body.inner }) })
+        .endpoint(&ECHO_CBOR, |body: Simple<String>| async move { Ok::<_, NoError>(body) })
+        .endpoint(&CREATE, |body: Simple<String>| async move { Ok::<_, NoError>(body) })
+        .endpoint(&UNIT, |_: ()| async { Ok::<_, NoError>(()) })
+        .endpoint(&SIGNUP, |body: Signup| async move { Ok::<_, NoError>(Simple { inner: body.username }) })
+        .endpoint_with_context(&INFO_POST, |context, _: (), body: InfoRequest<String>| async move {
+            Ok::<_, NoError>(InfoResponse {
+                request_headers: context.headers().iter().cloned().collect(),
+                request_method: "POST".to_owned(),
+                request_query_string: String::new(),
+                request_path: "/api/info".to_owned(),
+                request_payload: body.payload,
+            })
+        })
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct InfoRequest<T> {
     pub payload: T,
@@ -33,3 +187,75 @@ pub struct InfoResponse<T> {
 pub struct Simple<O> {
     pub inner: O,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ItemPath {
+    pub id: u64,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Item {
+    pub path: ItemPath,
+    pub inner: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Failure {
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExportRequest {
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TicksRequest {
+    pub batch: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Document {
+    pub title: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Stored {
+    pub folder: String,
+    pub title: String,
+    pub files: Vec<StoredFile>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredFile {
+    pub file_name: String,
+    pub content_type: String,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReportRequest {
+    pub lines: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MultiplesRequest {
+    pub factor: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+impl Paginated for MultiplesRequest {
+    fn with_page(self, page: PageRequest) -> Self {
+        Self { cursor: page.cursor, limit: page.limit, ..self }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Validate)]
+pub struct Signup {
+    #[validate(length(min = 3))]
+    pub username: String,
+}
@@ -1,17 +1,24 @@
 use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use ::axum::extract::State;
-use ajars::RestType;
-use ajars::axum::AjarsServerAxumHandler;
 use ajars::axum::axum::body::Body;
 use ajars::axum::axum::http::{self, Response};
 use ajars::axum::axum::response::IntoResponse;
 use ajars::axum::axum::{self, Router};
+use ajars::axum::{
+    AjarsAxumGroupRouter, AjarsServerAxumBatchHandler, AjarsServerAxumDownloadHandler, AjarsServerAxumHandler,
+    AjarsServerAxumSseHandler, AjarsServerAxumStreamHandler, AjarsServerAxumUploadHandler,
+    AjarsServerAxumWebSocketHandler, LastEventId, WebSocketReceiver, WebSocketSender,
+};
+use ajars::{Download, NoError, Page, PageRequest, RestType, SseEvent, UploadedFile};
+use futures_util::{Stream, StreamExt, stream};
 use tokio::net::TcpListener;
 
 use crate::api::*;
-use crate::error::MyError;
+use crate::error::{MyError, Unavailable};
+use crate::service::{InMemoryItems, item_api};
 
 impl IntoResponse for MyError {
     fn into_response(self) -> Response<Body> {
@@ -19,6 +26,12 @@ impl IntoResponse for MyError {
     }
 }
 
+impl IntoResponse for Unavailable {
+    fn into_response(self) -> Response<Body> {
+        (http::StatusCode::SERVICE_UNAVAILABLE, self.to_string()).into_response()
+    }
+}
+
 async fn echo(uri: http::Uri, method: http::Method, body: Simple<String>) -> Result<Simple<String>, MyError> {
     println!("echo - Request path: {:?}", uri.path());
     println!("echo - Request method: {:?}", method);
@@ -27,6 +40,126 @@ async fn echo(uri: http::Uri, method: http::Method, body: Simple<String>) -> Res
     Ok(body)
 }
 
+async fn exists(uri: http::Uri, method: http::Method, body: Simple<String>) -> Result<(), MyError> {
+    println!("exists - Request path: {:?}", uri.path());
+    println!("exists - Request method: {:?}", method);
+    println!("exists - Request query_string: {:?}", uri.query());
+    println!("exists - Request body: {:?}", body);
+    Ok(())
+}
+
+async fn item(uri: http::Uri, path: ItemPath, body: Simple<String>) -> Result<Item, MyError> {
+    println!("item - Request path: {:?}", uri.path());
+    println!("item - Request path params: {:?}", path);
+    println!("item - Request body: {:?}", body);
+    Ok(Item { path, inner: body.inner })
+}
+
+async fn failing(uri: http::Uri, body: Simple<String>) -> Result<Simple<String>, Failure> {
+    println!("failing - Request path: {:?}", uri.path());
+    println!("failing - Request body: {:?}", body);
+    Err(Failure { reason: body.inner })
+}
+
+async fn unit(uri: http::Uri, _body: ()) -> Result<(), MyError> {
+    println!("unit - Request path: {:?}", uri.path());
+    Ok(())
+}
+
+async fn maybe(uri: http::Uri, _body: ()) -> Result<Option<Simple<String>>, MyError> {
+    println!("maybe - Request path: {:?}", uri.path());
+    Ok(None)
+}
+
+async fn flaky_call(uri: http::Uri, body: Simple<String>) -> Result<Simple<u64>, Unavailable> {
+    println!("flaky - Request path: {:?}", uri.path());
+    flaky(body)
+}
+
+async fn signup(uri: http::Uri, body: Signup) -> Result<Simple<String>, MyError> {
+    println!("signup - Request path: {:?}", uri.path());
+    Ok(Simple { inner: body.username })
+}
+
+fn export(request: ExportRequest) -> impl Stream<Item = Result<Simple<u64>, Failure>> {
+    println!("export - Request: {:?}", request);
+    let items = match request.count {
+        0 => vec![Err(Failure { reason: "Nothing to export".to_owned() })],
+        count => (0..count).map(|inner| Ok(Simple { inner })).collect(),
+    };
+    stream::iter(items)
+}
+
+fn ticks(
+    last_event_id: LastEventId,
+    request: TicksRequest,
+) -> impl Stream<Item = Result<SseEvent<Simple<u64>>, Failure>> {
+    println!("ticks - Last-Event-ID: {:?}", last_event_id.0);
+    let first = last_event_id.0.map(|id| id.parse::<u64>().expect("The ID should be a number") + 1).unwrap_or(1);
+    let events = match request.batch {
+        0 => vec![Err(Failure { reason: "Empty batch".to_owned() })],
+        batch => {
+            (first..first + batch).map(|inner| Ok(SseEvent::new(Simple { inner }).with_id(inner.to_string()))).collect()
+        }
+    };
+    stream::iter(events)
+}
+
+async fn upload(folder: String, document: Document, files: Vec<UploadedFile>) -> Result<Stored, Failure> {
+    println!("upload - Folder: {:?}", folder);
+    if files.is_empty() {
+        return Err(Failure { reason: "No files".to_owned() });
+    }
+    let files = files
+        .iter()
+        .map(|file| StoredFile {
+            file_name: file.file_name().unwrap_or_default().to_owned(),
+            content_type: file.content_type().unwrap_or_default().to_owned(),
+            content: String::from_utf8(file.read().expect("The file should be readable")).unwrap_or_default(),
+        })
+        .collect();
+    Ok(Stored { folder, title: document.title, files })
+}
+
+async fn numbers(uri: http::Uri, request: PageRequest) -> Result<Page<u64>, MyError> {
+    println!("numbers - Request path: {:?}", uri.path());
+    let offset = request.offset_or_zero();
+    let items = (offset..NUMBERS_TOTAL).take(request.limit_or(3, 10) as usize).collect();
+    Ok(Page::with_offset(items, &request, NUMBERS_TOTAL))
+}
+
+async fn multiples(uri: http::Uri, request: MultiplesRequest) -> Result<Page<u64>, MyError> {
+    println!("multiples - Request path: {:?}", uri.path());
+    let after = request.cursor.as_ref().map(|cursor| cursor.parse::<u64>().expect("The cursor should be a number") + 1);
+    let items: Vec<u64> = (after.unwrap_or(0)..20)
+        .filter(|number| number % request.factor == 0)
+        .take(request.limit.unwrap_or(3) as usize)
+        .collect();
+    let next_cursor = items.last().filter(|last| *last + request.factor < 20).map(u64::to_string);
+    let page = PageRequest { limit: request.limit, ..PageRequest::default() };
+    Ok(Page::with_cursor(items, &page, next_cursor)
+        .with_next_link(MULTIPLES.path(), &request)
+        .expect("The request should be encoded"))
+}
+
+async fn report(name: String, request: ReportRequest) -> Result<Download, Failure> {
+    println!("report - Name: {:?}", name);
+    if request.lines == 0 {
+        return Err(Failure { reason: "Empty report".to_owned() });
+    }
+    let content = (0..request.lines).map(|line| format!("{} {}\n", name, line)).collect::<String>();
+    Ok(Download::bytes("text/plain", content).attachment(format!("{}.txt", name)))
+}
+
+async fn chat(room: String, sender: WebSocketSender<Simple<String>>, receiver: WebSocketReceiver<Simple<String>>) {
+    println!("chat - Room: {:?}", room);
+    let answers =
+        receiver.map(|message| message.map(|message| Simple { inner: format!("{}: {}", room, message.inner) }));
+    if let Err(error) = answers.forward(sender).await {
+        println!("chat - Error: {:?}", error);
+    }
+}
+
 async fn info(
     _: State<()>,
     uri: http::Uri,
@@ -58,18 +191,72 @@ async fn info(
 }
 
 /// spanws an actix server and returns the server port
-pub fn spawn_axum<REST: 'static + Clone + Send + RestType<Simple<String>, Simple<String>>>(echo_rest: REST) -> u16 {
+pub fn spawn_axum<
+    REST: 'static + Clone + Send + RestType<Simple<String>, Simple<String>, PathParams = (), Error = NoError>,
+>(
+    echo_rest: REST,
+) -> u16 {
     let free_port = port_check::free_local_port().unwrap();
     //let address = format!("127.0.0.1:{}", free_port);
 
     // Start Server
     tokio::spawn(async move {
+        let group = api_group();
         let app = Router::new()
             .merge(echo_rest.to(echo))
+            .merge(EXISTS.to(exists))
+            .merge(ITEM.to(item))
+            .merge(FAILING.to(failing))
+            .merge(ECHO_CBOR.to(echo))
+            .merge(ECHO_MSGPACK.to(echo))
+            .merge(ECHO_DELETE_BODY.to(echo))
+            .merge(ECHO_POST_QUERY.to(echo))
+            .merge(ECHO_PUT_FORM.to(echo))
+            .merge(UNIT.to(unit))
+            .merge(MAYBE.to(maybe))
+            .merge(SIGNUP.to(signup))
+            .merge(CREATE.to(echo))
+            .merge(FLAKY.to(flaky_call))
+            .merge(FLAKY_POST.to(flaky_call))
+            .merge(EXPORT.to(export))
+            .merge(TICKS.to(ticks))
+            .merge(UPLOAD.to(upload))
+            .merge(DOWNLOAD.to(report))
+            .merge(NUMBERS.to(numbers))
+            .merge(MULTIPLES.to(multiples))
+            .merge(BATCH.to(batch_handlers()))
+            .merge(CHAT.to(chat))
             .merge(INFO_DELETE.to(info))
             .merge(INFO_GET.to(info))
+            .merge(INFO_OPTIONS.to(info))
+            .merge(INFO_PATCH.to(info))
             .merge(INFO_POST.to(info))
-            .merge(INFO_PUT.to(info));
+            .merge(INFO_PUT.to(info))
+            .merge(
+                AjarsAxumGroupRouter::new(&group)
+                    .route(&GROUP_ECHO_GET, echo)
+                    .route(&GROUP_ECHO_POST, echo)
+                    .route(&GROUP_ITEM, item)
+                    .build(),
+            );
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], free_port));
+
+        println!("Start axum to {}", addr);
+
+        let listener = TcpListener::bind(&addr).await.unwrap();
+        axum::serve(listener, app.into_make_service()).await.unwrap();
+    });
+    free_port
+}
+
+/// spawns an axum server with the endpoints of the item service and returns the server port
+pub fn spawn_axum_service() -> u16 {
+    let free_port = port_check::free_local_port().unwrap();
+
+    // Start Server
+    tokio::spawn(async move {
+        let app: Router = item_api::axum_router(Arc::new(InMemoryItems::default()));
 
         let addr = SocketAddr::from(([127, 0, 0, 1], free_port));
 
@@ -8,3 +8,13 @@ impl Display for MyError {
         Ok(())
     }
 }
+
+/// The error of a server that is temporarily not able to answer, sent with the status '503 Service Unavailable'
+#[derive(Debug, Clone)]
+pub struct Unavailable {}
+
+impl Display for Unavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Service unavailable")
+    }
+}
@@ -2,3 +2,4 @@ pub mod actix_web;
 pub mod api;
 pub mod axum;
 pub mod error;
+pub mod service;
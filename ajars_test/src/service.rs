@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::api::{Failure, Simple};
+
+// This defines the endpoints of a service together with the trait implemented by the servers
+#[ajars::service]
+pub trait ItemApi {
+    #[post("/api/service/items", error_status = 409, success_status = 201)]
+    async fn create_item(&self, item: Simple<String>) -> Result<Simple<u64>, Failure>;
+
+    #[get("/api/service/items/{id}", error_status = 404)]
+    async fn get_item(&self, #[path] id: u64, request: Simple<String>) -> Result<Simple<String>, Failure>;
+
+    #[put("/api/service/items/{id}", input = Form)]
+    async fn update_item(&self, #[path] id: u64, item: Simple<String>) -> Simple<bool>;
+
+    #[delete("/api/service/items")]
+    async fn clear(&self) -> Simple<usize>;
+}
+
+#[derive(Default)]
+pub struct InMemoryItems {
+    items: Mutex<HashMap<u64, String>>,
+}
+
+impl ItemApi for InMemoryItems {
+    async fn create_item(&self, item: Simple<String>) -> Result<Simple<u64>, Failure> {
+        let mut items = self.items.lock().unwrap();
+        if items.values().any(|value| value == &item.inner) {
+            return Err(Failure { reason: format!("Duplicated item [{}]", item.inner) });
+        }
+        let id = items.len() as u64;
+        items.insert(id, item.inner);
+        Ok(Simple { inner: id })
+    }
+
+    async fn get_item(&self, id: u64, request: Simple<String>) -> Result<Simple<String>, Failure> {
+        match self.items.lock().unwrap().get(&id) {
+            Some(item) => Ok(Simple { inner: format!("{}{}", request.inner, item) }),
+            None => Err(Failure { reason: format!("Unknown item [{}]", id) }),
+        }
+    }
+
+    async fn update_item(&self, id: u64, item: Simple<String>) -> Simple<bool> {
+        Simple { inner: self.items.lock().unwrap().insert(id, item.inner).is_some() }
+    }
+
+    async fn clear(&self) -> Simple<usize> {
+        let mut items = self.items.lock().unwrap();
+        let count = items.len();
+        items.clear();
+        Simple { inner: count }
+    }
+}
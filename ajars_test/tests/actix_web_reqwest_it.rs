@@ -3,9 +3,20 @@ use std::time::Duration;
 use actix_rt::time::sleep;
 use ajars::reqwest::AjarsClientReqwest;
 use ajars::reqwest::reqwest::ClientBuilder;
-use ajars::{Rest, RestFluent, RestType};
+use ajars::{
+    AjarsClient, AjarsError, AjarsRequestBuilder, CircuitBreaker, CircuitState, ContentRange, FilePart, NoError,
+    PageRequest, Rest, RestFluent, RestType, RetryPolicy, SseEvent,
+};
 use ajars_test::actix_web::spawn_actix_web;
-use ajars_test::api::Simple;
+use ajars_test::api::{
+    BATCH, CREATE, CREATE_NUMBER, CREATE_UNDECLARED, DOWNLOAD, Document, ECHO_CBOR, ECHO_DELETE_BODY, ECHO_MSGPACK,
+    ECHO_POST_QUERY, ECHO_PUT_FORM, EXISTS, EXPORT, ExportRequest, FAILING, FAILING_UNDECLARED, FLAKY, FLAKY_FAILURES,
+    FLAKY_POST, FLAKY_POST_IDEMPOTENT, Failure, GROUP_ECHO_GET, GROUP_ECHO_POST, GROUP_ITEM, INFO_DELETE, ITEM,
+    InfoRequest, InfoResponse, Item, ItemPath, MAYBE, MULTIPLES, MultiplesRequest, NUMBERS, NUMBERS_TOTAL,
+    ReportRequest, SIGNUP, SIGNUP_UNCHECKED, Signup, Simple, Stored, StoredFile, TICKS, TicksRequest, UNIT, UPLOAD,
+    api_group,
+};
+use futures_util::StreamExt;
 
 #[actix_rt::test]
 async fn test_reqwest_rest() {
@@ -15,6 +26,16 @@ async fn test_reqwest_rest() {
     )))
     .await;
     perform_reqwest_call(&Rest::<Simple<String>, Simple<String>>::get("/api/const")).await;
+    perform_reqwest_call(&RestFluent::<Simple<String>, Simple<String>>::options(format!(
+        "/api/{}",
+        rand::random::<u64>()
+    )))
+    .await;
+    perform_reqwest_call(&RestFluent::<Simple<String>, Simple<String>>::patch(format!(
+        "/api/{}",
+        rand::random::<u64>()
+    )))
+    .await;
     perform_reqwest_call(&RestFluent::<Simple<String>, Simple<String>>::post(format!(
         "/api/{}",
         rand::random::<u64>()
@@ -24,7 +45,634 @@ async fn test_reqwest_rest() {
         .await;
 }
 
-async fn perform_reqwest_call<REST: 'static + Clone + Send + RestType<Simple<String>, Simple<String>>>(rest: &REST) {
+#[actix_rt::test]
+async fn test_reqwest_head() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    let req_data = Simple { inner: format!("{}", rand::random::<u64>()) };
+
+    // Act
+    let response = ajars.request(&EXISTS).send(&req_data).await;
+
+    // Assert
+    assert!(response.is_ok(), "{:?}", response);
+}
+
+#[actix_rt::test]
+async fn test_reqwest_path_params() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    let path = ItemPath { id: rand::random::<u64>(), name: "name with spaces/and slashes".to_owned() };
+    let req_data = Simple { inner: format!("{}", rand::random::<u64>()) };
+
+    // Act
+    let response = ajars.request_with_path(&ITEM, &path).send(&req_data).await;
+
+    // Assert
+    assert_eq!(Item { path, inner: req_data.inner }, response.unwrap());
+}
+
+#[actix_rt::test]
+async fn test_reqwest_typed_error() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    let req_data = Simple { inner: format!("{}", rand::random::<u64>()) };
+
+    // Act
+    let response = ajars.request(&FAILING).send(&req_data).await;
+
+    // Assert
+    match response {
+        Err(AjarsError::Endpoint { status, error }) => {
+            assert_eq!(409, status);
+            assert_eq!(Failure { reason: req_data.inner }, error);
+        }
+        other => panic!("Expected an endpoint error, got: {:?}", other),
+    }
+}
+
+#[actix_rt::test]
+async fn test_reqwest_codecs() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    let req_data = Simple { inner: format!("{}", rand::random::<u64>()) };
+
+    // Act
+    let cbor_response = ajars.request(&ECHO_CBOR).send(&req_data).await;
+    let msgpack_response = ajars.request(&ECHO_MSGPACK).send(&req_data).await;
+
+    // Assert
+    assert_eq!(req_data, cbor_response.unwrap());
+    assert_eq!(req_data, msgpack_response.unwrap());
+}
+
+#[actix_rt::test]
+async fn test_reqwest_input_locations() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    let req_data = Simple { inner: format!("{}", rand::random::<u64>()) };
+
+    // Act
+    let delete_response = ajars.request(&ECHO_DELETE_BODY).send(&req_data).await;
+    let post_response = ajars.request(&ECHO_POST_QUERY).send(&req_data).await;
+    let put_response = ajars.request(&ECHO_PUT_FORM).send(&req_data).await;
+
+    // Assert
+    assert_eq!(req_data, delete_response.unwrap());
+    assert_eq!(req_data, post_response.unwrap());
+    assert_eq!(req_data, put_response.unwrap());
+}
+
+#[actix_rt::test]
+async fn test_reqwest_unit() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    // Act
+    let unit_response = ajars.request(&UNIT).send(&()).await;
+    let maybe_response = ajars.request(&MAYBE).send(&()).await;
+
+    // Assert
+    assert!(unit_response.is_ok(), "{:?}", unit_response);
+    assert_eq!(None, maybe_response.unwrap());
+}
+
+#[actix_rt::test]
+async fn test_reqwest_success_status() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    let req_data = Simple { inner: format!("{}", rand::random::<u64>()) };
+
+    // Act
+    let declared_response = ajars.request(&CREATE).send(&req_data).await;
+    let undeclared_response = ajars.request(&CREATE_UNDECLARED).send(&req_data).await;
+
+    // Assert
+    assert_eq!(req_data, declared_response.unwrap());
+
+    match undeclared_response {
+        Err(AjarsError::UnexpectedStatus { status, expected }) => {
+            assert_eq!(201, status);
+            assert_eq!(200, expected);
+        }
+        _ => panic!("Expected an unexpected status error, got {:?}", undeclared_response),
+    }
+}
+
+#[actix_rt::test]
+async fn test_reqwest_status_and_decode_errors() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    let req_data = Simple { inner: "not a number".to_owned() };
+
+    // Act
+    let status_response = ajars.request(&FAILING_UNDECLARED).send(&req_data).await;
+    let decode_response = ajars.request(&CREATE_NUMBER).send(&req_data).await;
+
+    // Assert
+    match status_response {
+        Err(AjarsError::Status { status, headers, body }) => {
+            assert_eq!(409, status.status());
+            assert!(headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("content-type")));
+            assert_eq!(r#"{"reason":"not a number"}"#, String::from_utf8(body).unwrap());
+        }
+        _ => panic!("Expected an error status, got {:?}", status_response),
+    }
+
+    match decode_response {
+        Err(AjarsError::Decode(error)) => assert_eq!(Some("inner"), error.path()),
+        _ => panic!("Expected a decode error, got {:?}", decode_response),
+    }
+}
+
+#[actix_rt::test]
+async fn test_reqwest_full_response() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    let req_data = Simple { inner: format!("{}", rand::random::<u64>()) };
+
+    // Act
+    let response = ajars.request(&CREATE).send_full(&req_data).await.unwrap();
+
+    // Assert
+    assert_eq!(201, response.status().status());
+    assert_eq!(Some("application/json"), response.header("Content-Type"));
+    assert_eq!(&req_data, response.body());
+}
+
+#[actix_rt::test]
+async fn test_reqwest_retry() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let policy = RetryPolicy::default().with_backoff(Duration::from_millis(10), Duration::from_millis(100));
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port))
+        .with_retry_policy(policy.clone());
+    let exhausted =
+        AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port))
+            .with_retry_policy(policy.with_max_attempts(2));
+
+    let req_data = || Simple { inner: format!("{}", rand::random::<u64>()) };
+
+    // Act
+    let get = ajars.request(&FLAKY).send(&req_data()).await.unwrap();
+    let idempotent_post = ajars.request(&FLAKY_POST_IDEMPOTENT).send(&req_data()).await.unwrap();
+    let post = ajars.request(&FLAKY_POST).send(&req_data()).await.err();
+    let exhausted = exhausted.request(&FLAKY).send(&req_data()).await.err();
+
+    // Assert
+    assert_eq!(Simple { inner: FLAKY_FAILURES + 1 }, get);
+    assert_eq!(Simple { inner: FLAKY_FAILURES + 1 }, idempotent_post);
+    assert_eq!(Some(503), post.and_then(|err| err.status().map(u16::from)));
+    assert_eq!(Some(503), exhausted.and_then(|err| err.status().map(u16::from)));
+}
+
+#[actix_rt::test]
+async fn test_reqwest_request_errors() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let policy = RetryPolicy::default().with_backoff(Duration::from_millis(10), Duration::from_millis(100));
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port))
+        .with_retry_policy(policy);
+
+    // A nested input cannot be encoded in the query string
+    let nested = Rest::<InfoRequest<Simple<String>>, Simple<String>>::get("/api/nested");
+
+    // Act
+    let invalid_header = ajars
+        .request(&FLAKY)
+        .header("X-Invalid", "new\nline")
+        .send(&Simple { inner: format!("{}", rand::random::<u64>()) })
+        .await
+        .err();
+    let invalid_query =
+        ajars.request(&nested).send(&InfoRequest { payload: Simple { inner: "nested".to_owned() } }).await.err();
+
+    // Assert
+    assert!(matches!(invalid_header, Some(AjarsError::Transport(_))), "{:?}", invalid_header);
+    assert!(matches!(invalid_query, Some(AjarsError::Transport(_))), "{:?}", invalid_query);
+}
+
+#[actix_rt::test]
+async fn test_reqwest_circuit_breaker() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let base_url = format!("http://127.0.0.1:{}", port);
+    let breaker = CircuitBreaker::new()
+        .with_failure_threshold(FLAKY_FAILURES as u32)
+        .with_open_duration(Duration::from_millis(300))
+        .per_endpoint();
+    let ajars =
+        AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), &base_url).with_circuit_breaker(breaker.clone());
+    let circuit = breaker.circuit(&base_url, &FLAKY);
+
+    let req_data = Simple { inner: format!("{}", rand::random::<u64>()) };
+
+    // Act
+    let first = ajars.request(&FLAKY).send(&req_data).await.err();
+    let second = ajars.request(&FLAKY).send(&req_data).await.err();
+    let open = ajars.request(&FLAKY).send(&req_data).await.err();
+    let open_state = breaker.state(&circuit);
+    let other_endpoint = ajars.request(&CREATE).send(&req_data).await;
+
+    sleep(Duration::from_millis(300)).await;
+    let half_open_state = breaker.state(&circuit);
+    let probe = ajars.request(&FLAKY).send(&req_data).await.unwrap();
+
+    // Assert
+    assert_eq!(Some(503), first.and_then(|err| err.status().map(u16::from)));
+    assert_eq!(Some(503), second.and_then(|err| err.status().map(u16::from)));
+    match open {
+        Some(AjarsError::CircuitOpen { circuit: open_circuit }) => assert_eq!(circuit, open_circuit),
+        other => panic!("The circuit should be open, got {:?}", other),
+    }
+    assert_eq!(CircuitState::Open, open_state);
+    assert_eq!(req_data, other_endpoint.unwrap());
+    assert_eq!(CircuitState::HalfOpen, half_open_state);
+    // The request that failed fast was not sent, so the probe is the third attempt received by the server
+    assert_eq!(Simple { inner: FLAKY_FAILURES + 1 }, probe);
+    assert_eq!(CircuitState::Closed, breaker.state(&circuit));
+}
+
+#[actix_rt::test]
+async fn test_reqwest_stream() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    // Act
+    let items = ajars.request_stream(&EXPORT).send(&ExportRequest { count: 3 }).await.unwrap();
+    let items = items.map(Result::unwrap).collect::<Vec<_>>().await;
+    let failure = ajars.request_stream(&EXPORT).send(&ExportRequest { count: 0 }).await.err();
+
+    // Assert
+    assert_eq!(vec![Simple { inner: 0 }, Simple { inner: 1 }, Simple { inner: 2 }], items);
+
+    match failure {
+        Some(AjarsError::Endpoint { status, error }) => {
+            assert_eq!(409, status);
+            assert_eq!(Failure { reason: "Nothing to export".to_owned() }, error);
+        }
+        _ => panic!("Expected the typed error of the endpoint, got {:?}", failure),
+    }
+}
+
+#[actix_rt::test]
+async fn test_reqwest_upload() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    let files = [FilePart::new("a.txt", "hello"), FilePart::new("b.json", "{}").with_content_type("application/json")];
+
+    // Act
+    let stored = ajars
+        .request_upload_with_path(&UPLOAD, &"docs".to_owned())
+        .files(files)
+        .send(&Document { title: "notes".to_owned() })
+        .await
+        .unwrap();
+    let failure = ajars
+        .request_upload_with_path(&UPLOAD, &"docs".to_owned())
+        .send(&Document { title: "notes".to_owned() })
+        .await
+        .err();
+
+    // Assert
+    let expected = Stored {
+        folder: "docs".to_owned(),
+        title: "notes".to_owned(),
+        files: vec![
+            StoredFile {
+                file_name: "a.txt".to_owned(),
+                content_type: "application/octet-stream".to_owned(),
+                content: "hello".to_owned(),
+            },
+            StoredFile {
+                file_name: "b.json".to_owned(),
+                content_type: "application/json".to_owned(),
+                content: "{}".to_owned(),
+            },
+        ],
+    };
+    assert_eq!(expected, stored);
+
+    match failure {
+        Some(AjarsError::Endpoint { status, error }) => {
+            assert_eq!(409, status);
+            assert_eq!(Failure { reason: "No files".to_owned() }, error);
+        }
+        _ => panic!("Expected the typed error of the endpoint, got {:?}", failure),
+    }
+}
+
+#[actix_rt::test]
+async fn test_reqwest_download() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    // Act
+    let full = ajars
+        .request_download_with_path(&DOWNLOAD, &"sales".to_owned())
+        .send(&ReportRequest { lines: 3 })
+        .await
+        .unwrap();
+    let content_type = full.content_type().map(str::to_owned);
+    let file_name = full.file_name();
+    let full_range = full.content_range();
+    let full_body = full.bytes().await.unwrap();
+
+    let resumed = ajars
+        .request_download_with_path(&DOWNLOAD, &"sales".to_owned())
+        .range(8..)
+        .send(&ReportRequest { lines: 3 })
+        .await
+        .unwrap();
+    let resumed_range = resumed.content_range();
+    let resumed_body = resumed.stream().map(|chunk| chunk.unwrap()).concat().await;
+
+    let failure =
+        ajars.request_download_with_path(&DOWNLOAD, &"sales".to_owned()).send(&ReportRequest { lines: 0 }).await.err();
+
+    // Assert
+    assert_eq!(Some("text/plain"), content_type.as_deref());
+    assert_eq!(Some("sales.txt".to_owned()), file_name);
+    assert_eq!(None, full_range);
+    assert_eq!("sales 0\nsales 1\nsales 2\n", String::from_utf8(full_body).unwrap());
+
+    assert_eq!(Some(ContentRange { start: 8, end: 23, size: Some(24) }), resumed_range);
+    assert_eq!("sales 1\nsales 2\n", String::from_utf8(resumed_body).unwrap());
+
+    match failure {
+        Some(AjarsError::Endpoint { status, error }) => {
+            assert_eq!(404, status);
+            assert_eq!(Failure { reason: "Empty report".to_owned() }, error);
+        }
+        _ => panic!("Expected the typed error of the endpoint, got {:?}", failure.map(|_| ())),
+    }
+}
+
+#[actix_rt::test]
+async fn test_reqwest_paginate() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    let multiples = MultiplesRequest { factor: 3, cursor: None, limit: Some(2) };
+
+    // Act
+    let numbers = ajars.paginate(&NUMBERS).send(PageRequest::default()).map(Result::unwrap).collect::<Vec<_>>().await;
+    let all_multiples =
+        ajars.paginate(&MULTIPLES).send(multiples.clone()).map(Result::unwrap).collect::<Vec<_>>().await;
+    let first_page = ajars.request(&MULTIPLES).send(&multiples).await.unwrap();
+
+    // Assert
+    assert_eq!((0..NUMBERS_TOTAL).collect::<Vec<_>>(), numbers);
+    assert_eq!(vec![0, 3, 6, 9, 12, 15, 18], all_multiples);
+
+    assert_eq!(vec![0, 3], first_page.items);
+    assert_eq!(Some(PageRequest::after("3", Some(2))), first_page.next);
+    assert_eq!(Some("/api/multiples?factor=3&cursor=3&limit=2".to_owned()), first_page.next_link);
+}
+
+#[actix_rt::test]
+async fn test_reqwest_batch() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    let path = ItemPath { id: rand::random::<u64>(), name: "name".to_owned() };
+    let req_data = Simple { inner: format!("{}", rand::random::<u64>()) };
+    let not_valid = Signup { username: "u".to_owned() };
+
+    let mut batch = ajars.batch(&BATCH);
+    let item = batch.call_with_path(&ITEM, &path, &req_data).unwrap();
+    let failing = batch.call(&FAILING, &req_data).unwrap();
+    let cbor = batch.call(&ECHO_CBOR, &req_data).unwrap();
+    let created = batch.call(&CREATE, &req_data).unwrap();
+    let unit = batch.call(&UNIT, &()).unwrap();
+    let client_validation = batch.call(&SIGNUP, &not_valid);
+    let server_validation = batch.call(&SIGNUP_UNCHECKED, &not_valid).unwrap();
+    let not_batched = batch.call(&EXISTS, &req_data).unwrap();
+
+    // Act
+    let results = batch.send().await.unwrap();
+
+    // Assert
+    assert_eq!(7, results.len());
+    assert_eq!(Item { path, inner: req_data.inner.clone() }, results.get(item).unwrap());
+    assert_eq!(req_data, results.get(cbor).unwrap());
+    assert_eq!(req_data, results.get(created).unwrap());
+    results.get(unit).unwrap();
+
+    match results.get(failing) {
+        Err(AjarsError::Endpoint { status, error }) => {
+            assert_eq!(409, status);
+            assert_eq!(Failure { reason: req_data.inner.clone() }, error);
+        }
+        result => panic!("Expected the endpoint error, got {:?}", result),
+    }
+
+    match client_validation {
+        Err(AjarsError::Validation(errors)) => assert!(errors.field_errors().contains_key("username")),
+        result => panic!("Expected a validation error, got {:?}", result.err()),
+    }
+
+    match results.get(server_validation) {
+        Err(AjarsError::BatchCall { status, .. }) => assert_eq!(422, status),
+        result => panic!("Expected an unprocessable entity status, got {:?}", result),
+    }
+
+    match results.get(not_batched) {
+        Err(AjarsError::BatchCall { status, .. }) => assert_eq!(404, status),
+        result => panic!("Expected a not found status, got {:?}", result),
+    }
+}
+
+#[actix_rt::test]
+async fn test_reqwest_generic_client() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    // Act
+    let response = send_info(&ajars, "generic").await.unwrap();
+    // The futures of the native clients are `Send`, unlike the ones of the generic request builders
+    let concrete =
+        assert_send(ajars.request(&INFO_DELETE).send(&InfoRequest { payload: "concrete".to_owned() })).await.unwrap();
+
+    // Assert
+    assert_eq!("generic", response.request_payload);
+    assert_eq!(Some("ajars"), response.request_headers.get("x-sdk").map(String::as_str));
+    assert_eq!(Some("Basic dWZvOnNjb3V0IQ=="), response.request_headers.get("authorization").map(String::as_str));
+    assert_eq!("concrete", concrete.request_payload);
+}
+
+/// Checks at compile time that a value, like a future, can be sent to another thread.
+fn assert_send<T: Send>(value: T) -> T {
+    value
+}
+
+/// Sends a request with any client backend, as a library that is generic over the transport does
+async fn send_info<C: AjarsClient>(
+    client: &C,
+    payload: &str,
+) -> Result<InfoResponse<String>, AjarsError<NoError, C::Error>> {
+    client
+        .request(&INFO_DELETE)
+        .header("x-sdk", "ajars")
+        .basic_auth("ufo", Some("scout!"))
+        .send(&InfoRequest { payload: payload.to_owned() })
+        .await
+}
+
+#[actix_rt::test]
+async fn test_reqwest_sse() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    // Act
+    let events = ajars
+        .request_sse(&TICKS)
+        .reconnect_delay(Duration::from_millis(10))
+        .send(&TicksRequest { batch: 2 })
+        .await
+        .unwrap();
+    // Every connection sends two events, so the client reconnects twice
+    let events = events.take(5).map(Result::unwrap).collect::<Vec<_>>().await;
+    let failure = ajars.request_sse(&TICKS).send(&TicksRequest { batch: 0 }).await.err();
+    let invalid_header =
+        ajars.request_sse(&TICKS).header("X-Invalid", "new\nline").send(&TicksRequest { batch: 2 }).await.err();
+
+    // Assert
+    let expected = (1..=5).map(|inner| SseEvent::new(Simple { inner }).with_id(inner.to_string())).collect::<Vec<_>>();
+    assert_eq!(expected, events);
+
+    match failure {
+        Some(AjarsError::Endpoint { status, error }) => {
+            assert_eq!(409, status);
+            assert_eq!(Failure { reason: "Empty batch".to_owned() }, error);
+        }
+        _ => panic!("Expected the typed error of the endpoint, got {:?}", failure),
+    }
+    assert!(matches!(invalid_header, Some(AjarsError::Transport(_))), "{:?}", invalid_header);
+}
+
+#[actix_rt::test]
+async fn test_reqwest_validation() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    let valid = Signup { username: "ufoscout".to_owned() };
+    let not_valid = Signup { username: "u".to_owned() };
+
+    // Act
+    let valid_response = ajars.request(&SIGNUP).send(&valid).await;
+    let client_response = ajars.request(&SIGNUP).send(&not_valid).await;
+    let server_response = ajars.request(&SIGNUP_UNCHECKED).send(&not_valid).await;
+
+    // Assert
+    assert_eq!(valid.username, valid_response.unwrap().inner);
+
+    match client_response {
+        Err(AjarsError::Validation(errors)) => assert!(errors.field_errors().contains_key("username")),
+        _ => panic!("Expected a validation error, got {:?}", client_response),
+    }
+
+    match server_response {
+        Err(AjarsError::Status { status, .. }) => assert_eq!(422, status.status()),
+        _ => panic!("Expected an unprocessable entity status, got {:?}", server_response),
+    }
+}
+
+#[actix_rt::test]
+async fn test_reqwest_group() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientReqwest::new(ClientBuilder::new().build().unwrap(), format!("http://127.0.0.1:{}", port));
+
+    let req_data = Simple { inner: format!("{}", rand::random::<u64>()) };
+
+    let group = api_group();
+    let path = ItemPath { id: rand::random::<u64>(), name: "name".to_owned() };
+
+    // Act
+    let get_response = ajars.request(&group.scope(&GROUP_ECHO_GET)).send(&req_data).await;
+    let post_response = ajars.request(&group.scope(&GROUP_ECHO_POST)).send(&req_data).await;
+    let item_response = ajars.request_with_path(&group.scope(&GROUP_ITEM), &path).send(&req_data).await;
+
+    // Assert
+    assert_eq!(req_data, get_response.unwrap());
+    assert_eq!(req_data, post_response.unwrap());
+    assert_eq!(Item { path, inner: req_data.inner }, item_response.unwrap());
+}
+
+async fn perform_reqwest_call<
+    REST: 'static + Clone + Send + RestType<Simple<String>, Simple<String>, PathParams = (), Error = NoError>,
+>(
+    rest: &REST,
+) {
     // Arrange
     let rest_clone = rest.clone();
     let port = spawn_actix_web(rest_clone);
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use ajars::Rest;
+use ajars::tungstenite::AjarsClientTungstenite;
+use ajars_test::actix_web::spawn_actix_web;
+use ajars_test::api::{CHAT, Simple};
+use futures_util::{SinkExt, StreamExt};
+use tokio::time::sleep;
+
+#[actix_rt::test]
+async fn test_tungstenite_websocket() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientTungstenite::new(format!("http://127.0.0.1:{}", port));
+
+    // Act
+    let (mut sender, receiver) = ajars.request_websocket_with_path(&CHAT, &"rust".to_owned()).connect().await.unwrap();
+    sender.send(Simple { inner: "hello".to_owned() }).await.unwrap();
+    sender.send(Simple { inner: "world".to_owned() }).await.unwrap();
+    let answers = receiver.take(2).map(Result::unwrap).collect::<Vec<_>>().await;
+    sender.close().await.unwrap();
+
+    // Assert
+    assert_eq!(vec![Simple { inner: "rust: hello".to_owned() }, Simple { inner: "rust: world".to_owned() }], answers);
+}
+
+#[actix_rt::test]
+async fn test_tungstenite_websocket_should_fail_to_connect_to_an_unknown_path() {
+    // Arrange
+    let port = spawn_actix_web(Rest::<Simple<String>, Simple<String>>::get("/api/const"));
+    sleep(Duration::from_millis(200)).await;
+
+    let ajars = AjarsClientTungstenite::new(format!("ws://127.0.0.1:{}/api/unknown", port));
+
+    // Act
+    let result = ajars.request_websocket_with_path(&CHAT, &"rust".to_owned()).connect().await;
+
+    // Assert
+    assert!(result.is_err());
+}
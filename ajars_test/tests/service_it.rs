@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use ajars::AjarsError;
+use ajars::reqwest::AjarsClientReqwest;
+use ajars::reqwest::reqwest::ClientBuilder;
+use ajars::surf::AjarsClientSurf;
+use ajars_test::actix_web::spawn_actix_web_service;
+use ajars_test::api::Simple;
+use ajars_test::axum::spawn_axum_service;
+use ajars_test::service::item_api;
+use tokio::time::sleep;
+
+// The same calls are performed by every typed client against every server
+macro_rules! perform_service_calls {
+    ($client:expr) => {{
+        let client = $client;
+        let item = Simple { inner: format!("{}", rand::random::<u64>()) };
+
+        // Create
+        let id = client.create_item(&item).await.unwrap().inner;
+        match client.create_item(&item).await {
+            Err(AjarsError::Endpoint { status, error }) => {
+                assert_eq!(409, status);
+                assert!(error.reason.contains(&item.inner));
+            }
+            other => panic!("Expected an endpoint error, got {:?}", other),
+        }
+
+        // Get
+        let prefix = Simple { inner: "item: ".to_owned() };
+        assert_eq!(format!("item: {}", item.inner), client.get_item(&id, &prefix).await.unwrap().inner);
+        match client.get_item(&(id + 1000), &prefix).await {
+            Err(AjarsError::Endpoint { status, .. }) => assert_eq!(404, status),
+            other => panic!("Expected an endpoint error, got {:?}", other),
+        }
+
+        // Update
+        let updated = Simple { inner: "updated".to_owned() };
+        assert!(client.update_item(&id, &updated).await.unwrap().inner);
+        assert_eq!("updated", client.get_item(&id, &Simple { inner: "".to_owned() }).await.unwrap().inner);
+
+        // Clear
+        assert_eq!(1, client.clear().await.unwrap().inner);
+        assert_eq!(0, client.clear().await.unwrap().inner);
+    }};
+}
+
+fn reqwest_client(port: u16) -> item_api::ReqwestClient {
+    item_api::ReqwestClient::new(AjarsClientReqwest::new(
+        ClientBuilder::new().build().unwrap(),
+        format!("http://127.0.0.1:{}", port),
+    ))
+}
+
+fn surf_client(port: u16) -> item_api::SurfClient {
+    item_api::SurfClient::new(AjarsClientSurf::new(ajars::surf::surf::client(), format!("http://127.0.0.1:{}", port)))
+}
+
+#[actix_rt::test]
+async fn test_reqwest_service_axum() {
+    let port = spawn_axum_service();
+    sleep(Duration::from_millis(200)).await;
+
+    perform_service_calls!(reqwest_client(port));
+}
+
+#[actix_rt::test]
+async fn test_reqwest_service_actix_web() {
+    let port = spawn_actix_web_service();
+    sleep(Duration::from_millis(200)).await;
+
+    perform_service_calls!(reqwest_client(port));
+}
+
+#[actix_rt::test]
+async fn test_surf_service_axum() {
+    let port = spawn_axum_service();
+    sleep(Duration::from_millis(200)).await;
+
+    perform_service_calls!(surf_client(port));
+}
+
+#[actix_rt::test]
+async fn test_surf_service_actix_web() {
+    let port = spawn_actix_web_service();
+    sleep(Duration::from_millis(200)).await;
+
+    perform_service_calls!(surf_client(port));
+}
@@ -0,0 +1,436 @@
+use std::fmt::Write;
+
+use ajars_core::{HttpMethod, InputLocation, Json, RestType};
+use schemars::generate::SchemaSettings;
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+pub mod schemars {
+    pub use ::schemars::*;
+}
+
+const DEFINITIONS_PATH: &str = "/definitions";
+
+/// The helpers shared by the generated functions. They follow the same rules of `ajars_client_web`.
+const RUNTIME: &str = r#"export class AjarsError<E> extends Error {
+  constructor(
+    public readonly status: number,
+    public readonly error: E | undefined,
+    message: string,
+  ) {
+    super(message);
+  }
+}
+
+type InputLocation = "query" | "body" | "form";
+
+function fillPath(template: string, params: unknown): string {
+  let position = 0;
+  return template.replace(/\{([^}]*)\}/g, (_, name: string) => {
+    let value: unknown;
+    if (Array.isArray(params)) {
+      value = params[position];
+    } else if (params !== null && typeof params === "object") {
+      value = (params as Record<string, unknown>)[name];
+    } else if (position === 0) {
+      value = params;
+    }
+    position += 1;
+    if (value === undefined || value === null || typeof value === "object") {
+      throw new Error(`Missing scalar value for the parameter [${name}] of the path [${template}]`);
+    }
+    return encodeURIComponent(String(value));
+  });
+}
+
+function urlEncoded(input: unknown): URLSearchParams {
+  const params = new URLSearchParams();
+  if (input !== null && typeof input === "object") {
+    for (const [key, value] of Object.entries(input)) {
+      if (value !== undefined && value !== null) {
+        params.append(key, String(value));
+      }
+    }
+  }
+  return params;
+}
+
+async function send<O, E>(
+  url: string,
+  method: string,
+  location: InputLocation,
+  input: unknown,
+  errorStatus: number,
+  successStatus: number,
+  init?: RequestInit,
+): Promise<O> {
+  const headers = new Headers(init?.headers);
+  let body: BodyInit | undefined;
+  if (input === null || input === undefined) {
+    // An empty input, like `()`, is sent without a query string and without a body
+  } else if (location === "query") {
+    const query = urlEncoded(input).toString();
+    if (query.length > 0) {
+      url = `${url}?${query}`;
+    }
+  } else if (location === "form") {
+    body = urlEncoded(input);
+  } else {
+    headers.set("Content-Type", "application/json");
+    body = JSON.stringify(input);
+  }
+
+  const response = await fetch(url, { ...init, method, headers, body });
+
+  if (response.status >= 400) {
+    let error: E | undefined;
+    if (response.status === errorStatus) {
+      error = await response.json().catch(() => undefined);
+    }
+    throw new AjarsError<E>(response.status, error, `Error HTTP status code received: ${response.status}`);
+  }
+
+  // A successful status other than the one declared by the endpoint breaks the contract of the endpoint
+  if (response.status !== successStatus && !(response.status === 204 && successStatus === 200)) {
+    throw new AjarsError<E>(
+      response.status,
+      undefined,
+      `Unexpected HTTP status code received: ${response.status}. Expected: ${successStatus}`,
+    );
+  }
+
+  // A response to a HEAD request never has a body, a '204 No Content' response carries an empty output
+  if (method === "HEAD" || response.status === 204) {
+    return null as O;
+  }
+  return (await response.json()) as O;
+}
+"#;
+
+/// Builds a TypeScript module from a set of endpoints.
+///
+/// The module contains a type for each type used by the endpoints and a typed `fetch` wrapper for each endpoint,
+/// that sends the input to the input location of the endpoint and throws an `AjarsError` on error statuses.
+/// The types are generated from the `schemars` schemas of the Rust types.
+pub struct TypeScript {
+    functions: Vec<String>,
+    generator: SchemaGenerator,
+}
+
+impl Default for TypeScript {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeScript {
+    pub fn new() -> Self {
+        let settings = SchemaSettings::draft2020_12().with(|settings| {
+            settings.definitions_path = DEFINITIONS_PATH.into();
+            settings.meta_schema = None;
+        });
+        Self { functions: Vec::new(), generator: settings.into_generator() }
+    }
+
+    /// Adds a function with the given name that calls the endpoint.
+    /// Only the endpoints with the JSON codec can be called from TypeScript.
+    pub fn endpoint<I, O, REST>(mut self, name: &str, rest: &REST) -> Self
+    where
+        I: Serialize + DeserializeOwned + JsonSchema,
+        O: Serialize + DeserializeOwned + JsonSchema,
+        REST: RestType<I, O, Codec = Json>,
+        REST::PathParams: JsonSchema,
+        REST::Error: JsonSchema,
+    {
+        let input = self.type_of::<I>();
+        let output = self.type_of::<O>();
+        let error = self.type_of::<REST::Error>();
+        let path = rest.path();
+
+        let mut arguments = vec!["baseUrl: string".to_owned()];
+        if path.contains('{') {
+            arguments.push(format!("path: {}", self.type_of::<REST::PathParams>()));
+        }
+        arguments.push(format!("input: {}", input));
+        arguments.push("init?: RequestInit".to_owned());
+
+        let url = if path.contains('{') {
+            format!("baseUrl + fillPath({}, path)", string_literal(path))
+        } else {
+            format!("baseUrl + {}", string_literal(path))
+        };
+
+        let location = match rest.input_location() {
+            InputLocation::Query => "query",
+            InputLocation::Body => "body",
+            InputLocation::Form => "form",
+        };
+
+        self.functions.push(format!(
+            "export function {name}({arguments}): Promise<{output}> {{\n  return send<{output}, {error}>({url}, \"{method}\", \"{location}\", input, {error_status}, {success_status}, init);\n}}\n",
+            arguments = arguments.join(", "),
+            method = method_name(rest.method()),
+            error_status = rest.error_status(),
+            success_status = rest.success_status(),
+        ));
+        self
+    }
+
+    /// Returns the TypeScript module
+    pub fn to_module(&self) -> String {
+        let mut module = String::from("// This file is generated by AjaRS. Do not edit it by hand.\n\n");
+
+        for (name, schema) in self.generator.definitions() {
+            let name = type_name(name);
+            match schema.as_object().filter(|schema| is_interface(schema)) {
+                Some(schema) => {
+                    let _ = writeln!(module, "export interface {} {}\n", name, object_type(schema, 0));
+                }
+                None => {
+                    let _ = writeln!(module, "export type {} = {};\n", name, ts_type(schema, 0));
+                }
+            }
+        }
+
+        module.push_str(RUNTIME);
+
+        for function in &self.functions {
+            module.push('\n');
+            module.push_str(function);
+        }
+
+        module
+    }
+
+    fn type_of<T: JsonSchema>(&mut self) -> String {
+        let schema: Schema = self.generator.subschema_for::<T>();
+        ts_type(schema.as_value(), 0)
+    }
+}
+
+/// Converts a JSON schema to a TypeScript type
+fn ts_type(schema: &Value, indent: usize) -> String {
+    let schema = match schema {
+        Value::Bool(true) => return "unknown".to_owned(),
+        Value::Bool(false) => return "never".to_owned(),
+        Value::Object(schema) => schema,
+        _ => return "unknown".to_owned(),
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return type_name(reference.rsplit('/').next().unwrap_or(reference));
+    }
+    if let Some(value) = schema.get("const") {
+        return value.to_string();
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return union(values.iter().map(Value::to_string));
+    }
+    for (keyword, separator) in [("oneOf", " | "), ("anyOf", " | "), ("allOf", " & ")] {
+        if let Some(schemas) = schema.get(keyword).and_then(Value::as_array) {
+            let types: Vec<String> = schemas.iter().map(|schema| wrap(ts_type(schema, indent))).collect();
+            return types.join(separator);
+        }
+    }
+
+    match schema.get("type") {
+        Some(Value::String(kind)) => type_of_kind(kind, schema, indent),
+        Some(Value::Array(kinds)) => {
+            union(kinds.iter().filter_map(Value::as_str).map(|kind| wrap(type_of_kind(kind, schema, indent))))
+        }
+        _ => "unknown".to_owned(),
+    }
+}
+
+fn type_of_kind(kind: &str, schema: &Map<String, Value>, indent: usize) -> String {
+    match kind {
+        "string" => "string".to_owned(),
+        "integer" | "number" => "number".to_owned(),
+        "boolean" => "boolean".to_owned(),
+        "null" => "null".to_owned(),
+        "array" => match (schema.get("prefixItems").and_then(Value::as_array), schema.get("items")) {
+            (Some(items), _) => {
+                let items: Vec<String> = items.iter().map(|item| ts_type(item, indent)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            (None, Some(items)) => format!("{}[]", wrap(ts_type(items, indent))),
+            (None, None) => "unknown[]".to_owned(),
+        },
+        "object" => object_type(schema, indent),
+        _ => "unknown".to_owned(),
+    }
+}
+
+fn object_type(schema: &Map<String, Value>, indent: usize) -> String {
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let additional = schema.get("additionalProperties").filter(|additional| additional.as_bool() != Some(false));
+
+    if properties.is_none_or(Map::is_empty) {
+        return match additional {
+            Some(additional) => format!("Record<string, {}>", ts_type(additional, indent)),
+            None => "Record<string, never>".to_owned(),
+        };
+    }
+
+    let required = schema.get("required").and_then(Value::as_array);
+    let padding = "  ".repeat(indent + 1);
+    let mut object = String::from("{\n");
+    for (name, property) in properties.into_iter().flatten() {
+        let optional =
+            if required.is_some_and(|required| required.contains(&Value::from(name.as_str()))) { "" } else { "?" };
+        let _ = writeln!(object, "{}{}{}: {};", padding, property_name(name), optional, ts_type(property, indent + 1));
+    }
+    if let Some(additional) = additional {
+        let _ = writeln!(object, "{}[key: string]: {};", padding, ts_type(additional, indent + 1));
+    }
+    object.push_str(&"  ".repeat(indent));
+    object.push('}');
+    object
+}
+
+/// Returns whether the schema is a plain object with properties, that can be declared as an interface
+fn is_interface(schema: &Map<String, Value>) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("object") && schema.contains_key("properties")
+}
+
+fn union(types: impl Iterator<Item = String>) -> String {
+    let mut unique: Vec<String> = Vec::new();
+    for ts_type in types {
+        if !unique.contains(&ts_type) {
+            unique.push(ts_type);
+        }
+    }
+    unique.join(" | ")
+}
+
+/// Wraps the unions and intersections in parentheses, so they can be composed
+fn wrap(ts_type: String) -> String {
+    if !ts_type.starts_with('{') && (ts_type.contains(" | ") || ts_type.contains(" & ")) {
+        format!("({})", ts_type)
+    } else {
+        ts_type
+    }
+}
+
+/// Converts a schema name to a valid TypeScript identifier
+fn type_name(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' }).collect()
+}
+
+fn property_name(name: &str) -> String {
+    let identifier = name
+        .chars()
+        .enumerate()
+        .all(|(position, c)| c == '_' || c == '$' || c.is_ascii_alphabetic() || (position > 0 && c.is_ascii_digit()));
+    if identifier && !name.is_empty() { name.to_owned() } else { string_literal(name) }
+}
+
+fn string_literal(value: &str) -> String {
+    Value::from(value).to_string()
+}
+
+fn method_name(method: &HttpMethod) -> &'static str {
+    match method {
+        HttpMethod::DELETE => "DELETE",
+        HttpMethod::GET => "GET",
+        HttpMethod::HEAD => "HEAD",
+        HttpMethod::OPTIONS => "OPTIONS",
+        HttpMethod::PATCH => "PATCH",
+        HttpMethod::POST => "POST",
+        HttpMethod::PUT => "PUT",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashMap;
+
+    use ::schemars::JsonSchema;
+    use ajars_core::{InputLocation, Rest};
+    use serde::{Deserialize, Serialize};
+
+    use super::TypeScript;
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct UserRequest {
+        pub verbose: bool,
+        pub fields: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct User {
+        pub id: u64,
+        pub name: String,
+        pub roles: Vec<Role>,
+        pub attributes: HashMap<String, String>,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub enum Role {
+        Admin,
+        Guest,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct UserPath {
+        pub id: u64,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct UserError {
+        pub reason: String,
+    }
+
+    const GET_USER: Rest<UserRequest, User, UserPath> = Rest::get("/api/users/{id}");
+    const CREATE_USER: Rest<User, User, (), UserError> =
+        Rest::post("/api/users").with_error_status(409).with_success_status(201);
+    const LOGIN: Rest<UserRequest, ()> = Rest::post("/api/login").with_input_location(InputLocation::Form);
+
+    #[test]
+    fn should_generate_the_types() {
+        let module = TypeScript::new().endpoint("getUser", &GET_USER).to_module();
+
+        assert!(module.contains(
+            "export interface User {\n  attributes: Record<string, string>;\n  id: number;\n  name: string;\n  roles: Role[];\n}\n"
+        ));
+        assert!(module.contains("export type Role = \"Admin\" | \"Guest\";\n"));
+        assert!(module.contains("export interface UserRequest {\n  fields?: string | null;\n  verbose: boolean;\n}\n"));
+        assert!(module.contains("export interface UserPath {\n  id: number;\n}\n"));
+    }
+
+    #[test]
+    fn should_generate_a_function_for_each_endpoint() {
+        let module = TypeScript::new()
+            .endpoint("getUser", &GET_USER)
+            .endpoint("createUser", &CREATE_USER)
+            .endpoint("login", &LOGIN)
+            .to_module();
+
+        assert!(module.contains(
+            "export function getUser(baseUrl: string, path: UserPath, input: UserRequest, init?: RequestInit): Promise<User> {\n  return send<User, never>(baseUrl + fillPath(\"/api/users/{id}\", path), \"GET\", \"query\", input, 400, 200, init);\n}\n"
+        ));
+        assert!(module.contains(
+            "export function createUser(baseUrl: string, input: User, init?: RequestInit): Promise<User> {\n  return send<User, UserError>(baseUrl + \"/api/users\", \"POST\", \"body\", input, 409, 201, init);\n}\n"
+        ));
+        assert!(module.contains(
+            "export function login(baseUrl: string, input: UserRequest, init?: RequestInit): Promise<null> {\n  return send<null, never>(baseUrl + \"/api/login\", \"POST\", \"form\", input, 400, 200, init);\n}\n"
+        ));
+        assert!(module.contains("export class AjarsError<E> extends Error {"));
+    }
+
+    #[test]
+    fn should_quote_the_properties_that_are_not_identifiers() {
+        #[derive(Serialize, Deserialize, JsonSchema)]
+        pub struct Headers {
+            #[serde(rename = "content-type")]
+            pub content_type: String,
+        }
+
+        let module = TypeScript::new().endpoint("headers", &Rest::<Headers, ()>::get("/api/headers")).to_module();
+
+        assert!(module.contains("export interface Headers {\n  \"content-type\": string;\n}\n"));
+    }
+}
@@ -2,12 +2,13 @@ use std::rc::Rc;
 
 use ajars::web::AjarsClientWeb;
 use ajars::web::error::Error;
+use ajars::{AjarsError, NoError};
 use examples_common::ping::{PING, PingRequest, PingResponse};
 use yew::prelude::*;
 
 enum Msg {
     PingSend,
-    PingSetResponse(Result<PingResponse, Error>),
+    PingSetResponse(Result<PingResponse, AjarsError<NoError, Error>>),
 }
 
 struct Model {
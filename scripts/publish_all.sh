@@ -7,10 +7,14 @@ set -e
 
 declare -a publish_list=(
     "ajars_core"
+    "ajars_macros"
+    "ajars_openapi"
+    "ajars_typescript"
     "ajars_server_actix_web"
     "ajars_server_axum"
     "ajars_client_reqwest"
     "ajars_client_surf"
+    "ajars_client_tungstenite"
     "ajars_client_web"
     "."
 )
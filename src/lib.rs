@@ -10,6 +10,11 @@ pub mod axum {
     pub use ajars_server_axum::*;
 }
 
+#[cfg(feature = "openapi")]
+pub mod openapi {
+    pub use ajars_openapi::*;
+}
+
 #[cfg(feature = "reqwest")]
 pub mod reqwest {
     pub use ajars_client_reqwest::*;
@@ -20,9 +25,21 @@ pub mod surf {
     pub use ajars_client_surf::*;
 }
 
+#[cfg(feature = "tungstenite")]
+pub mod tungstenite {
+    pub use ajars_client_tungstenite::*;
+}
+
+#[cfg(feature = "typescript")]
+pub mod typescript {
+    pub use ajars_typescript::*;
+}
+
 #[cfg(feature = "web")]
 pub mod web {
     pub use ajars_client_web::*;
 }
 
 pub use ajars_core::*;
+#[cfg(feature = "macros")]
+pub use ajars_macros::service;